@@ -33,10 +33,12 @@ print(output) # 4
 
 """
 
-from typing import Optional
+from typing import Dict, List, Optional, Tuple, Union
+
+import pyarrow
 from pathlib import Path
 
-def row_count(file_path: Optional[Path], file_data: Optional[str], include_header_row: Optional[bool]) -> int:
+def row_count(file_path: Optional[Path], file_data: Optional[str], include_header_row: Optional[bool], encoding: Optional[str]) -> int:
     """Returns a count of the total number of rows.
     
     ## Arguments
@@ -44,13 +46,234 @@ def row_count(file_path: Optional[Path], file_data: Optional[str], include_heade
     * `file_path` - CSV file path.
     * `file_data` - CSV file data.
     * `include_header_row` - Specify whether to include the header row (first row) in the row count. Default is false.
+    * `encoding` - The character encoding of `file_path`, e.g. `latin1` or `utf-16le`, or `auto` to detect it. Default is `utf-8`.
     """
 
-def column_count(file_path: Optional[Path], file_data: Optional[str]) -> int:
+def column_count(file_path: Optional[Path], file_data: Optional[str], encoding: Optional[str]) -> int:
     """Returns a count of the total number of columns (fields).
     
     ## Arguments
     
     * `file_path` - CSV file path.
     * `file_data` - CSV file data.
+    * `encoding` - The character encoding of `file_path`, e.g. `latin1` or `utf-16le`, or `auto` to detect it. Default is `utf-8`.
     """
+
+def to_arrow(
+    file_path: Optional[Path],
+    file_data: Optional[str],
+    column_types: Optional[Dict[str, str]],
+    batch_size: Optional[int],
+    encoding: Optional[str],
+) -> pyarrow.Table:
+    """Returns the CSV data as a `pyarrow.Table`, passed from Rust without copying. Requires `pyarrow`.
+
+    Each column's type is inferred from its values unless it is named in `column_types`.
+
+    ## Arguments
+
+    * `file_path` - CSV file path.
+    * `file_data` - CSV file data.
+    * `column_types` - Column types by header name, each one of `boolean`, `integer`, `float` or `string`.
+    * `batch_size` - The maximum number of rows in a record batch. Default is 8192.
+    * `encoding` - The character encoding of `file_path`, e.g. `latin1` or `utf-16le`, or `auto` to detect it. Default is `utf-8`.
+    """
+
+def from_arrow(
+    data: Union[pyarrow.Table, pyarrow.RecordBatch, pyarrow.RecordBatchReader],
+) -> str:
+    """Returns CSV data converted from Arrow data. Requires `pyarrow`.
+
+    ## Arguments
+
+    * `data` - A `pyarrow.Table`, `pyarrow.RecordBatch` or `pyarrow.RecordBatchReader`.
+    """
+
+def to_parquet(
+    file_path: Optional[Path],
+    file_data: Optional[str],
+    column_types: Optional[Dict[str, str]],
+    compression: Optional[str],
+    row_group_size: Optional[int],
+    encoding: Optional[str],
+) -> bytes:
+    """Returns the CSV data converted to Parquet.
+
+    Each column's type is inferred from its values unless it is named in `column_types`.
+
+    ## Arguments
+
+    * `file_path` - CSV file path.
+    * `file_data` - CSV file data.
+    * `column_types` - Column types by header name, each one of `boolean`, `integer`, `float` or `string`.
+    * `compression` - One of `none`, `snappy`, `gzip`, `zstd`, `brotli` or `lz4`. Default is `snappy`.
+    * `row_group_size` - The maximum number of rows in a row group. Default is 1048576.
+    * `encoding` - The character encoding of `file_path`, e.g. `latin1` or `utf-16le`, or `auto` to detect it. Default is `utf-8`.
+    """
+
+def from_parquet(file_path: Optional[Path], file_data: Optional[bytes]) -> str:
+    """Returns CSV data converted from Parquet.
+
+    ## Arguments
+
+    * `file_path` - Parquet file path.
+    * `file_data` - Parquet file data.
+    """
+
+def from_excel(
+    file_path: Optional[Path],
+    file_data: Optional[bytes],
+    sheet_name: Optional[str],
+    sheet_index: Optional[int],
+    range: Optional[str],
+    date_format: Optional[str],
+) -> str:
+    """Returns a sheet of an Excel or OpenDocument workbook converted to CSV.
+
+    The first sheet's used range is read unless another sheet or range is selected, and its first row is the header row.
+
+    ## Arguments
+
+    * `file_path` - Workbook file path.
+    * `file_data` - Workbook file data.
+    * `sheet_name` - The name of the sheet to read (alternative to `sheet_index`).
+    * `sheet_index` - The 0-indexed position of the sheet to read (alternative to `sheet_name`).
+    * `range` - The cells to read in A1 notation, e.g. `A1:C10`, or a start cell such as `B3`.
+    * `date_format` - The chrono format string for date cells. Default is `%Y-%m-%d`, or `%Y-%m-%d %H:%M:%S` for dates with a time of day.
+    """
+
+def excel_sheets(
+    file_path: Optional[Path], file_data: Optional[bytes]
+) -> List[Tuple[str, int, int]]:
+    """Returns the name, row count and column count of each sheet in the workbook.
+
+    ## Arguments
+
+    * `file_path` - Workbook file path.
+    * `file_data` - Workbook file data.
+    """
+
+def table(
+    file_path: Optional[Path],
+    file_data: Optional[str],
+    format: Optional[str],
+    max_width: Optional[int],
+    encoding: Optional[str],
+) -> str:
+    """Returns the CSV data rendered as a table.
+
+    Numeric columns are right-aligned, and values are escaped for Markdown and HTML.
+
+    ## Arguments
+
+    * `file_path` - CSV file path.
+    * `file_data` - CSV file data.
+    * `format` - One of `text`, `markdown` or `html`. Default is `text`.
+    * `max_width` - The maximum width of a column in characters. Longer values are cut and end with `…`.
+    * `encoding` - The character encoding of `file_path`, e.g. `latin1` or `utf-16le`, or `auto` to detect it. Default is `utf-8`.
+    """
+
+def to_sqlite(
+    file_path: Optional[Path],
+    file_data: Optional[str],
+    database_path: Path,
+    table_name: Optional[str],
+    column_types: Optional[Dict[str, str]],
+    indexes: Optional[List[str]],
+    if_exists: Optional[str],
+    encoding: Optional[str],
+) -> int:
+    """Returns the number of rows inserted into a SQLite database table from the CSV data.
+
+    The table's column types are inferred, booleans are stored as `1` and `0`, and empty values as `NULL`.
+
+    ## Arguments
+
+    * `file_path` - CSV file path.
+    * `file_data` - CSV file data.
+    * `database_path` - SQLite database file path, created if it does not exist.
+    * `table_name` - The name of the table. Default is the file name without its extension, or `data` for `file_data`.
+    * `column_types` - Column types by header name (`boolean`, `integer`, `float` or `string`), overriding the inferred types.
+    * `indexes` - The header names of the columns to index.
+    * `if_exists` - One of `fail`, `replace` or `append`. Default is `fail`.
+    * `encoding` - The character encoding of `file_path`, e.g. `latin1` or `utf-16le`, or `auto` to detect it. Default is `utf-8`.
+    """
+
+def query(
+    file_paths: Optional[Dict[str, Path]],
+    file_data: Optional[Dict[str, str]],
+    sql: str,
+    encoding: Optional[str],
+) -> str:
+    """Returns the result of a SQL query over CSV data as CSV.
+
+    Each source is loaded into an in-memory SQLite table named by its key, and `NULL` values are written as empty values.
+
+    ## Arguments
+
+    * `file_paths` - CSV file paths by table name.
+    * `file_data` - CSV file data by table name.
+    * `sql` - A read-only SQL query, e.g. a `SELECT` with joins and `GROUP BY`.
+    * `encoding` - The character encoding of `file_paths`, e.g. `latin1` or `utf-16le`, or `auto` to detect it. Default is `utf-8`.
+    """
+
+def query_records(
+    file_paths: Optional[Dict[str, Path]],
+    file_data: Optional[Dict[str, str]],
+    sql: str,
+    encoding: Optional[str],
+) -> Tuple[List[str], List[List[Optional[str]]]]:
+    """Returns the column names and rows of a SQL query over CSV data, with `None` for `NULL` values.
+
+    ## Arguments
+
+    * `file_paths` - CSV file paths by table name.
+    * `file_data` - CSV file data by table name.
+    * `sql` - A read-only SQL query, e.g. a `SELECT` with joins and `GROUP BY`.
+    * `encoding` - The character encoding of `file_paths`, e.g. `latin1` or `utf-16le`, or `auto` to detect it. Default is `utf-8`.
+    """
+
+def map(
+    file_path: Optional[Path],
+    file_data: Optional[str],
+    script: str,
+    begin: Optional[str],
+    new_columns: Optional[List[str]],
+    encoding: Optional[str],
+) -> str:
+    """Returns the CSV data with each row passed through a Rhai script.
+
+    The script reads and assigns the row's fields in the `row` object map, and the row is dropped if the script evaluates to `false`.
+    Each row's script may perform at most 1,000,000 operations, and the output of `print` and `debug` is discarded.
+
+    ## Arguments
+
+    * `file_path` - CSV file path.
+    * `file_data` - CSV file data.
+    * `script` - The Rhai script run for each row, e.g. `row.total = row.price.parse_float() * 2`.
+    * `begin` - A Rhai script run once before the first row, whose variables keep their values across rows.
+    * `new_columns` - The header names of the columns added by the script.
+    * `encoding` - The character encoding of `file_path`, e.g. `latin1` or `utf-16le`, or `auto` to detect it. Default is `utf-8`.
+    """
+
+class CzvError(Exception):
+    """Base class for every error raised by czv."""
+
+class IoError(CzvError):
+    """Reading or writing a file failed, e.g. the file does not exist."""
+
+class ParseError(CzvError):
+    """The input data is malformed.
+
+    The `line`, `byte` and `record` attributes hold the position of the problem when it is known.
+    """
+
+    line: Optional[int]
+    byte: Optional[int]
+    record: Optional[int]
+
+class InvalidOptionsError(CzvError):
+    """The options passed to a function are missing or cannot be used together."""
+
+class ColumnNotFoundError(CzvError):
+    """A column selected by name does not exist in the header row."""
@@ -0,0 +1,80 @@
+use crate::CzvError;
+use arrow::array::{RecordBatch, RecordBatchIterator, RecordBatchReader};
+use arrow::ffi_stream::{ArrowArrayStreamReader, FFI_ArrowArrayStream};
+use czv::infer::ColumnType;
+use czv::{Encoding, ErrorPolicy};
+use pyo3::prelude::*;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Returns the CSV data as a `pyarrow.Table`, passed through the Arrow C stream interface without copying.
+#[pyfunction]
+pub fn to_arrow(
+    py: Python<'_>,
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    column_types: Option<HashMap<String, String>>,
+    batch_size: Option<usize>,
+    encoding: Option<&str>,
+) -> PyResult<PyObject> {
+    let column_types = column_types
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(name, column_type)| Ok((name, column_type.parse::<ColumnType>()?)))
+        .collect::<czv::Result<_>>()
+        .map_err(CzvError::from)?;
+    let encoding = encoding
+        .map_or(Ok(Encoding::default()), str::parse)
+        .map_err(CzvError::from)?;
+    let batches = czv::arrow::to_arrow(
+        file_path,
+        file_data,
+        column_types,
+        batch_size.unwrap_or(8192),
+        ErrorPolicy::Fail,
+        encoding,
+    )
+    .map_err(CzvError::from)?;
+    let schema = batches[0].schema();
+    let reader: Box<dyn RecordBatchReader + Send> = Box::new(RecordBatchIterator::new(
+        batches.into_iter().map(Ok),
+        schema,
+    ));
+    let mut stream = FFI_ArrowArrayStream::new(reader);
+    // pyarrow moves the stream out, leaving a released stream behind to be dropped.
+    let stream_ptr = std::ptr::addr_of_mut!(stream) as usize;
+    let pyarrow = py.import_bound("pyarrow")?;
+    let reader = pyarrow
+        .getattr("RecordBatchReader")?
+        .call_method1("_import_from_c", (stream_ptr,))?;
+    Ok(reader.call_method0("read_all")?.unbind())
+}
+
+/// Returns CSV data from a `pyarrow.Table`, `pyarrow.RecordBatch` or `pyarrow.RecordBatchReader`.
+#[pyfunction]
+pub fn from_arrow(py: Python<'_>, data: &Bound<'_, PyAny>) -> PyResult<String> {
+    let pyarrow = py.import_bound("pyarrow")?;
+    let mut data = data.clone();
+    if data.is_instance(&pyarrow.getattr("RecordBatch")?)? {
+        data = pyarrow
+            .getattr("Table")?
+            .call_method1("from_batches", (vec![data],))?;
+    }
+    if data.is_instance(&pyarrow.getattr("Table")?)? {
+        data = data.call_method0("to_reader")?;
+    }
+    let mut stream = FFI_ArrowArrayStream::empty();
+    let stream_ptr = std::ptr::addr_of_mut!(stream) as usize;
+    data.call_method1("_export_to_c", (stream_ptr,))?;
+    let reader = ArrowArrayStreamReader::try_new(stream)
+        .map_err(|err| CzvError::from(czv::CzvError::from(err)))?;
+    let schema = reader.schema();
+    let mut batches = reader
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| CzvError::from(czv::CzvError::from(err)))?;
+    // The header row comes from the first batch, so a stream without batches gets an empty one.
+    if batches.is_empty() {
+        batches.push(RecordBatch::new_empty(schema));
+    }
+    Ok(czv::arrow::from_arrow(Some(batches), None).map_err(CzvError::from)?)
+}
@@ -1,4 +1,5 @@
 use crate::Result;
+use czv::{Encoding, ErrorPolicy};
 use pyo3::pyfunction;
 use std::path::PathBuf;
 
@@ -7,15 +8,24 @@ pub fn row_count(
     file_path: Option<PathBuf>,
     file_data: Option<String>,
     include_header_row: Option<bool>,
+    encoding: Option<&str>,
 ) -> Result<usize> {
+    let encoding = encoding.map_or(Ok(Encoding::default()), str::parse)?;
     Ok(czv::count::row_count(
         file_path,
         file_data,
         include_header_row.unwrap_or(false),
+        ErrorPolicy::Fail,
+        encoding,
     )?)
 }
 
 #[pyfunction]
-pub fn column_count(file_path: Option<PathBuf>, file_data: Option<String>) -> Result<usize> {
-    Ok(czv::count::column_count(file_path, file_data)?)
+pub fn column_count(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    encoding: Option<&str>,
+) -> Result<usize> {
+    let encoding = encoding.map_or(Ok(Encoding::default()), str::parse)?;
+    Ok(czv::count::column_count(file_path, file_data, encoding)?)
 }
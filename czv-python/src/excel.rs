@@ -0,0 +1,35 @@
+use crate::Result;
+use pyo3::pyfunction;
+use std::path::PathBuf;
+
+#[pyfunction]
+pub fn from_excel(
+    file_path: Option<PathBuf>,
+    file_data: Option<&[u8]>,
+    sheet_name: Option<String>,
+    sheet_index: Option<usize>,
+    range: Option<String>,
+    date_format: Option<String>,
+) -> Result<String> {
+    Ok(czv::excel::from_excel(
+        file_path,
+        file_data.map(<[u8]>::to_vec),
+        sheet_name,
+        sheet_index,
+        range,
+        date_format,
+    )?)
+}
+
+#[pyfunction]
+pub fn excel_sheets(
+    file_path: Option<PathBuf>,
+    file_data: Option<&[u8]>,
+) -> Result<Vec<(String, usize, usize)>> {
+    Ok(
+        czv::excel::excel_sheets(file_path, file_data.map(<[u8]>::to_vec))?
+            .into_iter()
+            .map(|sheet| (sheet.name, sheet.rows, sheet.columns))
+            .collect(),
+    )
+}
@@ -0,0 +1,33 @@
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+
+create_exception!(
+    czv,
+    CzvError,
+    PyException,
+    "Base class for every error raised by czv."
+);
+create_exception!(
+    czv,
+    IoError,
+    CzvError,
+    "Reading or writing a file failed, e.g. the file does not exist."
+);
+create_exception!(
+    czv,
+    ParseError,
+    CzvError,
+    "The input data is malformed. The `line`, `byte` and `record` attributes hold the position of the problem when it is known."
+);
+create_exception!(
+    czv,
+    InvalidOptionsError,
+    CzvError,
+    "The options passed to a function are missing or cannot be used together."
+);
+create_exception!(
+    czv,
+    ColumnNotFoundError,
+    CzvError,
+    "A column selected by name does not exist in the header row."
+);
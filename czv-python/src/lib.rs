@@ -4,47 +4,100 @@ use pyo3::prelude::*;
 // Error-handling helpers
 #[derive(thiserror::Error, Debug)]
 #[error("{0}")]
-pub struct CzvError(anyhow::Error);
+pub struct CzvError(OGError);
 
 impl From<OGError> for CzvError {
     fn from(value: OGError) -> Self {
-        Self(anyhow::anyhow!(value))
+        Self(value)
     }
 }
 
 impl From<pyo3::PyErr> for CzvError {
     fn from(value: pyo3::PyErr) -> Self {
-        Self(anyhow::anyhow!(value))
+        Self(OGError::Other(anyhow::anyhow!(value)))
     }
 }
 
 impl From<csv::Error> for CzvError {
     fn from(value: csv::Error) -> Self {
-        Self(anyhow::anyhow!(value))
+        Self(value.into())
     }
 }
 
 impl From<CzvError> for pyo3::PyErr {
     fn from(value: CzvError) -> Self {
-        anyhow::anyhow!(value).into()
+        let message = value.to_string();
+        match value.0 {
+            OGError::Io(_) => exceptions::IoError::new_err(message),
+            OGError::Parse {
+                line, byte, record, ..
+            } => Python::with_gil(|py| {
+                let err = exceptions::ParseError::new_err(message);
+                let value = err.value_bound(py);
+                let _ = value.setattr("line", line);
+                let _ = value.setattr("byte", byte);
+                let _ = value.setattr("record", record);
+                err
+            }),
+            OGError::InvalidOptions(_) => exceptions::InvalidOptionsError::new_err(message),
+            OGError::ColumnNotFound(_) => exceptions::ColumnNotFoundError::new_err(message),
+            _ => exceptions::CzvError::new_err(message),
+        }
     }
 }
 
-pub type Result<T> = anyhow::Result<T, CzvError>;
+pub type Result<T> = std::result::Result<T, CzvError>;
 
 #[allow(unused_macros)]
 macro_rules! bail {
     ($err:expr $(,)?) => {
-        return Err(crate::CzvError(anyhow::anyhow!($err)))
+        return Err(crate::CzvError(::czv::CzvError::Other(anyhow::anyhow!(
+            $err
+        ))))
     };
 }
 
+// Exception classes
+pub mod exceptions;
+
 // Command imports
+pub mod arrow;
 pub mod count;
+pub mod excel;
+pub mod map;
+pub mod parquet;
+pub mod query;
+pub mod sqlite;
+pub mod table;
 
 #[pymodule]
 fn czv(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(count::row_count, m)?)?;
     m.add_function(wrap_pyfunction!(count::column_count, m)?)?;
+    m.add_function(wrap_pyfunction!(arrow::to_arrow, m)?)?;
+    m.add_function(wrap_pyfunction!(arrow::from_arrow, m)?)?;
+    m.add_function(wrap_pyfunction!(parquet::to_parquet, m)?)?;
+    m.add_function(wrap_pyfunction!(parquet::from_parquet, m)?)?;
+    m.add_function(wrap_pyfunction!(excel::from_excel, m)?)?;
+    m.add_function(wrap_pyfunction!(excel::excel_sheets, m)?)?;
+    m.add_function(wrap_pyfunction!(table::table, m)?)?;
+    m.add_function(wrap_pyfunction!(sqlite::to_sqlite, m)?)?;
+    m.add_function(wrap_pyfunction!(query::query, m)?)?;
+    m.add_function(wrap_pyfunction!(query::query_records, m)?)?;
+    m.add_function(wrap_pyfunction!(map::map, m)?)?;
+    m.add("CzvError", m.py().get_type_bound::<exceptions::CzvError>())?;
+    m.add("IoError", m.py().get_type_bound::<exceptions::IoError>())?;
+    m.add(
+        "ParseError",
+        m.py().get_type_bound::<exceptions::ParseError>(),
+    )?;
+    m.add(
+        "InvalidOptionsError",
+        m.py().get_type_bound::<exceptions::InvalidOptionsError>(),
+    )?;
+    m.add(
+        "ColumnNotFoundError",
+        m.py().get_type_bound::<exceptions::ColumnNotFoundError>(),
+    )?;
     Ok(())
 }
@@ -0,0 +1,23 @@
+use crate::Result;
+use czv::{Encoding, Map};
+use pyo3::pyfunction;
+use std::path::PathBuf;
+
+#[pyfunction]
+pub fn map(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    script: Option<String>,
+    begin: Option<String>,
+    new_columns: Option<Vec<String>>,
+    encoding: Option<&str>,
+) -> Result<String> {
+    Ok(Map::new()
+        .file_path_opt(file_path)
+        .file_data_opt(file_data)
+        .script_opt(script)
+        .begin_opt(begin)
+        .new_columns(new_columns.unwrap_or_default())
+        .encoding(encoding.map_or(Ok(Encoding::default()), str::parse)?)
+        .execute()?)
+}
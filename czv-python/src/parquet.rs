@@ -0,0 +1,59 @@
+use crate::Result;
+use czv::infer::ColumnType;
+use czv::parquet::ParquetCompression;
+use czv::{CzvError, Encoding, ErrorPolicy};
+use pyo3::pyfunction;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+fn compression_from(name: &str) -> Result<ParquetCompression> {
+    Ok(match name.to_ascii_lowercase().as_str() {
+        "none" | "uncompressed" => ParquetCompression::Uncompressed,
+        "snappy" => ParquetCompression::Snappy,
+        "gzip" => ParquetCompression::Gzip,
+        "zstd" => ParquetCompression::Zstd,
+        "brotli" => ParquetCompression::Brotli,
+        "lz4" => ParquetCompression::Lz4,
+        _ => {
+            return Err(CzvError::InvalidOptions(format!(
+                "Unknown compression {name:?}, expected none, snappy, gzip, zstd, brotli or lz4."
+            ))
+            .into())
+        }
+    })
+}
+
+#[pyfunction]
+pub fn to_parquet(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    column_types: Option<HashMap<String, String>>,
+    compression: Option<&str>,
+    row_group_size: Option<usize>,
+    encoding: Option<&str>,
+) -> Result<Cow<'static, [u8]>> {
+    let column_types = column_types
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(name, column_type)| Ok((name, column_type.parse::<ColumnType>()?)))
+        .collect::<Result<_>>()?;
+    let encoding = encoding.map_or(Ok(Encoding::default()), str::parse)?;
+    Ok(Cow::Owned(czv::parquet::to_parquet(
+        file_path,
+        file_data,
+        column_types,
+        compression.map_or(Ok(ParquetCompression::default()), compression_from)?,
+        row_group_size.unwrap_or(1024 * 1024),
+        ErrorPolicy::Fail,
+        encoding,
+    )?))
+}
+
+#[pyfunction]
+pub fn from_parquet(file_path: Option<PathBuf>, file_data: Option<&[u8]>) -> Result<String> {
+    Ok(czv::parquet::from_parquet(
+        file_path,
+        file_data.map(<[u8]>::to_vec),
+    )?)
+}
@@ -0,0 +1,39 @@
+use crate::Result;
+use czv::{Encoding, Query};
+use pyo3::pyfunction;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// The column names and rows of a query result.
+type Records = (Vec<String>, Vec<Vec<Option<String>>>);
+
+#[pyfunction]
+pub fn query(
+    file_paths: Option<HashMap<String, PathBuf>>,
+    file_data: Option<HashMap<String, String>>,
+    sql: Option<String>,
+    encoding: Option<&str>,
+) -> Result<String> {
+    Ok(Query::new()
+        .file_paths(file_paths.unwrap_or_default())
+        .file_data(file_data.unwrap_or_default())
+        .sql_opt(sql)
+        .encoding(encoding.map_or(Ok(Encoding::default()), str::parse)?)
+        .execute()?)
+}
+
+#[pyfunction]
+pub fn query_records(
+    file_paths: Option<HashMap<String, PathBuf>>,
+    file_data: Option<HashMap<String, String>>,
+    sql: Option<String>,
+    encoding: Option<&str>,
+) -> Result<Records> {
+    let records = Query::new()
+        .file_paths(file_paths.unwrap_or_default())
+        .file_data(file_data.unwrap_or_default())
+        .sql_opt(sql)
+        .encoding(encoding.map_or(Ok(Encoding::default()), str::parse)?)
+        .execute_to_records()?;
+    Ok((records.headers, records.rows))
+}
@@ -0,0 +1,40 @@
+use crate::Result;
+use czv::infer::ColumnType;
+use czv::sqlite::IfExists;
+use czv::{Encoding, ErrorPolicy};
+use pyo3::pyfunction;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+pub fn to_sqlite(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    database_path: Option<PathBuf>,
+    table_name: Option<String>,
+    column_types: Option<HashMap<String, String>>,
+    indexes: Option<Vec<String>>,
+    if_exists: Option<&str>,
+    encoding: Option<&str>,
+) -> Result<usize> {
+    let column_types = column_types
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(name, column_type)| Ok((name, column_type.parse::<ColumnType>()?)))
+        .collect::<Result<_>>()?;
+    let if_exists = if_exists.map_or(Ok(IfExists::default()), str::parse)?;
+    let encoding = encoding.map_or(Ok(Encoding::default()), str::parse)?;
+    Ok(czv::sqlite::to_sqlite(
+        file_path,
+        file_data,
+        database_path,
+        table_name,
+        column_types,
+        indexes.unwrap_or_default(),
+        if_exists,
+        10_000,
+        ErrorPolicy::Fail,
+        encoding,
+    )?)
+}
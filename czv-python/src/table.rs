@@ -0,0 +1,25 @@
+use crate::Result;
+use czv::table::TableFormat;
+use czv::{Encoding, ErrorPolicy};
+use pyo3::pyfunction;
+use std::path::PathBuf;
+
+#[pyfunction]
+pub fn table(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    format: Option<&str>,
+    max_width: Option<usize>,
+    encoding: Option<&str>,
+) -> Result<String> {
+    let format = format.map_or(Ok(TableFormat::default()), str::parse)?;
+    let encoding = encoding.map_or(Ok(Encoding::default()), str::parse)?;
+    Ok(czv::table::table(
+        file_path,
+        file_data,
+        format,
+        max_width,
+        ErrorPolicy::Fail,
+        encoding,
+    )?)
+}
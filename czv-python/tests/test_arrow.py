@@ -0,0 +1,31 @@
+import czv
+import pytest
+
+pyarrow = pytest.importorskip("pyarrow")
+
+
+class TestArrow:
+    def test_to_arrow(self):
+        """CSV data becomes a `pyarrow.Table` with inferred column types."""
+
+        table = czv.to_arrow(file_data="fruit,price,stock\napple,2.50,\n")
+        assert table.schema.field("price").type == pyarrow.float64()
+        assert table.column("stock").null_count == 1
+        assert table.column("fruit").to_pylist() == ["apple"]
+
+    def test_round_trip(self):
+        """Tables, record batches and readers convert back to CSV data."""
+
+        table = czv.to_arrow(
+            file_data="zip,price\n02134,2.50\n", column_types={"price": "string"}
+        )
+        expected = "zip,price\n02134,2.50\n"
+        assert czv.from_arrow(table) == expected
+        assert czv.from_arrow(table.to_batches()[0]) == expected
+        assert czv.from_arrow(table.to_reader()) == expected
+
+    def test_from_empty_table(self):
+        """A table without record batches still writes its header row."""
+
+        table = pyarrow.table({"fruit": pyarrow.array([], pyarrow.string())})
+        assert czv.from_arrow(table) == "fruit\n"
@@ -33,3 +33,16 @@ class TestColumnCount:
 
         result = czv.column_count(file_path=test_data[file_name])
         assert result == expected
+
+class TestEncoding:
+    def test_encoding(self, tmp_path):
+        """A Latin-1 file is read with its encoding, which fails as UTF-8."""
+
+        file_path = tmp_path / "latin1.csv"
+        file_path.write_bytes(b"fruit,caf\xe9\napple,2.50\n")
+        assert czv.column_count(file_path=file_path, encoding="latin1") == 2
+        assert czv.row_count(file_path=file_path, encoding="auto") == 1
+        with pytest.raises(czv.ParseError):
+            czv.column_count(file_path=file_path)
+        with pytest.raises(czv.InvalidOptionsError):
+            czv.row_count(file_path=file_path, encoding="bogus")
@@ -0,0 +1,32 @@
+import czv
+import pytest
+
+
+class TestErrors:
+    def test_io_error(self):
+        """A missing file raises an `IoError`."""
+
+        with pytest.raises(czv.IoError):
+            czv.row_count(file_path="does_not_exist.csv")
+
+    def test_parse_error(self, tmp_path):
+        """Invalid UTF-8 in the header row raises a `ParseError` with its position."""
+
+        file_path = tmp_path / "invalid_utf8.csv"
+        file_path.write_bytes(b"fruit,pr\xffice\napple,2.50\n")
+        with pytest.raises(czv.ParseError) as exc_info:
+            czv.column_count(file_path=file_path)
+        assert exc_info.value.line == 1
+        assert exc_info.value.record == 0
+
+    def test_invalid_options_error(self):
+        """Providing neither a file path nor file data raises an `InvalidOptionsError`."""
+
+        with pytest.raises(czv.InvalidOptionsError):
+            czv.row_count()
+
+    def test_base_class(self):
+        """Every error is a subclass of `CzvError`."""
+
+        with pytest.raises(czv.CzvError):
+            czv.row_count(file_path="does_not_exist.csv")
@@ -0,0 +1,37 @@
+import czv
+import pytest
+
+from .test_data import test_data
+
+
+class TestExcel:
+    def test_from_excel(self):
+        """The first sheet is converted with its date cells formatted."""
+
+        expected = (
+            "fruit,price,harvested,fresh\n"
+            "apple,2.5,2024-05-01,true\n"
+            "banana,3,2024-05-01 18:00:00,false\n"
+            "strawberry,1.5,,true\n"
+        )
+        assert czv.from_excel(file_path=test_data["fruits.xlsx"]) == expected
+
+    def test_sheet_and_range(self):
+        """A sheet and range may be selected from workbook data."""
+
+        file_data = test_data["fruits.xlsx"].read_bytes()
+        output = czv.from_excel(
+            file_data=file_data, sheet_name="fruits", range="A1:A2"
+        )
+        assert output == "fruit\napple\n"
+
+        with pytest.raises(czv.InvalidOptionsError):
+            czv.from_excel(file_data=file_data, sheet_name="vegetables")
+
+    def test_excel_sheets(self):
+        """Each sheet is listed with its row and column counts."""
+
+        assert czv.excel_sheets(file_path=test_data["fruits.xlsx"]) == [
+            ("fruits", 4, 4),
+            ("notes", 2, 2),
+        ]
@@ -0,0 +1,31 @@
+import czv
+import pytest
+
+from .test_data import test_data
+
+
+class TestMap:
+    def test_map(self):
+        """The script can add columns and keep state from the begin script."""
+
+        output = czv.map(
+            file_data="a\n1\n2\n3\n",
+            begin="let total = 0;",
+            script='total += row.a.parse_int(); row.total = total; row.a != "2"',
+            new_columns=["total"],
+        )
+        assert output == "a,total\n1,1\n3,6\n"
+
+    def test_map_file_path(self):
+        """Existing columns can be replaced."""
+
+        output = czv.map(
+            file_path=test_data["fruits.csv"], script="row.fruit = row.fruit.to_upper()"
+        )
+        assert output.splitlines()[1] == "APPLE,2.50"
+
+    def test_invalid_script(self):
+        """A script that does not compile is an invalid option."""
+
+        with pytest.raises(czv.InvalidOptionsError):
+            czv.map(file_data="a\n1\n", script="row.a = ")
@@ -0,0 +1,35 @@
+import czv
+import pytest
+
+from .test_data import test_data
+
+
+class TestParquet:
+    def test_round_trip(self):
+        """CSV data converted to Parquet and back keeps its values."""
+
+        parquet = czv.to_parquet(
+            file_data="zip,price\n02134,2.50\n",
+            column_types={"price": "string"},
+            compression="zstd",
+        )
+        assert isinstance(parquet, bytes)
+        assert czv.from_parquet(file_data=parquet) == "zip,price\n02134,2.50\n"
+
+    def test_file_path(self, tmp_path):
+        """A Parquet file written from a CSV file path can be read back from its path."""
+
+        file_path = tmp_path / "fruits.parquet"
+        file_path.write_bytes(
+            czv.to_parquet(
+                file_path=test_data["fruits.csv"], column_types={"price": "string"}
+            )
+        )
+        expected = "fruit,price\napple,2.50\nbanana,3.00\nstrawberry,1.50\n"
+        assert czv.from_parquet(file_path=file_path) == expected
+
+    def test_unknown_compression(self):
+        """An unknown compression codec raises an `InvalidOptionsError`."""
+
+        with pytest.raises(czv.InvalidOptionsError):
+            czv.to_parquet(file_data="a\n1\n", compression="lzo")
@@ -0,0 +1,31 @@
+import czv
+import pytest
+
+from .test_data import test_data
+
+
+class TestQuery:
+    def test_query(self):
+        """Tables from file paths and file data can be joined."""
+
+        output = czv.query(
+            file_paths={"fruits": test_data["fruits.csv"]},
+            file_data={"stock": "fruit,count\nbanana,4\n"},
+            sql="SELECT fruit, price, count FROM fruits JOIN stock USING (fruit)",
+        )
+        assert output == "fruit,price,count\nbanana,3.0,4\n"
+
+    def test_query_records(self):
+        """Records keep NULL values as None."""
+
+        headers, rows = czv.query_records(
+            file_data={"t": "a,b\n1,x\n2,\n"}, sql="SELECT a, b FROM t"
+        )
+        assert headers == ["a", "b"]
+        assert rows == [["1", "x"], ["2", None]]
+
+    def test_invalid_query(self):
+        """A statement that changes data is an invalid option."""
+
+        with pytest.raises(czv.InvalidOptionsError):
+            czv.query(file_data={"t": "a\n1\n"}, sql="DELETE FROM t")
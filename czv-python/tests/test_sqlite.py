@@ -0,0 +1,40 @@
+import sqlite3
+
+import czv
+import pytest
+
+from .test_data import test_data
+
+
+class TestToSqlite:
+    def test_to_sqlite(self, tmp_path):
+        """The rows are inserted into a table named after the file."""
+
+        database_path = tmp_path / "fruits.db"
+        inserted = czv.to_sqlite(
+            file_path=test_data["fruits.csv"],
+            database_path=database_path,
+            indexes=["fruit"],
+        )
+        assert inserted == 3
+        with sqlite3.connect(database_path) as conn:
+            rows = conn.execute("SELECT fruit, price FROM fruits").fetchall()
+        assert rows == [("apple", 2.5), ("banana", 3.0), ("strawberry", 1.5)]
+
+    def test_if_exists(self, tmp_path):
+        """An existing table is an error unless replaced or appended to."""
+
+        database_path = tmp_path / "data.db"
+        czv.to_sqlite(file_data="a\n1\n", database_path=database_path)
+        with pytest.raises(czv.InvalidOptionsError):
+            czv.to_sqlite(file_data="a\n1\n", database_path=database_path)
+        inserted = czv.to_sqlite(
+            file_data="a\n007\n",
+            database_path=database_path,
+            table_name="codes",
+            column_types={"a": "string"},
+            if_exists="append",
+        )
+        assert inserted == 1
+        with sqlite3.connect(database_path) as conn:
+            assert conn.execute("SELECT a FROM codes").fetchall() == [("007",)]
@@ -0,0 +1,32 @@
+import czv
+import pytest
+
+from .test_data import test_data
+
+
+class TestTable:
+    def test_text(self):
+        """The default table is aligned plain text."""
+
+        expected = (
+            "fruit       price\n"
+            "----------  -----\n"
+            "apple        2.50\n"
+            "banana       3.00\n"
+            "strawberry   1.50\n"
+        )
+        assert czv.table(file_path=test_data["fruits.csv"]) == expected
+
+    def test_markdown(self):
+        """A Markdown table cuts values to the maximum width."""
+
+        output = czv.table(
+            file_data="fruit,price\nstrawberry,1.50\n", format="markdown", max_width=6
+        )
+        assert output == "| fruit  | price |\n| ------ | ----: |\n| straw… |  1.50 |\n"
+
+    def test_unknown_format(self):
+        """An unknown format is an invalid option."""
+
+        with pytest.raises(czv.InvalidOptionsError):
+            czv.table(file_data="a\n1\n", format="csv")
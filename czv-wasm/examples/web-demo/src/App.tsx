@@ -11,6 +11,7 @@ function App() {
     const [columnCount, setColumnCount] = useState<number | undefined>(
         undefined
     );
+    const [tableHtml, setTableHtml] = useState<string | undefined>(undefined);
 
     const handleFile = async (e: React.ChangeEvent<HTMLInputElement>) => {
         setLoading(true);
@@ -27,6 +28,13 @@ function App() {
                 file_data: await e.target.files[0].text(),
             });
             setColumnCount(columnCountOutput);
+            // czv escapes the values, so the table is safe to insert as HTML
+            const tableOutput = czv.table({
+                file_data: await e.target.files[0].text(),
+                format: "html",
+                max_width: 40,
+            });
+            setTableHtml(tableOutput);
         }
         setLoading(false);
     };
@@ -72,6 +80,12 @@ function App() {
                         <strong>Column count</strong>: {columnCount}
                     </p>
                 )}
+                {tableHtml && (
+                    <div
+                        className="csv-table overflow-x-auto"
+                        dangerouslySetInnerHTML={{ __html: tableHtml }}
+                    />
+                )}
             </div>
         </>
     );
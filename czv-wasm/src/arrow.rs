@@ -0,0 +1,48 @@
+use crate::Result;
+use czv::infer::ColumnType;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tsify_next::Tsify;
+use wasm_bindgen::prelude::*;
+
+/// Options for `toArrowIPC`.
+#[derive(Tsify, Serialize, Deserialize)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct ToArrowIpcOptions {
+    /// CSV file data.
+    pub file_data: String,
+
+    #[tsify(optional)]
+    /// Column types by header name, each one of `boolean`, `integer`, `float` or `string`.
+    /// Other columns have their type inferred from their values.
+    pub column_types: Option<HashMap<String, String>>,
+
+    #[tsify(optional)]
+    /// The maximum number of rows in a record batch.
+    /// Default is 8192.
+    pub batch_size: Option<usize>,
+}
+
+/// Returns the CSV data as an Arrow IPC stream, e.g. for `tableFromIPC` in apache-arrow.
+#[wasm_bindgen(skip_jsdoc, js_name = toArrowIPC)]
+pub fn to_arrow_ipc(options: ToArrowIpcOptions) -> Result<Vec<u8>> {
+    let column_types = options
+        .column_types
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(name, column_type)| Ok((name, column_type.parse::<ColumnType>()?)))
+        .collect::<std::result::Result<Vec<_>, czv::CzvError>>()?;
+    let mut output = vec![];
+    czv::ToArrow::new()
+        .file_data(options.file_data)
+        .column_types(column_types)
+        .batch_size(options.batch_size.unwrap_or(8192))
+        .execute_to_ipc(&mut output)?;
+    Ok(output)
+}
+
+/// Returns CSV data converted from an Arrow IPC stream, e.g. from `tableToIPC` in apache-arrow.
+#[wasm_bindgen(skip_jsdoc, js_name = fromArrowIPC)]
+pub fn from_arrow_ipc(ipc_data: &[u8]) -> Result<String> {
+    Ok(czv::FromArrow::new().ipc_data(ipc_data).execute()?)
+}
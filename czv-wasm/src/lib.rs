@@ -46,27 +46,112 @@ use wasm_bindgen::JsValue;
 #[derive(thiserror::Error, Debug)]
 pub enum CzvError {
     #[error("{0}")]
-    GeneralError(#[from] anyhow::Error),
+    Io(#[from] std::io::Error),
+    #[error("{message}")]
+    Parse {
+        message: String,
+        line: Option<u64>,
+        byte: Option<u64>,
+        record: Option<u64>,
+    },
     #[error("{0}")]
-    CsvError(#[from] csv::Error),
+    InvalidOptions(String),
+    #[error("Column not found: {0}")]
+    ColumnNotFound(String),
     #[error("{0}")]
-    SerdeWasmBindgenError(#[from] serde_wasm_bindgen::Error),
+    Other(#[from] anyhow::Error),
 }
 
+impl CzvError {
+    /// Returns the value of the `kind` field set on the JS `Error`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Io(_) => "io",
+            Self::Parse { .. } => "parse",
+            Self::InvalidOptions(_) => "invalid_options",
+            Self::ColumnNotFound(_) => "column_not_found",
+            Self::Other(_) => "other",
+        }
+    }
+}
+
+impl From<csv::Error> for CzvError {
+    fn from(value: csv::Error) -> Self {
+        let message = value.to_string();
+        let position = value.position().cloned();
+        match value.into_kind() {
+            csv::ErrorKind::Io(err) => Self::Io(err),
+            _ => Self::Parse {
+                message,
+                line: position.as_ref().map(|pos| pos.line()),
+                byte: position.as_ref().map(|pos| pos.byte()),
+                record: position.as_ref().map(|pos| pos.record()),
+            },
+        }
+    }
+}
+
+impl From<czv::CzvError> for CzvError {
+    fn from(value: czv::CzvError) -> Self {
+        match value {
+            czv::CzvError::Io(err) => Self::Io(err),
+            czv::CzvError::Parse {
+                message,
+                line,
+                byte,
+                record,
+            } => Self::Parse {
+                message,
+                line,
+                byte,
+                record,
+            },
+            czv::CzvError::InvalidOptions(message) => Self::InvalidOptions(message),
+            czv::CzvError::ColumnNotFound(name) => Self::ColumnNotFound(name),
+            err => Self::Other(anyhow::anyhow!(err.to_string())),
+        }
+    }
+}
+
+impl From<serde_wasm_bindgen::Error> for CzvError {
+    fn from(value: serde_wasm_bindgen::Error) -> Self {
+        Self::InvalidOptions(value.to_string())
+    }
+}
+
+/// Converts into a JS `Error` with a `kind` field, plus `line`, `byte` and `record` fields for parse errors.
 impl From<CzvError> for JsValue {
     fn from(val: CzvError) -> Self {
-        JsValue::from_str(val.to_string().as_str())
+        let err = js_sys::Error::new(val.to_string().as_str());
+        let set = |key: &str, value: JsValue| {
+            let _ = js_sys::Reflect::set(&err, &JsValue::from_str(key), &value);
+        };
+        set("kind", JsValue::from_str(val.kind()));
+        if let CzvError::Parse {
+            line, byte, record, ..
+        } = val
+        {
+            let position = |value: Option<u64>| {
+                value.map_or(JsValue::UNDEFINED, |v| JsValue::from_f64(v as f64))
+            };
+            set("line", position(line));
+            set("byte", position(byte));
+            set("record", position(record));
+        }
+        err.into()
     }
 }
 
-pub type Result<T> = anyhow::Result<T, CzvError>;
+pub type Result<T> = std::result::Result<T, CzvError>;
 
 #[allow(unused_macros)]
 macro_rules! bail {
     ($err:expr $(,)?) => {
-        return Err(crate::CzvError(anyhow::anyhow!($err)))
+        return Err(crate::CzvError::Other(anyhow::anyhow!($err)))
     };
 }
 
 // Command imports
+pub mod arrow;
 pub mod count;
+pub mod table;
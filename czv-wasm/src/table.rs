@@ -0,0 +1,40 @@
+use crate::Result;
+use czv::table::TableFormat;
+use serde::{Deserialize, Serialize};
+use tsify_next::Tsify;
+use wasm_bindgen::prelude::*;
+
+/// Options for `table`.
+#[derive(Tsify, Serialize, Deserialize)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct TableOptions {
+    /// CSV file data.
+    pub file_data: String,
+
+    #[tsify(optional)]
+    /// The format of the table, one of `text`, `markdown` or `html`.
+    /// Default is `text`.
+    pub format: Option<String>,
+
+    #[tsify(optional)]
+    /// The maximum width of a column in characters. Longer values are cut and end with `…`.
+    /// Default is no maximum.
+    pub max_width: Option<usize>,
+}
+
+/// Returns the CSV data rendered as an aligned text, Markdown or HTML table.
+#[wasm_bindgen(skip_jsdoc)]
+pub fn table(options: TableOptions) -> Result<String> {
+    let format = options
+        .format
+        .as_deref()
+        .map_or(Ok(TableFormat::default()), str::parse)?;
+    Ok(czv::table::table(
+        None,
+        Some(options.file_data),
+        format,
+        options.max_width,
+        czv::ErrorPolicy::Fail,
+        czv::Encoding::Utf8,
+    )?)
+}
@@ -0,0 +1,33 @@
+use czv_wasm::arrow::ToArrowIpcOptions;
+use czv_wasm::{CzvError, Result};
+use std::collections::HashMap;
+use wasm_bindgen_test::*;
+
+#[test]
+#[wasm_bindgen_test]
+fn arrow_ipc_round_trip() -> Result<()> {
+    let options = ToArrowIpcOptions {
+        file_data: "zip,price
+02134,2.50
+10001,"
+            .to_string(),
+        column_types: None,
+        batch_size: None,
+    };
+    let ipc_data = czv_wasm::arrow::to_arrow_ipc(options)?;
+    let result = czv_wasm::arrow::from_arrow_ipc(&ipc_data)?;
+    assert_eq!(result, "zip,price\n02134,2.5\n10001,\n");
+    Ok(())
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn arrow_ipc_column_types() {
+    let options = ToArrowIpcOptions {
+        file_data: "a\n1\n".to_string(),
+        column_types: Some(HashMap::from([("b".to_string(), "string".to_string())])),
+        batch_size: None,
+    };
+    let result = czv_wasm::arrow::to_arrow_ipc(options);
+    assert!(matches!(result, Err(CzvError::ColumnNotFound(_))));
+}
@@ -1,4 +1,3 @@
-use czv_wasm;
 use czv_wasm::count::{ColumnCountOptions, RowCountOptions};
 use czv_wasm::Result;
 use wasm_bindgen_test::*;
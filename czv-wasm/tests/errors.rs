@@ -0,0 +1,21 @@
+use czv_wasm::CzvError;
+use wasm_bindgen_test::*;
+
+#[test]
+#[wasm_bindgen_test]
+fn csv_error_is_parse_error() {
+    let err = csv::Reader::from_reader("fruit,price\napple".as_bytes())
+        .records()
+        .next()
+        .unwrap()
+        .unwrap_err();
+    let err = CzvError::from(err);
+    assert_eq!(err.kind(), "parse");
+    match err {
+        CzvError::Parse { line, record, .. } => {
+            assert_eq!(line, Some(2));
+            assert_eq!(record, Some(1));
+        }
+        _ => panic!("expected a parse error"),
+    }
+}
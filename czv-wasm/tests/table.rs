@@ -0,0 +1,28 @@
+use czv_wasm::table::TableOptions;
+use czv_wasm::{CzvError, Result};
+use wasm_bindgen_test::*;
+
+#[test]
+#[wasm_bindgen_test]
+fn table_html() -> Result<()> {
+    let options = TableOptions {
+        file_data: "fruit,price\n<kiwi>,1.50\n".to_string(),
+        format: Some("html".to_string()),
+        max_width: None,
+    };
+    let result = czv_wasm::table::table(options)?;
+    assert!(result.contains("<td>&lt;kiwi&gt;</td><td style=\"text-align: right\">1.50</td>"));
+    Ok(())
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn table_unknown_format() {
+    let options = TableOptions {
+        file_data: "a\n1\n".to_string(),
+        format: Some("csv".to_string()),
+        max_width: None,
+    };
+    let result = czv_wasm::table::table(options);
+    assert!(matches!(result, Err(CzvError::InvalidOptions(_))));
+}
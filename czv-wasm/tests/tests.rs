@@ -1 +1,4 @@
+mod arrow;
 mod count;
+mod errors;
+mod table;
@@ -1,6 +1,5 @@
 use criterion::{black_box, criterion_group, Criterion};
-use czv;
-use czv::Result;
+use czv::{Encoding, ErrorPolicy, Result};
 use std::path::PathBuf;
 
 // macro_rules! get_test_files {
@@ -17,7 +16,13 @@ use std::path::PathBuf;
 // }
 
 fn bench_row_count(file_path: PathBuf) -> Result<()> {
-    czv::count::row_count(Some(file_path), None, false)?;
+    czv::count::row_count(
+        Some(file_path),
+        None,
+        false,
+        ErrorPolicy::Fail,
+        Encoding::Utf8,
+    )?;
     Ok(())
 }
 
@@ -37,7 +42,7 @@ fn bench_row_count_group(c: &mut Criterion) {
 }
 
 fn bench_column_count(file_path: PathBuf) -> Result<()> {
-    czv::count::column_count(Some(file_path), None)?;
+    czv::count::column_count(Some(file_path), None, Encoding::Utf8)?;
     Ok(())
 }
 
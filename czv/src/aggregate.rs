@@ -0,0 +1,578 @@
+use crate::encoding::Encoding;
+use crate::policy::{ErrorPolicy, Report, Skipper};
+use crate::util::{column_index, column_indexes, into_string, reader_from, writer};
+use crate::{CzvError, Result};
+use csv::{ReaderBuilder, StringRecord};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::str::FromStr;
+use typed_builder::TypedBuilder;
+
+/// A function summarizing the values of a column in each group.
+///
+/// Every function except [`Count`](AggregateFunction::Count) without a column ignores empty values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AggregateFunction {
+    /// The number of rows, or of non-empty values when a column is given.
+    Count,
+    /// The sum of the values, an integer if every value is an integer.
+    Sum,
+    /// The arithmetic mean of the values.
+    Mean,
+    /// The smallest value, compared as numbers if every value is a number and as text otherwise.
+    Min,
+    /// The largest value, compared as numbers if every value is a number and as text otherwise.
+    Max,
+    /// The first value.
+    First,
+    /// The last value.
+    Last,
+    /// The number of distinct values.
+    DistinctCount,
+    /// The values joined with a separator.
+    Concat,
+}
+
+impl AggregateFunction {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Count => "count",
+            Self::Sum => "sum",
+            Self::Mean => "mean",
+            Self::Min => "min",
+            Self::Max => "max",
+            Self::First => "first",
+            Self::Last => "last",
+            Self::DistinctCount => "distinct_count",
+            Self::Concat => "concat",
+        }
+    }
+}
+
+impl FromStr for AggregateFunction {
+    type Err = CzvError;
+
+    /// Parses a function name, e.g. `distinct_count`.
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "count" => Ok(Self::Count),
+            "sum" => Ok(Self::Sum),
+            "mean" => Ok(Self::Mean),
+            "min" => Ok(Self::Min),
+            "max" => Ok(Self::Max),
+            "first" => Ok(Self::First),
+            "last" => Ok(Self::Last),
+            "distinct_count" => Ok(Self::DistinctCount),
+            "concat" => Ok(Self::Concat),
+            _ => Err(CzvError::InvalidOptions(format!(
+                "Unknown aggregate function {s:?}, expected count, sum, mean, min, max, first, last, distinct_count or concat."
+            ))),
+        }
+    }
+}
+
+/// An aggregate function applied to a column, written as the output column `{function}_{column}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Aggregation {
+    /// The aggregate function.
+    pub function: AggregateFunction,
+    /// The header name of the column, or `None` to count rows with [`AggregateFunction::Count`].
+    pub column: Option<String>,
+}
+
+impl Aggregation {
+    /// Returns `function` applied to `column`.
+    pub fn new(function: AggregateFunction, column: impl Into<String>) -> Self {
+        Self {
+            function,
+            column: Some(column.into()),
+        }
+    }
+
+    /// Returns the count of rows, written as the output column `count`.
+    pub fn count() -> Self {
+        Self {
+            function: AggregateFunction::Count,
+            column: None,
+        }
+    }
+
+    /// Returns a parse error if `value` cannot be summed or averaged by this aggregation.
+    pub(crate) fn check(&self, value: &str, record: &StringRecord) -> Result<()> {
+        match self.function {
+            AggregateFunction::Sum | AggregateFunction::Mean
+                if !value.is_empty() && number(value).is_none() =>
+            {
+                Err(CzvError::parse_at(
+                    format!(
+                        "Value {value:?} in column {:?} is not a number.",
+                        self.column.as_deref().unwrap_or_default()
+                    ),
+                    record,
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn header(&self) -> String {
+        match &self.column {
+            Some(column) => format!("{}_{column}", self.function.name()),
+            None => self.function.name().to_string(),
+        }
+    }
+}
+
+impl FromStr for Aggregation {
+    type Err = CzvError;
+
+    /// Parses `function(column)`, e.g. `sum(price)`, or `count` to count rows.
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("count") || s.eq_ignore_ascii_case("count(*)") {
+            return Ok(Self::count());
+        }
+        let (function, column) = s
+            .strip_suffix(')')
+            .and_then(|s| s.split_once('('))
+            .ok_or_else(|| {
+                CzvError::InvalidOptions(format!("Expected function(column) but found {s:?}."))
+            })?;
+        Ok(Self::new(function.trim().parse()?, column))
+    }
+}
+
+/// Returns `value` as a finite number.
+fn number(value: &str) -> Option<f64> {
+    value.parse().ok().filter(|n: &f64| n.is_finite())
+}
+
+/// The running state of an aggregation in one group.
+pub(crate) enum Accumulator {
+    Rows(u64),
+    Count(u64),
+    Sum {
+        integer: Option<i64>,
+        float: f64,
+        count: u64,
+    },
+    Mean {
+        sum: f64,
+        count: u64,
+    },
+    Extreme {
+        max: bool,
+        number: Option<(f64, String)>,
+        text: Option<String>,
+        numeric: bool,
+    },
+    First(Option<String>),
+    Last(Option<String>),
+    Distinct(HashSet<String>),
+    Concat(Vec<String>),
+}
+
+impl Accumulator {
+    pub(crate) fn new(aggregation: &Aggregation) -> Self {
+        match (aggregation.function, &aggregation.column) {
+            (AggregateFunction::Count, None) => Self::Rows(0),
+            (AggregateFunction::Count, Some(_)) => Self::Count(0),
+            (AggregateFunction::Sum, _) => Self::Sum {
+                integer: Some(0),
+                float: 0.0,
+                count: 0,
+            },
+            (AggregateFunction::Mean, _) => Self::Mean { sum: 0.0, count: 0 },
+            (function @ (AggregateFunction::Min | AggregateFunction::Max), _) => Self::Extreme {
+                max: function == AggregateFunction::Max,
+                number: None,
+                text: None,
+                numeric: true,
+            },
+            (AggregateFunction::First, _) => Self::First(None),
+            (AggregateFunction::Last, _) => Self::Last(None),
+            (AggregateFunction::DistinctCount, _) => Self::Distinct(HashSet::new()),
+            (AggregateFunction::Concat, _) => Self::Concat(vec![]),
+        }
+    }
+
+    /// Adds a value, which has been checked to be a number for sums and means.
+    pub(crate) fn update(&mut self, value: &str) {
+        match self {
+            Self::Rows(count) => *count += 1,
+            _ if value.is_empty() => {}
+            Self::Count(count) => *count += 1,
+            Self::Sum {
+                integer,
+                float,
+                count,
+            } => {
+                *integer = integer
+                    .zip(value.parse::<i64>().ok())
+                    .and_then(|(sum, n)| sum.checked_add(n));
+                *float += number(value).unwrap_or_default();
+                *count += 1;
+            }
+            Self::Mean { sum, count } => {
+                *sum += number(value).unwrap_or_default();
+                *count += 1;
+            }
+            Self::Extreme {
+                max,
+                number: best_number,
+                text,
+                numeric,
+            } => {
+                let ordering = if *max {
+                    std::cmp::Ordering::Greater
+                } else {
+                    std::cmp::Ordering::Less
+                };
+                match number(value) {
+                    Some(n) if *numeric => {
+                        if best_number
+                            .as_ref()
+                            .is_none_or(|(best, _)| n.total_cmp(best) == ordering)
+                        {
+                            *best_number = Some((n, value.to_string()));
+                        }
+                    }
+                    Some(_) => {}
+                    None => *numeric = false,
+                }
+                if text
+                    .as_deref()
+                    .is_none_or(|best| value.cmp(best) == ordering)
+                {
+                    *text = Some(value.to_string());
+                }
+            }
+            Self::First(first) => {
+                if first.is_none() {
+                    *first = Some(value.to_string());
+                }
+            }
+            Self::Last(last) => *last = Some(value.to_string()),
+            Self::Distinct(values) => {
+                if !values.contains(value) {
+                    values.insert(value.to_string());
+                }
+            }
+            Self::Concat(values) => values.push(value.to_string()),
+        }
+    }
+
+    /// Returns the aggregated value, empty if the group has no values.
+    pub(crate) fn finish(&self, separator: &str) -> String {
+        match self {
+            Self::Rows(count) | Self::Count(count) => count.to_string(),
+            Self::Sum { count: 0, .. } | Self::Mean { count: 0, .. } => String::new(),
+            Self::Sum {
+                integer: Some(sum), ..
+            } => sum.to_string(),
+            Self::Sum { float, .. } => format!("{float:?}"),
+            Self::Mean { sum, count } => format!("{:?}", sum / *count as f64),
+            Self::Extreme {
+                number,
+                text,
+                numeric,
+                ..
+            } => match numeric {
+                true => number.as_ref().map(|(_, value)| value.clone()),
+                false => text.clone(),
+            }
+            .unwrap_or_default(),
+            Self::First(value) | Self::Last(value) => value.clone().unwrap_or_default(),
+            Self::Distinct(values) => values.len().to_string(),
+            Self::Concat(values) => values.join(separator),
+        }
+    }
+}
+
+/// Returns the grouped and aggregated CSV data. Recommended alternative: [`czv::aggregate::Aggregate`](crate::aggregate::Aggregate).
+///
+/// See [`czv::aggregate::Aggregate`](crate::aggregate::Aggregate) for a builder version (recommended) of this function.
+///
+/// ## Notes
+///
+/// - The output has the `group_by` columns followed by a column for each of `aggregations`, and
+///   a row for each distinct combination of `group_by` values in the order they first appear.
+/// - Aggregations are written as columns named after their function and column, e.g. `sum_price`,
+///   or `count` for a count of rows. Output columns with the same name are an invalid option.
+/// - Without `group_by`, the output has a single row aggregating every record.
+/// - Records are read one at a time, and only the state of each group is kept in memory.
+/// - Sums and means of groups without values are empty. Float sums and means are written with
+///   at least one decimal place, e.g. `7.0`.
+/// - Records with a non-numeric value in a column that is summed or averaged are malformed. They
+///   are left out of every aggregation when skipped with `error_policy`.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+///
+/// ## Arguments
+///
+/// * `file_path` - CSV file path (alternative to `file_data`).
+/// * `file_data` - CSV file data (alternative to `file_path`).
+/// * `group_by` - The header names of the key columns.
+/// * `aggregations` - The aggregations computed for each group.
+/// * `separator` - The separator of values joined with [`AggregateFunction::Concat`].
+/// * `error_policy` - How to handle malformed records.
+/// * `encoding` - The character encoding of `file_path`.
+pub fn aggregate(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    group_by: Vec<String>,
+    aggregations: Vec<Aggregation>,
+    separator: String,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<String> {
+    Ok(aggregate_with_report(
+        file_path,
+        file_data,
+        group_by,
+        aggregations,
+        separator,
+        error_policy,
+        encoding,
+    )?
+    .output)
+}
+
+fn aggregate_with_report(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    group_by: Vec<String>,
+    aggregations: Vec<Aggregation>,
+    separator: String,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<Report<String>> {
+    if group_by.is_empty() && aggregations.is_empty() {
+        return Err(CzvError::InvalidOptions(
+            "Must provide group_by or aggregations.".to_string(),
+        ));
+    }
+    let rdr = ReaderBuilder::new();
+    let mut rdr = reader_from(&rdr, file_path.as_deref(), file_data.as_deref(), encoding)?;
+    let headers = rdr.headers()?.clone();
+    let keys = column_indexes(&headers, &group_by)?;
+    let mut names = HashSet::new();
+    if let Some(name) = group_by
+        .iter()
+        .cloned()
+        .chain(aggregations.iter().map(Aggregation::header))
+        .find(|name| !names.insert(name.clone()))
+    {
+        return Err(CzvError::InvalidOptions(format!(
+            "Output column {name:?} is written more than once."
+        )));
+    }
+    // Rows are counted from the first column, which every record has.
+    let columns: Vec<usize> = aggregations
+        .iter()
+        .map(|aggregation| match &aggregation.column {
+            Some(name) => column_index(&headers, name),
+            None => Ok(0),
+        })
+        .collect::<Result<_>>()?;
+    let new_group = || -> Vec<Accumulator> { aggregations.iter().map(Accumulator::new).collect() };
+    let mut indexes: HashMap<Vec<String>, usize> = HashMap::new();
+    let mut groups: Vec<(Vec<String>, Vec<Accumulator>)> = vec![];
+    if keys.is_empty() {
+        indexes.insert(vec![], 0);
+        groups.push((vec![], new_group()));
+    }
+
+    let mut skipper = Skipper::new(error_policy);
+    let mut record = StringRecord::new();
+    while skipper.read_record(&mut rdr, &mut record)? {
+        if let Err(err) = columns
+            .iter()
+            .zip(&aggregations)
+            .try_for_each(|(column, aggregation)| {
+                aggregation.check(record.get(*column).unwrap_or_default(), &record)
+            })
+        {
+            skipper.skip(err)?;
+            continue;
+        }
+        let key: Vec<String> = keys
+            .iter()
+            .map(|i| record.get(*i).unwrap_or_default().to_string())
+            .collect();
+        let index = match indexes.get(&key) {
+            Some(index) => *index,
+            None => {
+                indexes.insert(key.clone(), groups.len());
+                groups.push((key, new_group()));
+                groups.len() - 1
+            }
+        };
+        for (accumulator, column) in groups[index].1.iter_mut().zip(&columns) {
+            accumulator.update(record.get(*column).unwrap_or_default());
+        }
+    }
+
+    let mut wtr = writer();
+    wtr.write_record(
+        group_by
+            .iter()
+            .cloned()
+            .chain(aggregations.iter().map(Aggregation::header)),
+    )?;
+    for (key, accumulators) in &groups {
+        wtr.write_record(
+            key.iter().cloned().chain(
+                accumulators
+                    .iter()
+                    .map(|accumulator| accumulator.finish(&separator)),
+            ),
+        )?;
+    }
+    Ok(skipper.finish(into_string(wtr)?))
+}
+
+#[derive(TypedBuilder)]
+#[builder(doc, builder_method(name=new, doc="Returns an [`AggregateBuilder`](crate::aggregate::AggregateBuilder) to customize aggregation options by running other methods before getting the output with the [`execute`](crate::aggregate::AggregateBuilder::execute) method."), build_method(vis="", name=__build))]
+/// Returns the CSV data grouped by key columns with aggregate values for each group.
+///
+/// The entry point for setting up a [`czv::aggregate::AggregateBuilder`](crate::aggregate::AggregateBuilder) by running [`Aggregate::new()`](crate::aggregate::Aggregate::new).
+///
+/// # Example
+///
+/// ```rust
+/// use czv::aggregate::{AggregateFunction, Aggregation};
+/// use czv::{Aggregate, Result};
+///
+/// fn main() -> Result<()> {
+///     let data = "\
+/// store,fruit,count
+/// north,apple,3
+/// south,apple,2
+/// north,banana,4
+/// ";
+///     let output = Aggregate::new()
+///         .file_data(data)
+///         .group_by(["store".to_string()])
+///         .aggregations([
+///             Aggregation::count(),
+///             Aggregation::new(AggregateFunction::Sum, "count"),
+///             Aggregation::new(AggregateFunction::Concat, "fruit"),
+///         ])
+///         .separator(";")
+///         .execute()?;
+///     assert_eq!(
+///         output,
+///         "\
+/// store,count,sum_count,concat_fruit
+/// north,2,7,apple;banana
+/// south,1,2,apple
+/// "
+///     );
+///     Ok(())
+/// }
+/// ```
+///
+/// ## Notes
+///
+/// - See [`aggregate`](crate::aggregate::aggregate) for how groups are formed and written.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+pub struct Aggregate {
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file path (alternative to `file_data`).",
+            strip_option(fallback = file_path_opt),
+            into
+        )
+    )]
+    file_path: Option<PathBuf>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file data (alternative to `file_path`).",
+            strip_option(fallback = file_data_opt),
+            into
+        )
+    )]
+    file_data: Option<String>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "The header names of the key columns. Defaults to a single group of every record.",
+            transform = |group_by: impl IntoIterator<Item = String>| group_by.into_iter().collect()
+        )
+    )]
+    group_by: Vec<String>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "The aggregations computed for each group.",
+            transform = |aggregations: impl IntoIterator<Item = Aggregation>| aggregations.into_iter().collect()
+        )
+    )]
+    aggregations: Vec<Aggregation>,
+
+    #[builder(
+        default = ",".to_string(),
+        setter(
+            doc = "The separator of values joined with `AggregateFunction::Concat`. Defaults to `,`.",
+            into
+        )
+    )]
+    separator: String,
+
+    #[builder(
+        default,
+        setter(doc = "How to handle malformed records. Defaults to `ErrorPolicy::Fail`.")
+    )]
+    error_policy: ErrorPolicy,
+
+    #[builder(
+        default,
+        setter(doc = "The character encoding of `file_path`. Defaults to `Encoding::Utf8`.")
+    )]
+    encoding: Encoding,
+}
+
+#[allow(non_camel_case_types)]
+impl<
+        __encoding: typed_builder::Optional<Encoding>,
+        __error_policy: typed_builder::Optional<ErrorPolicy>,
+        __separator: typed_builder::Optional<String>,
+        __aggregations: typed_builder::Optional<Vec<Aggregation>>,
+        __group_by: typed_builder::Optional<Vec<String>>,
+        __file_data: typed_builder::Optional<Option<String>>,
+        __file_path: typed_builder::Optional<Option<PathBuf>>,
+    >
+    AggregateBuilder<(
+        __file_path,
+        __file_data,
+        __group_by,
+        __aggregations,
+        __separator,
+        __error_policy,
+        __encoding,
+    )>
+{
+    /// Returns the aggregated CSV data.
+    pub fn execute(self) -> Result<String> {
+        Ok(self.execute_with_report()?.output)
+    }
+
+    /// Returns the aggregated CSV data along with the records skipped with `ErrorPolicy::Collect`.
+    pub fn execute_with_report(self) -> Result<Report<String>> {
+        let builder = self.__build();
+        aggregate_with_report(
+            builder.file_path,
+            builder.file_data,
+            builder.group_by,
+            builder.aggregations,
+            builder.separator,
+            builder.error_policy,
+            builder.encoding,
+        )
+    }
+}
@@ -0,0 +1,593 @@
+use crate::encoding::Encoding;
+use crate::policy::{ErrorPolicy, Report, Skipper};
+use crate::util::{column_indexes, into_string, reader_from, writer};
+use crate::{CzvError, Result};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use csv::{ReaderBuilder, StringRecord};
+use md5::Md5;
+use regex::Regex;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::str::FromStr;
+use typed_builder::TypedBuilder;
+
+/// The input formats tried in order when a [`Transform::Date`] has no input format.
+const DATE_FORMATS: [&str; 6] = [
+    "%Y-%m-%d",
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M",
+    "%Y/%m/%d",
+];
+
+/// A hash function producing a lowercase hexadecimal digest.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    /// MD5, a 32-character digest.
+    Md5,
+    /// SHA-1, a 40-character digest.
+    Sha1,
+    /// SHA-256, a 64-character digest. This is the default.
+    #[default]
+    Sha256,
+}
+
+impl HashAlgorithm {
+    /// Returns the hexadecimal digest of `value`.
+    pub(crate) fn digest(self, value: &[u8]) -> String {
+        let bytes = match self {
+            Self::Md5 => Md5::digest(value).to_vec(),
+            Self::Sha1 => Sha1::digest(value).to_vec(),
+            Self::Sha256 => Sha256::digest(value).to_vec(),
+        };
+        bytes.iter().fold(String::new(), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+    }
+}
+
+impl FromStr for HashAlgorithm {
+    type Err = CzvError;
+
+    /// Parses an algorithm name, e.g. `sha256`.
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().replace('-', "").as_str() {
+            "md5" => Ok(Self::Md5),
+            "sha1" => Ok(Self::Sha1),
+            "sha256" => Ok(Self::Sha256),
+            _ => Err(CzvError::InvalidOptions(format!(
+                "Unknown hash algorithm {s:?}, expected md5, sha1 or sha256."
+            ))),
+        }
+    }
+}
+
+/// A transformation of a column value. Empty values are left empty by every transformation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Transform {
+    /// Removes leading and trailing whitespace.
+    Trim,
+    /// Converts to uppercase.
+    Upper,
+    /// Converts to lowercase.
+    Lower,
+    /// Capitalizes the first letter of each word and lowercases the other letters.
+    Title,
+    /// Replaces each run of whitespace with a single space, removing leading and trailing whitespace.
+    Squeeze,
+    /// Replaces every match of the regular expression `pattern` with `replacement`, which may
+    /// refer to capture groups as `$1` or `${name}`.
+    Replace {
+        /// The regular expression.
+        pattern: String,
+        /// The replacement text.
+        replacement: String,
+    },
+    /// Keeps `length` characters from the 0-indexed character `start`, or every character from
+    /// `start` if `length` is `None`.
+    Substring {
+        /// The index of the first character kept.
+        start: usize,
+        /// The number of characters kept.
+        length: Option<usize>,
+    },
+    /// Rounds a number to `decimals` decimal places, writing exactly that many.
+    Round {
+        /// The number of decimal places, at most 17, past which a float has no more precision.
+        decimals: usize,
+    },
+    /// Groups the digits of a number's integer part in threes with `separator`, e.g. `1,234.5`.
+    Thousands {
+        /// The separator between digit groups.
+        separator: char,
+    },
+    /// Parses a date or date and time with the chrono format `input_format` and writes it with the
+    /// chrono format `output_format`. Without `input_format`, ISO 8601 dates, date-times and RFC
+    /// 3339 timestamps are recognized.
+    Date {
+        /// The format of the values, e.g. `%d/%m/%Y`.
+        input_format: Option<String>,
+        /// The format of the output, e.g. `%Y-%m-%d`.
+        output_format: String,
+    },
+    /// Replaces the value with its hexadecimal digest.
+    Hash(HashAlgorithm),
+}
+
+impl FromStr for Transform {
+    type Err = CzvError;
+
+    /// Parses a transformation name followed by its arguments, each preceded by a delimiter that
+    /// does not appear in them, e.g. `upper`, `round/2`, `replace/\s+/ ` or
+    /// `date|%d/%m/%Y|%Y-%m-%d`. A date with one argument has no input format.
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || {
+            CzvError::InvalidOptions(format!(
+                "Invalid transformation {s:?}, expected trim, upper, lower, title, squeeze, \
+                 replace/PATTERN/REPLACEMENT, substring/START[/LENGTH], round/DECIMALS, \
+                 thousands[/SEPARATOR], date[/INPUT_FORMAT]/OUTPUT_FORMAT or hash[/ALGORITHM]."
+            ))
+        };
+        let name_end = s
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .unwrap_or(s.len());
+        let (name, rest) = s.split_at(name_end);
+        let args: Vec<&str> = match rest.chars().next() {
+            Some(delimiter) => rest[delimiter.len_utf8()..].split(delimiter).collect(),
+            None => vec![],
+        };
+        let number = |arg: &str| arg.parse::<usize>().map_err(|_| invalid());
+        match (name.to_ascii_lowercase().as_str(), args.as_slice()) {
+            ("trim", []) => Ok(Self::Trim),
+            ("upper", []) => Ok(Self::Upper),
+            ("lower", []) => Ok(Self::Lower),
+            ("title", []) => Ok(Self::Title),
+            ("squeeze", []) => Ok(Self::Squeeze),
+            ("replace", [pattern, replacement]) => Ok(Self::Replace {
+                pattern: pattern.to_string(),
+                replacement: replacement.to_string(),
+            }),
+            ("substring", [start]) => Ok(Self::Substring {
+                start: number(start)?,
+                length: None,
+            }),
+            ("substring", [start, length]) => Ok(Self::Substring {
+                start: number(start)?,
+                length: Some(number(length)?),
+            }),
+            ("round", [decimals]) => Ok(Self::Round {
+                decimals: check_decimals(number(decimals)?)?,
+            }),
+            ("thousands", []) => Ok(Self::Thousands { separator: ',' }),
+            ("thousands", [separator]) if separator.chars().count() == 1 => Ok(Self::Thousands {
+                separator: separator.chars().next().unwrap_or(','),
+            }),
+            ("date", [output_format]) => Ok(Self::Date {
+                input_format: None,
+                output_format: output_format.to_string(),
+            }),
+            ("date", [input_format, output_format]) => Ok(Self::Date {
+                input_format: Some(input_format.to_string()),
+                output_format: output_format.to_string(),
+            }),
+            ("hash", []) => Ok(Self::Hash(HashAlgorithm::default())),
+            ("hash", [algorithm]) => Ok(Self::Hash(algorithm.parse()?)),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// The most decimal places a number can be rounded to.
+const MAX_DECIMALS: usize = 17;
+
+/// Returns `decimals`, or an error if it is more than [`MAX_DECIMALS`].
+fn check_decimals(decimals: usize) -> Result<usize> {
+    match decimals > MAX_DECIMALS {
+        true => Err(CzvError::InvalidOptions(format!(
+            "Cannot round to {decimals} decimal places, expected at most {MAX_DECIMALS}."
+        ))),
+        false => Ok(decimals),
+    }
+}
+
+/// Returns an error if the chrono `format` is invalid.
+fn check_date_format(format: &str) -> Result<()> {
+    match StrftimeItems::new(format).any(|item| item == Item::Error) {
+        true => Err(CzvError::InvalidOptions(format!(
+            "Invalid date format {format:?}."
+        ))),
+        false => Ok(()),
+    }
+}
+
+/// Returns `value` parsed as a date-time with `format`, or with the ISO formats.
+fn parse_date(value: &str, format: Option<&str>) -> Option<NaiveDateTime> {
+    let parse = |format: &str| {
+        NaiveDateTime::parse_from_str(value, format)
+            .ok()
+            .or_else(|| {
+                NaiveDate::parse_from_str(value, format)
+                    .ok()
+                    .and_then(|date| date.and_hms_opt(0, 0, 0))
+            })
+    };
+    match format {
+        Some(format) => parse(format),
+        None => DateTime::parse_from_rfc3339(value)
+            .ok()
+            .map(|date| date.naive_local())
+            .or_else(|| DATE_FORMATS.into_iter().find_map(parse)),
+    }
+}
+
+/// Returns `value` with the digits of its integer part grouped by `separator`.
+fn group_thousands(value: &str, separator: char) -> String {
+    let (sign, unsigned) = match value.strip_prefix(['-', '+']) {
+        Some(unsigned) => (&value[..1], unsigned),
+        None => ("", value),
+    };
+    let (integer, fraction) = match unsigned.find(['.', 'e', 'E']) {
+        Some(i) => unsigned.split_at(i),
+        None => (unsigned, ""),
+    };
+    let mut output = sign.to_string();
+    for (i, digit) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            output.push(separator);
+        }
+        output.push(digit);
+    }
+    output.push_str(fraction);
+    output
+}
+
+/// Returns `value` with the first letter of each word capitalized.
+fn title_case(value: &str) -> String {
+    let mut output = String::with_capacity(value.len());
+    let mut word_start = true;
+    for c in value.chars() {
+        if word_start {
+            output.extend(c.to_uppercase());
+        } else {
+            output.extend(c.to_lowercase());
+        }
+        word_start = c.is_whitespace();
+    }
+    output
+}
+
+/// A transformation with its regular expression compiled.
+struct Step<'a> {
+    transform: &'a Transform,
+    regex: Option<Regex>,
+}
+
+impl<'a> Step<'a> {
+    fn new(transform: &'a Transform) -> Result<Self> {
+        let regex = match transform {
+            Transform::Replace { pattern, .. } => Some(Regex::new(pattern).map_err(|err| {
+                CzvError::InvalidOptions(format!("Invalid regular expression: {err}"))
+            })?),
+            Transform::Date {
+                input_format,
+                output_format,
+            } => {
+                input_format.as_deref().map(check_date_format).transpose()?;
+                check_date_format(output_format)?;
+                None
+            }
+            Transform::Round { decimals } => {
+                check_decimals(*decimals)?;
+                None
+            }
+            _ => None,
+        };
+        Ok(Self { transform, regex })
+    }
+
+    /// Returns the transformed value, or the value and the reason it cannot be transformed.
+    fn apply(&self, value: String) -> std::result::Result<String, (String, &'static str)> {
+        if value.is_empty() {
+            return Ok(value);
+        }
+        let number = |value: &String| {
+            value
+                .parse::<f64>()
+                .ok()
+                .filter(|n| n.is_finite())
+                .ok_or_else(|| (value.clone(), "is not a number"))
+        };
+        Ok(match self.transform {
+            Transform::Trim => value.trim().to_string(),
+            Transform::Upper => value.to_uppercase(),
+            Transform::Lower => value.to_lowercase(),
+            Transform::Title => title_case(&value),
+            Transform::Squeeze => value.split_whitespace().collect::<Vec<_>>().join(" "),
+            Transform::Replace { replacement, .. } => match &self.regex {
+                Some(regex) => regex.replace_all(&value, replacement).into_owned(),
+                None => value,
+            },
+            Transform::Substring { start, length } => {
+                let chars = value.chars().skip(*start);
+                match length {
+                    Some(length) => chars.take(*length).collect(),
+                    None => chars.collect(),
+                }
+            }
+            Transform::Round { decimals } => format!("{:.*}", decimals, number(&value)?),
+            Transform::Thousands { separator } => {
+                number(&value)?;
+                group_thousands(&value, *separator)
+            }
+            Transform::Date {
+                input_format,
+                output_format,
+            } => {
+                let date = parse_date(&value, input_format.as_deref())
+                    .ok_or_else(|| (value.clone(), "is not a recognized date"))?;
+                let mut output = String::new();
+                write!(output, "{}", date.format(output_format))
+                    .map_err(|_| (value.clone(), "cannot be written with the date format"))?;
+                output
+            }
+            Transform::Hash(algorithm) => algorithm.digest(value.as_bytes()),
+        })
+    }
+}
+
+/// Returns the CSV data with the selected columns transformed. Recommended alternative: [`czv::apply::Apply`](crate::apply::Apply).
+///
+/// See [`czv::apply::Apply`](crate::apply::Apply) for a builder version (recommended) of this function.
+///
+/// ## Notes
+///
+/// - The `transforms` are applied in order to the value of each of `columns`.
+/// - With `new_columns`, the transformed values are written to new columns added after the other
+///   columns, one for each of `columns`, and the original values are kept.
+/// - Records with a value that cannot be transformed, e.g. a non-numeric value to round, are
+///   malformed. They are left out when skipped with `error_policy`.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+///
+/// ## Arguments
+///
+/// * `file_path` - CSV file path (alternative to `file_data`).
+/// * `file_data` - CSV file data (alternative to `file_path`).
+/// * `columns` - The header names of the columns to transform.
+/// * `transforms` - The transformations applied in order.
+/// * `new_columns` - The header names of new columns for the transformed values.
+/// * `error_policy` - How to handle malformed records.
+/// * `encoding` - The character encoding of `file_path`.
+pub fn apply(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    columns: Vec<String>,
+    transforms: Vec<Transform>,
+    new_columns: Vec<String>,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<String> {
+    Ok(apply_with_report(
+        file_path,
+        file_data,
+        columns,
+        transforms,
+        new_columns,
+        error_policy,
+        encoding,
+    )?
+    .output)
+}
+
+fn apply_with_report(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    columns: Vec<String>,
+    transforms: Vec<Transform>,
+    new_columns: Vec<String>,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<Report<String>> {
+    if columns.is_empty() || transforms.is_empty() {
+        return Err(CzvError::InvalidOptions(
+            "Must provide columns and transforms.".to_string(),
+        ));
+    }
+    if !new_columns.is_empty() && new_columns.len() != columns.len() {
+        return Err(CzvError::InvalidOptions(format!(
+            "Expected {} new_columns, one for each of columns, but found {}.",
+            columns.len(),
+            new_columns.len()
+        )));
+    }
+    let steps = transforms
+        .iter()
+        .map(Step::new)
+        .collect::<Result<Vec<_>>>()?;
+    let rdr = ReaderBuilder::new();
+    let mut rdr = reader_from(&rdr, file_path.as_deref(), file_data.as_deref(), encoding)?;
+    let headers = rdr.headers()?.clone();
+    let indexes = column_indexes(&headers, &columns)?;
+
+    let mut wtr = writer();
+    wtr.write_record(headers.iter().chain(new_columns.iter().map(String::as_str)))?;
+    let mut skipper = Skipper::new(error_policy);
+    let mut record = StringRecord::new();
+    let mut values: Vec<String> = vec![];
+    'records: while skipper.read_record(&mut rdr, &mut record)? {
+        values.clear();
+        for (column, index) in columns.iter().zip(&indexes) {
+            let mut value = record.get(*index).unwrap_or_default().to_string();
+            for step in &steps {
+                value = match step.apply(value) {
+                    Ok(value) => value,
+                    Err((value, reason)) => {
+                        skipper.skip(CzvError::parse_at(
+                            format!("Value {value:?} in column {column:?} {reason}."),
+                            &record,
+                        ))?;
+                        continue 'records;
+                    }
+                };
+            }
+            values.push(value);
+        }
+        if new_columns.is_empty() {
+            let mut output: Vec<&str> = record.iter().collect();
+            for (index, value) in indexes.iter().zip(&values) {
+                output[*index] = value;
+            }
+            wtr.write_record(output)?;
+        } else {
+            wtr.write_record(record.iter().chain(values.iter().map(String::as_str)))?;
+        }
+    }
+    Ok(skipper.finish(into_string(wtr)?))
+}
+
+#[derive(TypedBuilder)]
+#[builder(doc, builder_method(name=new, doc="Returns an [`ApplyBuilder`](crate::apply::ApplyBuilder) to customize transformation options by running other methods before getting the output with the [`execute`](crate::apply::ApplyBuilder::execute) method."), build_method(vis="", name=__build))]
+/// Returns the CSV data with the selected columns transformed.
+///
+/// The entry point for setting up a [`czv::apply::ApplyBuilder`](crate::apply::ApplyBuilder) by running [`Apply::new()`](crate::apply::Apply::new).
+///
+/// # Example
+///
+/// ```rust
+/// use czv::apply::Transform;
+/// use czv::{Apply, Result};
+///
+/// fn main() -> Result<()> {
+///     let data = "\
+/// name,joined
+///   ada   LOVELACE ,10/12/1815
+/// ";
+///     let output = Apply::new()
+///         .file_data(data)
+///         .columns(["name".to_string()])
+///         .transforms([Transform::Squeeze, Transform::Title])
+///         .execute()?;
+///     assert_eq!(output, "name,joined\nAda Lovelace,10/12/1815\n");
+///
+///     let output = Apply::new()
+///         .file_data(data)
+///         .columns(["joined".to_string()])
+///         .transforms(["date|%d/%m/%Y|%Y-%m-%d".parse::<Transform>()?])
+///         .new_columns(["joined_iso".to_string()])
+///         .execute()?;
+///     assert_eq!(
+///         output,
+///         "name,joined,joined_iso\n  ada   LOVELACE ,10/12/1815,1815-12-10\n"
+///     );
+///     Ok(())
+/// }
+/// ```
+///
+/// ## Notes
+///
+/// - See [`apply`](crate::apply::apply) for how values are transformed and written.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+pub struct Apply {
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file path (alternative to `file_data`).",
+            strip_option(fallback = file_path_opt),
+            into
+        )
+    )]
+    file_path: Option<PathBuf>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file data (alternative to `file_path`).",
+            strip_option(fallback = file_data_opt),
+            into
+        )
+    )]
+    file_data: Option<String>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "The header names of the columns to transform.",
+            transform = |columns: impl IntoIterator<Item = String>| columns.into_iter().collect()
+        )
+    )]
+    columns: Vec<String>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "The transformations applied in order.",
+            transform = |transforms: impl IntoIterator<Item = Transform>| transforms.into_iter().collect()
+        )
+    )]
+    transforms: Vec<Transform>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "The header names of new columns for the transformed values, one for each of `columns`. Defaults to transforming in place.",
+            transform = |new_columns: impl IntoIterator<Item = String>| new_columns.into_iter().collect()
+        )
+    )]
+    new_columns: Vec<String>,
+
+    #[builder(
+        default,
+        setter(doc = "How to handle malformed records. Defaults to `ErrorPolicy::Fail`.")
+    )]
+    error_policy: ErrorPolicy,
+
+    #[builder(
+        default,
+        setter(doc = "The character encoding of `file_path`. Defaults to `Encoding::Utf8`.")
+    )]
+    encoding: Encoding,
+}
+
+#[allow(non_camel_case_types)]
+impl<
+        __encoding: typed_builder::Optional<Encoding>,
+        __error_policy: typed_builder::Optional<ErrorPolicy>,
+        __new_columns: typed_builder::Optional<Vec<String>>,
+        __transforms: typed_builder::Optional<Vec<Transform>>,
+        __columns: typed_builder::Optional<Vec<String>>,
+        __file_data: typed_builder::Optional<Option<String>>,
+        __file_path: typed_builder::Optional<Option<PathBuf>>,
+    >
+    ApplyBuilder<(
+        __file_path,
+        __file_data,
+        __columns,
+        __transforms,
+        __new_columns,
+        __error_policy,
+        __encoding,
+    )>
+{
+    /// Returns the transformed CSV data.
+    pub fn execute(self) -> Result<String> {
+        Ok(self.execute_with_report()?.output)
+    }
+
+    /// Returns the transformed CSV data along with the records skipped with `ErrorPolicy::Collect`.
+    pub fn execute_with_report(self) -> Result<Report<String>> {
+        let builder = self.__build();
+        apply_with_report(
+            builder.file_path,
+            builder.file_data,
+            builder.columns,
+            builder.transforms,
+            builder.new_columns,
+            builder.error_policy,
+            builder.encoding,
+        )
+    }
+}
@@ -0,0 +1,532 @@
+use crate::encoding::Encoding;
+use crate::infer::{infer_columns, ColumnType};
+use crate::policy::{ErrorPolicy, Report, Skipper};
+use crate::util::{into_string, reader_from, writer};
+use crate::{CzvError, Result};
+use ::arrow::array::{
+    ArrayRef, BooleanBuilder, Float64Builder, Int64Builder, RecordBatch, StringBuilder,
+};
+use ::arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use ::arrow::ipc::reader::StreamReader;
+use ::arrow::ipc::writer::StreamWriter;
+use ::arrow::util::display::{ArrayFormatter, FormatOptions};
+use csv::{Reader, ReaderBuilder, StringRecord};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use typed_builder::TypedBuilder;
+
+/// Collects the values of one column into an Arrow array.
+enum ColumnBuilder {
+    Boolean(BooleanBuilder),
+    Integer(Int64Builder),
+    Float(Float64Builder),
+    String(StringBuilder),
+}
+
+impl ColumnBuilder {
+    fn new(column_type: ColumnType) -> Self {
+        match column_type {
+            ColumnType::Boolean => Self::Boolean(BooleanBuilder::new()),
+            ColumnType::Integer => Self::Integer(Int64Builder::new()),
+            ColumnType::Float => Self::Float(Float64Builder::new()),
+            ColumnType::String => Self::String(StringBuilder::new()),
+        }
+    }
+
+    /// Returns whether `value` can be appended, empty values are null except in string columns.
+    fn accepts(&self, value: &str) -> bool {
+        match self {
+            _ if value.is_empty() => true,
+            Self::Boolean(_) => {
+                value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("false")
+            }
+            Self::Integer(_) => value.parse::<i64>().is_ok(),
+            Self::Float(_) => value.parse::<f64>().is_ok(),
+            Self::String(_) => true,
+        }
+    }
+
+    /// Appends `value`, which must be [accepted](Self::accepts).
+    fn append(&mut self, value: &str) {
+        match self {
+            Self::String(builder) => builder.append_value(value),
+            Self::Boolean(builder) if value.is_empty() => builder.append_null(),
+            Self::Integer(builder) if value.is_empty() => builder.append_null(),
+            Self::Float(builder) if value.is_empty() => builder.append_null(),
+            Self::Boolean(builder) => builder.append_value(value.eq_ignore_ascii_case("true")),
+            Self::Integer(builder) => builder.append_option(value.parse().ok()),
+            Self::Float(builder) => builder.append_option(value.parse().ok()),
+        }
+    }
+
+    fn finish(&mut self) -> ArrayRef {
+        match self {
+            Self::Boolean(builder) => Arc::new(builder.finish()),
+            Self::Integer(builder) => Arc::new(builder.finish()),
+            Self::Float(builder) => Arc::new(builder.finish()),
+            Self::String(builder) => Arc::new(builder.finish()),
+        }
+    }
+}
+
+fn data_type(column_type: ColumnType) -> DataType {
+    match column_type {
+        ColumnType::Boolean => DataType::Boolean,
+        ColumnType::Integer => DataType::Int64,
+        ColumnType::Float => DataType::Float64,
+        ColumnType::String => DataType::Utf8,
+    }
+}
+
+/// Returns the type of every column, inferring the types that are not in `column_types`.
+fn resolve_types(
+    file_path: Option<&Path>,
+    file_data: Option<&str>,
+    column_types: &HashMap<String, ColumnType>,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<(StringRecord, Vec<ColumnType>)> {
+    let rdr = ReaderBuilder::new();
+    let headers = reader_from(&rdr, file_path, file_data, encoding)?
+        .headers()?
+        .clone();
+    if let Some(name) = column_types
+        .keys()
+        .find(|name| !headers.iter().any(|header| header == name.as_str()))
+    {
+        return Err(CzvError::ColumnNotFound(name.clone()));
+    }
+
+    let inferred = if headers.iter().all(|name| column_types.contains_key(name)) {
+        None
+    } else {
+        let mut rdr = reader_from(&rdr, file_path, file_data, encoding)?;
+        Some(infer_columns(&mut rdr, error_policy)?)
+    };
+    let types = headers
+        .iter()
+        .enumerate()
+        .map(|(i, name)| match column_types.get(name) {
+            Some(column_type) => *column_type,
+            None => inferred
+                .as_ref()
+                .map_or(ColumnType::String, |columns| columns[i].column_type),
+        })
+        .collect();
+    Ok((headers, types))
+}
+
+/// Reads CSV data as Arrow record batches of typed columns.
+pub(crate) struct CsvBatches<'a> {
+    rdr: Reader<Box<dyn Read + 'a>>,
+    headers: StringRecord,
+    schema: SchemaRef,
+    builders: Vec<ColumnBuilder>,
+    batch_size: usize,
+    skipper: Skipper,
+    record: StringRecord,
+}
+
+impl<'a> CsvBatches<'a> {
+    /// Resolves the schema, which reads the input once if any column type must be inferred.
+    pub(crate) fn new(
+        file_path: Option<&Path>,
+        file_data: Option<&'a str>,
+        column_types: &HashMap<String, ColumnType>,
+        batch_size: usize,
+        error_policy: ErrorPolicy,
+        encoding: Encoding,
+    ) -> Result<Self> {
+        if batch_size == 0 {
+            return Err(CzvError::InvalidOptions(
+                "batch_size must be greater than 0.".to_string(),
+            ));
+        }
+        let (headers, types) =
+            resolve_types(file_path, file_data, column_types, error_policy, encoding)?;
+        let schema = Arc::new(Schema::new(
+            headers
+                .iter()
+                .zip(&types)
+                .map(|(name, column_type)| Field::new(name, data_type(*column_type), true))
+                .collect::<Vec<_>>(),
+        ));
+        Ok(Self {
+            rdr: reader_from(&ReaderBuilder::new(), file_path, file_data, encoding)?,
+            headers,
+            schema,
+            builders: types.into_iter().map(ColumnBuilder::new).collect(),
+            batch_size,
+            skipper: Skipper::new(error_policy),
+            record: StringRecord::new(),
+        })
+    }
+
+    pub(crate) fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    /// Returns the next batch of up to `batch_size` rows, or `None` at the end of the data.
+    ///
+    /// Records with a value that does not match its column type are passed to the error policy.
+    pub(crate) fn next_batch(&mut self) -> Result<Option<RecordBatch>> {
+        let mut rows = 0;
+        while rows < self.batch_size && self.skipper.read_record(&mut self.rdr, &mut self.record)? {
+            let rejected = self
+                .record
+                .iter()
+                .zip(&self.builders)
+                .zip(self.headers.iter())
+                .find(|((value, builder), _)| !builder.accepts(value));
+            if let Some(((value, _), name)) = rejected {
+                self.skipper.skip(CzvError::parse_at(
+                    format!("Value {value:?} in column {name:?} does not match the column type."),
+                    &self.record,
+                ))?;
+                continue;
+            }
+            for (value, builder) in self.record.iter().zip(&mut self.builders) {
+                builder.append(value);
+            }
+            rows += 1;
+        }
+        if rows == 0 {
+            return Ok(None);
+        }
+        let columns = self
+            .builders
+            .iter_mut()
+            .map(ColumnBuilder::finish)
+            .collect();
+        Ok(Some(RecordBatch::try_new(self.schema.clone(), columns)?))
+    }
+
+    /// Returns the output along with the records skipped so far.
+    pub(crate) fn finish<T>(self, output: T) -> Report<T> {
+        self.skipper.finish(output)
+    }
+}
+
+/// Returns the batches as CSV data with a header row of the schema's field names.
+pub(crate) fn write_csv(
+    schema: &Schema,
+    batches: impl IntoIterator<Item = Result<RecordBatch>>,
+) -> Result<String> {
+    let mut wtr = writer();
+    wtr.write_record(schema.fields().iter().map(|field| field.name()))?;
+    let options = FormatOptions::default();
+    let mut row = vec![];
+    for batch in batches {
+        let batch = batch?;
+        let formatters = batch
+            .columns()
+            .iter()
+            .map(|column| ArrayFormatter::try_new(column.as_ref(), &options))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        for i in 0..batch.num_rows() {
+            row.clear();
+            row.extend(
+                formatters
+                    .iter()
+                    .map(|formatter| formatter.value(i).to_string()),
+            );
+            wtr.write_record(&row)?;
+        }
+    }
+    into_string(wtr)
+}
+
+/// Returns the CSV data converted to Arrow record batches. Recommended alternative: [`czv::arrow::ToArrow`](crate::arrow::ToArrow).
+///
+/// See [`czv::arrow::ToArrow`](crate::arrow::ToArrow) for a builder version (recommended) of this function.
+///
+/// ## Notes
+///
+/// - Each column is a nullable `Boolean`, `Int64`, `Float64` or `Utf8` array. The type comes from
+///   `column_types` if the column is named there, otherwise it is inferred from the whole column,
+///   which reads the input twice.
+/// - Empty values are null, except in string columns where they stay empty strings.
+/// - At least one batch is returned so that the schema is available, which is empty if there are no rows.
+/// - Records with a value that does not match its column type are malformed. They are left out of
+///   the output when skipped with `error_policy`.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+///
+/// ## Arguments
+///
+/// * `file_path` - CSV file path (alternative to `file_data`).
+/// * `file_data` - CSV file data (alternative to `file_path`).
+/// * `column_types` - Column types by header name, overriding the inferred types.
+/// * `batch_size` - The maximum number of rows in a batch.
+/// * `error_policy` - How to handle malformed records.
+/// * `encoding` - The character encoding of `file_path`.
+pub fn to_arrow(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    column_types: HashMap<String, ColumnType>,
+    batch_size: usize,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<Vec<RecordBatch>> {
+    Ok(to_arrow_with_report(
+        file_path,
+        file_data,
+        column_types,
+        batch_size,
+        error_policy,
+        encoding,
+    )?
+    .output)
+}
+
+fn to_arrow_with_report(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    column_types: HashMap<String, ColumnType>,
+    batch_size: usize,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<Report<Vec<RecordBatch>>> {
+    let mut reader = CsvBatches::new(
+        file_path.as_deref(),
+        file_data.as_deref(),
+        &column_types,
+        batch_size,
+        error_policy,
+        encoding,
+    )?;
+    let mut batches = vec![];
+    while let Some(batch) = reader.next_batch()? {
+        batches.push(batch);
+    }
+    if batches.is_empty() {
+        batches.push(RecordBatch::new_empty(reader.schema()));
+    }
+    Ok(reader.finish(batches))
+}
+
+#[derive(TypedBuilder)]
+#[builder(doc, builder_method(name=new, doc="Returns a [`ToArrowBuilder`](crate::arrow::ToArrowBuilder) to customize Arrow conversion options by running other methods before getting the record batches with the [`execute`](crate::arrow::ToArrowBuilder::execute) method."), build_method(vis="", name=__build))]
+/// Returns the CSV data converted to Arrow record batches with an inferred schema.
+///
+/// The batches can be passed without copying to other Arrow-based libraries such as DataFusion or Polars.
+///
+/// The entry point for setting up a [`czv::arrow::ToArrowBuilder`](crate::arrow::ToArrowBuilder) by running [`ToArrow::new()`](crate::arrow::ToArrow::new).
+///
+/// # Example
+///
+/// ```rust
+/// use arrow::datatypes::DataType;
+/// use czv::{FromArrow, Result, ToArrow};
+///
+/// fn main() -> Result<()> {
+///     let data = "\
+/// fruit,price
+/// apple,2.50
+/// banana,
+/// ";
+///     let batches = ToArrow::new().file_data(data).execute()?;
+///     let schema = batches[0].schema();
+///     assert_eq!(schema.field(1).data_type(), &DataType::Float64);
+///     assert_eq!(batches[0].num_rows(), 2);
+///
+///     let csv = FromArrow::new().batches(batches).execute()?;
+///     assert_eq!(csv, "fruit,price\napple,2.5\nbanana,\n");
+///     Ok(())
+/// }
+/// ```
+///
+/// Use [`execute_to_ipc`](crate::arrow::ToArrowBuilder::execute_to_ipc) to write an Arrow IPC
+/// stream instead, e.g. for apache-arrow in JavaScript.
+///
+/// ## Notes
+///
+/// - See [`to_arrow`](crate::arrow::to_arrow) for how column types are chosen.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+pub struct ToArrow {
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file path (alternative to `file_data`).",
+            strip_option(fallback = file_path_opt),
+            into
+        )
+    )]
+    file_path: Option<PathBuf>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file data (alternative to `file_path`).",
+            strip_option(fallback = file_data_opt),
+            into
+        )
+    )]
+    file_data: Option<String>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "Column types by header name, overriding the inferred types.",
+            transform = |column_types: impl IntoIterator<Item = (String, ColumnType)>| column_types.into_iter().collect()
+        )
+    )]
+    column_types: HashMap<String, ColumnType>,
+
+    #[builder(
+        default = 8192,
+        setter(doc = "The maximum number of rows in a batch. Defaults to 8192.")
+    )]
+    batch_size: usize,
+
+    #[builder(
+        default,
+        setter(doc = "How to handle malformed records. Defaults to `ErrorPolicy::Fail`.")
+    )]
+    error_policy: ErrorPolicy,
+
+    #[builder(
+        default,
+        setter(doc = "The character encoding of `file_path`. Defaults to `Encoding::Utf8`.")
+    )]
+    encoding: Encoding,
+}
+
+#[allow(non_camel_case_types)]
+impl<
+        __encoding: typed_builder::Optional<Encoding>,
+        __error_policy: typed_builder::Optional<ErrorPolicy>,
+        __batch_size: typed_builder::Optional<usize>,
+        __column_types: typed_builder::Optional<HashMap<String, ColumnType>>,
+        __file_data: typed_builder::Optional<Option<String>>,
+        __file_path: typed_builder::Optional<Option<PathBuf>>,
+    >
+    ToArrowBuilder<(
+        __file_path,
+        __file_data,
+        __column_types,
+        __batch_size,
+        __error_policy,
+        __encoding,
+    )>
+{
+    /// Returns the record batches.
+    pub fn execute(self) -> Result<Vec<RecordBatch>> {
+        Ok(self.execute_with_report()?.output)
+    }
+
+    /// Returns the record batches along with the records skipped with `ErrorPolicy::Collect`.
+    pub fn execute_with_report(self) -> Result<Report<Vec<RecordBatch>>> {
+        let builder = self.__build();
+        to_arrow_with_report(
+            builder.file_path,
+            builder.file_data,
+            builder.column_types,
+            builder.batch_size,
+            builder.error_policy,
+            builder.encoding,
+        )
+    }
+
+    /// Writes the record batches to `wtr` as an Arrow IPC stream as each batch is converted,
+    /// returning the records skipped with `ErrorPolicy::Collect`.
+    pub fn execute_to_ipc<W: Write>(self, wtr: W) -> Result<Report<()>> {
+        let builder = self.__build();
+        let mut reader = CsvBatches::new(
+            builder.file_path.as_deref(),
+            builder.file_data.as_deref(),
+            &builder.column_types,
+            builder.batch_size,
+            builder.error_policy,
+            builder.encoding,
+        )?;
+        let mut ipc_writer = StreamWriter::try_new(wtr, &reader.schema())?;
+        while let Some(batch) = reader.next_batch()? {
+            ipc_writer.write(&batch)?;
+        }
+        ipc_writer.finish()?;
+        Ok(reader.finish(()))
+    }
+}
+
+/// Returns CSV data converted from Arrow record batches. Recommended alternative: [`czv::arrow::FromArrow`](crate::arrow::FromArrow).
+///
+/// See [`czv::arrow::FromArrow`](crate::arrow::FromArrow) for a builder version (recommended) of this function.
+///
+/// ## Notes
+///
+/// - The header row comes from the schema of the first batch, or of the IPC stream.
+/// - Values are written as Arrow displays them, e.g. dates as `2024-01-31`, and nulls are empty.
+/// - You may not use `batches` and `ipc_data` together, only one may be specified.
+///
+/// ## Arguments
+///
+/// * `batches` - Arrow record batches (alternative to `ipc_data`).
+/// * `ipc_data` - Arrow IPC stream data (alternative to `batches`).
+pub fn from_arrow(batches: Option<Vec<RecordBatch>>, ipc_data: Option<Vec<u8>>) -> Result<String> {
+    match (batches, ipc_data) {
+        (Some(_), Some(_)) => Err(CzvError::InvalidOptions(
+            "Cannot have both batches and ipc_data, specify one only.".to_string(),
+        )),
+        (None, None) => Err(CzvError::InvalidOptions(
+            "Must provide either batches or ipc_data.".to_string(),
+        )),
+        (Some(batches), None) => match batches.first() {
+            Some(first) => write_csv(&first.schema(), batches.iter().cloned().map(Ok)),
+            None => Ok(String::new()),
+        },
+        (None, Some(ipc_data)) => {
+            let reader = StreamReader::try_new(ipc_data.as_slice(), None)?;
+            let schema = reader.schema();
+            write_csv(&schema, reader.map(|batch| Ok(batch?)))
+        }
+    }
+}
+
+#[derive(TypedBuilder)]
+#[builder(doc, builder_method(name=new, doc="Returns a [`FromArrowBuilder`](crate::arrow::FromArrowBuilder) to customize Arrow conversion options by running other methods before getting the CSV with the [`execute`](crate::arrow::FromArrowBuilder::execute) method."), build_method(vis="", name=__build))]
+/// Returns CSV data converted from Arrow record batches or an Arrow IPC stream.
+///
+/// The entry point for setting up a [`czv::arrow::FromArrowBuilder`](crate::arrow::FromArrowBuilder) by running [`FromArrow::new()`](crate::arrow::FromArrow::new).
+///
+/// See [`ToArrow`](crate::arrow::ToArrow) for an example.
+///
+/// ## Notes
+///
+/// - See [`from_arrow`](crate::arrow::from_arrow) for how values are written.
+/// - You may not use `batches` and `ipc_data` together, only one may be specified.
+pub struct FromArrow {
+    #[builder(
+        default,
+        setter(
+            doc = "Arrow record batches (alternative to `ipc_data`).",
+            strip_option(fallback = batches_opt),
+            into
+        )
+    )]
+    batches: Option<Vec<RecordBatch>>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "Arrow IPC stream data (alternative to `batches`).",
+            strip_option(fallback = ipc_data_opt),
+            into
+        )
+    )]
+    ipc_data: Option<Vec<u8>>,
+}
+
+#[allow(non_camel_case_types)]
+impl<
+        __ipc_data: typed_builder::Optional<Option<Vec<u8>>>,
+        __batches: typed_builder::Optional<Option<Vec<RecordBatch>>>,
+    > FromArrowBuilder<(__batches, __ipc_data)>
+{
+    /// Returns the CSV output.
+    pub fn execute(self) -> Result<String> {
+        let builder = self.__build();
+        from_arrow(builder.batches, builder.ipc_data)
+    }
+}
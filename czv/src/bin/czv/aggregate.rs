@@ -0,0 +1,44 @@
+use crate::{print_skipped, Input, Policy};
+use clap::Args;
+use czv::aggregate::Aggregation;
+use czv::{Aggregate, Result};
+use std::process::ExitCode;
+
+#[derive(Args)]
+pub struct AggregateArgs {
+    #[command(flatten)]
+    input: Input,
+
+    /// The key columns to group by, e.g. `store,fruit`.
+    #[arg(short, long, value_name = "COLUMNS", value_delimiter = ',')]
+    group_by: Vec<String>,
+
+    /// An aggregation as `function(column)` or `count`, e.g. `sum(price),max(price)`. The functions
+    /// are count, sum, mean, min, max, first, last, distinct_count and concat.
+    #[arg(short, long = "agg", value_name = "AGGREGATIONS", value_delimiter = ',', value_parser = str::parse::<Aggregation>)]
+    aggregations: Vec<Aggregation>,
+
+    /// The separator of values joined with `concat`.
+    #[arg(long, default_value = ",")]
+    separator: String,
+
+    /// How to handle malformed records.
+    #[arg(long, value_enum, default_value_t)]
+    error_policy: Policy,
+}
+
+pub fn run(args: AggregateArgs) -> Result<ExitCode> {
+    let (file_path, file_data, encoding) = args.input.source()?;
+    let report = Aggregate::new()
+        .file_path_opt(file_path)
+        .file_data_opt(file_data)
+        .encoding(encoding)
+        .group_by(args.group_by)
+        .aggregations(args.aggregations)
+        .separator(args.separator)
+        .error_policy(args.error_policy.into())
+        .execute_with_report()?;
+    print_skipped(&report.skipped);
+    print!("{}", report.output);
+    Ok(ExitCode::SUCCESS)
+}
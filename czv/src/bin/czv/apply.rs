@@ -0,0 +1,52 @@
+use crate::{print_skipped, Input, Policy};
+use clap::Args;
+use czv::apply::Transform;
+use czv::{Apply, Result};
+use std::process::ExitCode;
+
+#[derive(Args)]
+pub struct ApplyArgs {
+    #[command(flatten)]
+    input: Input,
+
+    /// The columns to transform, e.g. `name,city`.
+    #[arg(
+        short,
+        long,
+        value_name = "COLUMNS",
+        value_delimiter = ',',
+        required = true
+    )]
+    columns: Vec<String>,
+
+    /// A transformation, applied in the order given: trim, upper, lower, title, squeeze,
+    /// replace/PATTERN/REPLACEMENT, substring/START[/LENGTH], round/DECIMALS, thousands[/SEPARATOR],
+    /// date[/INPUT_FORMAT]/OUTPUT_FORMAT or hash[/md5|sha1|sha256]. Any delimiter that does not
+    /// appear in the arguments may be used instead of `/`, e.g. `date|%d/%m/%Y|%Y-%m-%d`.
+    #[arg(short, long = "transform", value_name = "TRANSFORM", required = true, value_parser = str::parse::<Transform>)]
+    transforms: Vec<Transform>,
+
+    /// Write the transformed values to new columns with these names instead of in place, one for each column.
+    #[arg(short, long, value_name = "NAMES", value_delimiter = ',')]
+    new_columns: Vec<String>,
+
+    /// How to handle malformed records.
+    #[arg(long, value_enum, default_value_t)]
+    error_policy: Policy,
+}
+
+pub fn run(args: ApplyArgs) -> Result<ExitCode> {
+    let (file_path, file_data, encoding) = args.input.source()?;
+    let report = Apply::new()
+        .file_path_opt(file_path)
+        .file_data_opt(file_data)
+        .encoding(encoding)
+        .columns(args.columns)
+        .transforms(args.transforms)
+        .new_columns(args.new_columns)
+        .error_policy(args.error_policy.into())
+        .execute_with_report()?;
+    print_skipped(&report.skipped);
+    print!("{}", report.output);
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,58 @@
+use crate::{print_skipped, Input, Policy};
+use clap::Args;
+use czv::infer::ColumnType;
+use czv::{FromArrow, Result, ToArrow};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+#[derive(Args)]
+pub struct ToArrowArgs {
+    #[command(flatten)]
+    input: Input,
+
+    /// Arrow IPC stream file path to write to.
+    #[arg(short, long)]
+    output: PathBuf,
+
+    /// Set a column's type instead of inferring it, as `name=type` where type is boolean, integer, float or string.
+    #[arg(long = "type", value_name = "NAME=TYPE", value_parser = crate::parse_column_type)]
+    types: Vec<(String, ColumnType)>,
+
+    /// The maximum number of rows in a record batch.
+    #[arg(long, default_value_t = 8192)]
+    batch_size: usize,
+
+    /// How to handle malformed records.
+    #[arg(long, value_enum, default_value_t)]
+    error_policy: Policy,
+}
+
+pub fn run_to_arrow(args: ToArrowArgs) -> Result<ExitCode> {
+    let (file_path, file_data, encoding) = args.input.source()?;
+    let report = ToArrow::new()
+        .file_path_opt(file_path)
+        .file_data_opt(file_data)
+        .encoding(encoding)
+        .column_types(args.types)
+        .batch_size(args.batch_size)
+        .error_policy(args.error_policy.into())
+        .execute_to_ipc(BufWriter::new(File::create(args.output)?))?;
+    print_skipped(&report.skipped);
+    Ok(ExitCode::SUCCESS)
+}
+
+#[derive(Args)]
+pub struct FromArrowArgs {
+    /// Arrow IPC stream file path.
+    input: PathBuf,
+}
+
+pub fn run_from_arrow(args: FromArrowArgs) -> Result<ExitCode> {
+    let output = FromArrow::new()
+        .ipc_data(std::fs::read(args.input)?)
+        .execute()?;
+    print!("{output}");
+    Ok(ExitCode::SUCCESS)
+}
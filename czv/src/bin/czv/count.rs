@@ -0,0 +1,49 @@
+use crate::{print_skipped, Input, Policy};
+use clap::Args;
+use czv::{ColumnCount, Result, RowCount};
+use std::process::ExitCode;
+
+#[derive(Args)]
+pub struct CountArgs {
+    #[command(flatten)]
+    input: Input,
+
+    /// Include the header row (first row) in the row count.
+    #[arg(long)]
+    include_header_row: bool,
+
+    /// How to handle malformed records.
+    #[arg(long, value_enum, default_value_t)]
+    error_policy: Policy,
+}
+
+pub fn run_count(args: CountArgs) -> Result<ExitCode> {
+    let (file_path, file_data, encoding) = args.input.source()?;
+    let report = RowCount::new()
+        .file_path_opt(file_path)
+        .file_data_opt(file_data)
+        .encoding(encoding)
+        .include_header_row(args.include_header_row)
+        .error_policy(args.error_policy.into())
+        .execute_with_report()?;
+    print_skipped(&report.skipped);
+    println!("{}", report.output);
+    Ok(ExitCode::SUCCESS)
+}
+
+#[derive(Args)]
+pub struct ColumnsArgs {
+    #[command(flatten)]
+    input: Input,
+}
+
+pub fn run_columns(args: ColumnsArgs) -> Result<ExitCode> {
+    let (file_path, file_data, encoding) = args.input.source()?;
+    let output = ColumnCount::new()
+        .file_path_opt(file_path)
+        .file_data_opt(file_data)
+        .encoding(encoding)
+        .execute()?;
+    println!("{output}");
+    Ok(ExitCode::SUCCESS)
+}
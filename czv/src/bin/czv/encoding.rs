@@ -0,0 +1,40 @@
+use clap::Args;
+use czv::{Encoding, Result};
+use std::io::Read;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+#[derive(Args)]
+pub struct InputArgs {
+    /// CSV file path. Reads from stdin if omitted or `-`.
+    input: Option<PathBuf>,
+
+    /// The character encoding of the input, e.g. `latin1` or `utf-16le`, or `auto` to detect it.
+    #[arg(long, default_value = "auto")]
+    encoding: Encoding,
+
+    /// Print the name of the encoding the input is read with instead of transcoding it.
+    #[arg(long)]
+    detect: bool,
+}
+
+pub fn run_input(args: InputArgs) -> Result<ExitCode> {
+    let (file_path, file_data) = match args.input {
+        Some(path) if path.as_os_str() != "-" => (Some(path), None),
+        _ => {
+            let mut data = vec![];
+            std::io::stdin().read_to_end(&mut data)?;
+            (None, Some(data))
+        }
+    };
+    let input = czv::Input::new()
+        .file_path_opt(file_path)
+        .file_data_opt(file_data)
+        .encoding(args.encoding);
+    if args.detect {
+        println!("{}", input.detect()?);
+    } else {
+        print!("{}", input.execute()?);
+    }
+    Ok(ExitCode::SUCCESS)
+}
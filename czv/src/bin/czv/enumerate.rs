@@ -0,0 +1,90 @@
+use crate::{print_skipped, Input, Policy};
+use clap::{ArgGroup, Args};
+use czv::apply::HashAlgorithm;
+use czv::enumerate::EnumerateValue;
+use czv::{Enumerate, Result};
+use std::process::ExitCode;
+
+#[derive(Args)]
+#[command(group(ArgGroup::new("value").args(["uuid", "constant", "copy", "hash", "file_name"])))]
+pub struct EnumerateArgs {
+    #[command(flatten)]
+    input: Input,
+
+    /// The index of the first row.
+    #[arg(
+        long,
+        default_value_t = 0,
+        conflicts_with = "value",
+        allow_negative_numbers = true
+    )]
+    start: i64,
+
+    /// The difference between the indexes of consecutive rows.
+    #[arg(
+        long,
+        default_value_t = 1,
+        conflicts_with = "value",
+        allow_negative_numbers = true
+    )]
+    step: i64,
+
+    /// Add a random UUID instead of an index.
+    #[arg(long)]
+    uuid: bool,
+
+    /// Add a constant value instead of an index.
+    #[arg(long, value_name = "VALUE")]
+    constant: Option<String>,
+
+    /// Add a copy of a column instead of an index.
+    #[arg(long, value_name = "COLUMN")]
+    copy: Option<String>,
+
+    /// Add a hash of the row's fields instead of an index: md5, sha1 or sha256.
+    #[arg(long, value_name = "ALGORITHM", value_parser = str::parse::<HashAlgorithm>)]
+    hash: Option<HashAlgorithm>,
+
+    /// Add the name of the input file instead of an index, or an empty value for stdin.
+    #[arg(long)]
+    file_name: bool,
+
+    /// The name of the added column. Defaults to a name based on the value, e.g. `index`.
+    #[arg(short, long, value_name = "NAME")]
+    new_column: Option<String>,
+
+    /// How to handle malformed records.
+    #[arg(long, value_enum, default_value_t)]
+    error_policy: Policy,
+}
+
+pub fn run(args: EnumerateArgs) -> Result<ExitCode> {
+    let value = if args.uuid {
+        EnumerateValue::Uuid
+    } else if let Some(constant) = args.constant {
+        EnumerateValue::Constant(constant)
+    } else if let Some(column) = args.copy {
+        EnumerateValue::Copy(column)
+    } else if let Some(algorithm) = args.hash {
+        EnumerateValue::Hash(algorithm)
+    } else if args.file_name {
+        EnumerateValue::FileName
+    } else {
+        EnumerateValue::Index {
+            start: args.start,
+            step: args.step,
+        }
+    };
+    let (file_path, file_data, encoding) = args.input.source()?;
+    let report = Enumerate::new()
+        .file_path_opt(file_path)
+        .file_data_opt(file_data)
+        .encoding(encoding)
+        .value(value)
+        .new_column_opt(args.new_column)
+        .error_policy(args.error_policy.into())
+        .execute_with_report()?;
+    print_skipped(&report.skipped);
+    print!("{}", report.output);
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,57 @@
+use clap::Args;
+use czv::{ExcelSheets, FromExcel, Result};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+#[derive(Args)]
+pub struct FromExcelArgs {
+    /// Workbook file path (.xlsx, .xlsm, .xlsb, .xls or .ods).
+    input: PathBuf,
+
+    /// The name of the sheet to read. Defaults to the first sheet.
+    #[arg(long, conflicts_with = "sheet_index")]
+    sheet: Option<String>,
+
+    /// The 0-indexed position of the sheet to read.
+    #[arg(long)]
+    sheet_index: Option<usize>,
+
+    /// The cells to read in A1 notation, e.g. A1:C10, or a start cell such as B3.
+    #[arg(long)]
+    range: Option<String>,
+
+    /// The chrono format string for date cells, e.g. %d/%m/%Y.
+    #[arg(long)]
+    date_format: Option<String>,
+
+    /// List the sheets with their row and column counts instead of converting one.
+    #[arg(long, conflicts_with_all = ["sheet", "sheet_index", "range", "date_format"])]
+    list_sheets: bool,
+}
+
+pub fn run_from_excel(args: FromExcelArgs) -> Result<ExitCode> {
+    if args.list_sheets {
+        let sheets = ExcelSheets::new().file_path(args.input).execute()?;
+        let mut wtr = csv::Writer::from_writer(std::io::stdout());
+        wtr.write_record(["sheet", "rows", "columns"])?;
+        for sheet in sheets {
+            wtr.write_record([
+                sheet.name,
+                sheet.rows.to_string(),
+                sheet.columns.to_string(),
+            ])?;
+        }
+        wtr.flush()?;
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let output = FromExcel::new()
+        .file_path(args.input)
+        .sheet_name_opt(args.sheet)
+        .sheet_index_opt(args.sheet_index)
+        .range_opt(args.range)
+        .date_format_opt(args.date_format)
+        .execute()?;
+    print!("{output}");
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,75 @@
+use crate::{print_skipped, Input, Policy};
+use clap::Args;
+use czv::{Explode, Implode, Result};
+use std::process::ExitCode;
+
+#[derive(Args)]
+pub struct ExplodeArgs {
+    #[command(flatten)]
+    input: Input,
+
+    /// The column to split.
+    #[arg(short, long)]
+    column: String,
+
+    /// The separator between the parts of a value.
+    #[arg(short, long, default_value = ",")]
+    separator: String,
+
+    /// How to handle malformed records.
+    #[arg(long, value_enum, default_value_t)]
+    error_policy: Policy,
+}
+
+pub fn run_explode(args: ExplodeArgs) -> Result<ExitCode> {
+    let (file_path, file_data, encoding) = args.input.source()?;
+    let report = Explode::new()
+        .file_path_opt(file_path)
+        .file_data_opt(file_data)
+        .encoding(encoding)
+        .column(args.column)
+        .separator(args.separator)
+        .error_policy(args.error_policy.into())
+        .execute_with_report()?;
+    print_skipped(&report.skipped);
+    print!("{}", report.output);
+    Ok(ExitCode::SUCCESS)
+}
+
+#[derive(Args)]
+pub struct ImplodeArgs {
+    #[command(flatten)]
+    input: Input,
+
+    /// The column whose values are joined.
+    #[arg(short, long)]
+    column: String,
+
+    /// The key columns, e.g. `store,date`. Defaults to every other column.
+    #[arg(short, long, value_name = "COLUMNS", value_delimiter = ',')]
+    keys: Vec<String>,
+
+    /// The separator between the joined values.
+    #[arg(short, long, default_value = ",")]
+    separator: String,
+
+    /// How to handle malformed records.
+    #[arg(long, value_enum, default_value_t)]
+    error_policy: Policy,
+}
+
+pub fn run_implode(args: ImplodeArgs) -> Result<ExitCode> {
+    let (file_path, file_data, encoding) = args.input.source()?;
+    let report = Implode::new()
+        .file_path_opt(file_path)
+        .file_data_opt(file_data)
+        .encoding(encoding)
+        .column(args.column)
+        .keys(args.keys)
+        .separator(args.separator)
+        .error_policy(args.error_policy.into())
+        .execute_with_report()?;
+    print_skipped(&report.skipped);
+    print!("{}", report.output);
+    Ok(ExitCode::SUCCESS)
+}
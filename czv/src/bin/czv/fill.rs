@@ -0,0 +1,57 @@
+use crate::{print_skipped, Input, Policy};
+use clap::Args;
+use czv::fill::FillMethod;
+use czv::{Fill, Result};
+use std::process::ExitCode;
+
+#[derive(Args)]
+pub struct FillArgs {
+    #[command(flatten)]
+    input: Input,
+
+    /// The columns to fill, e.g. `price,count`. Defaults to every column not in `--group-by`.
+    #[arg(short, long, value_name = "COLUMNS", value_delimiter = ',')]
+    columns: Vec<String>,
+
+    /// Only fill from rows with the same values in these key columns, e.g. `store`.
+    #[arg(short, long, value_name = "COLUMNS", value_delimiter = ',')]
+    group_by: Vec<String>,
+
+    /// Fill with the next non-empty value instead of the previous one.
+    #[arg(short, long, conflicts_with_all = ["first", "value"])]
+    backward: bool,
+
+    /// Fill with the first non-empty value instead of the previous one.
+    #[arg(short, long, conflicts_with = "value")]
+    first: bool,
+
+    /// Fill with a constant value instead of the previous one.
+    #[arg(long)]
+    value: Option<String>,
+
+    /// How to handle malformed records.
+    #[arg(long, value_enum, default_value_t)]
+    error_policy: Policy,
+}
+
+pub fn run(args: FillArgs) -> Result<ExitCode> {
+    let (file_path, file_data, encoding) = args.input.source()?;
+    let method = match args.value {
+        Some(value) => FillMethod::Constant(value),
+        None if args.backward => FillMethod::Backward,
+        None if args.first => FillMethod::First,
+        None => FillMethod::Forward,
+    };
+    let report = Fill::new()
+        .file_path_opt(file_path)
+        .file_data_opt(file_data)
+        .encoding(encoding)
+        .columns(args.columns)
+        .method(method)
+        .group_by(args.group_by)
+        .error_policy(args.error_policy.into())
+        .execute_with_report()?;
+    print_skipped(&report.skipped);
+    print!("{}", report.output);
+    Ok(ExitCode::SUCCESS)
+}
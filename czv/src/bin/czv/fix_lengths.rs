@@ -0,0 +1,37 @@
+use crate::{print_skipped, Input, Policy};
+use clap::Args;
+use czv::{FixLengths, Result};
+use std::process::ExitCode;
+
+#[derive(Args)]
+pub struct FixLengthsArgs {
+    #[command(flatten)]
+    input: Input,
+
+    /// The number of fields of every row. Defaults to the length of the header row.
+    #[arg(short, long, value_name = "N", conflicts_with = "longest")]
+    length: Option<usize>,
+
+    /// Use the number of fields of the longest row as the length.
+    #[arg(long)]
+    longest: bool,
+
+    /// How to handle malformed records.
+    #[arg(long, value_enum, default_value_t)]
+    error_policy: Policy,
+}
+
+pub fn run(args: FixLengthsArgs) -> Result<ExitCode> {
+    let (file_path, file_data, encoding) = args.input.source()?;
+    let report = FixLengths::new()
+        .file_path_opt(file_path)
+        .file_data_opt(file_data)
+        .encoding(encoding)
+        .length_opt(args.length)
+        .longest(args.longest)
+        .error_policy(args.error_policy.into())
+        .execute_with_report()?;
+    print_skipped(&report.skipped);
+    print!("{}", report.output);
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,131 @@
+use crate::{print_skipped, Input, Policy};
+use clap::{Args, ValueEnum};
+use czv::fixed_width::Alignment;
+use czv::{CzvError, Encoding, FromFixedWidth, Result, ToFixedWidth};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+/// How values are padded to their column width.
+#[derive(Clone, Copy, Default, ValueEnum)]
+pub enum Align {
+    #[default]
+    Left,
+    Right,
+}
+
+impl From<Align> for Alignment {
+    fn from(value: Align) -> Self {
+        match value {
+            Align::Left => Alignment::Left,
+            Align::Right => Alignment::Right,
+        }
+    }
+}
+
+/// Parses a `name:width` layout option.
+fn parse_layout_column(value: &str) -> Result<(String, usize)> {
+    let (name, width) = value.rsplit_once(':').ok_or_else(|| {
+        CzvError::InvalidOptions(format!("Expected name:width but found {value:?}."))
+    })?;
+    let width = width.parse().map_err(|_| {
+        CzvError::InvalidOptions(format!(
+            "Expected a width in characters but found {width:?}."
+        ))
+    })?;
+    Ok((name.to_string(), width))
+}
+
+#[derive(Args)]
+pub struct FromFixedWidthArgs {
+    /// Fixed-width file path. Reads from stdin if omitted or `-`.
+    input: Option<PathBuf>,
+
+    /// The character encoding of the input, e.g. `latin1` or `utf-16le`, or `auto` to detect it.
+    #[arg(long, default_value = "utf-8")]
+    encoding: Encoding,
+
+    /// The width of each column in characters, e.g. `10,6`.
+    #[arg(short, long, value_delimiter = ',', required_unless_present = "layout")]
+    widths: Vec<usize>,
+
+    /// The name and width of each column, e.g. `fruit:10,price:6`.
+    #[arg(short, long, value_name = "NAME:WIDTH", value_delimiter = ',', value_parser = parse_layout_column, conflicts_with = "widths")]
+    layout: Vec<(String, usize)>,
+
+    /// The first line is data rather than a header row.
+    #[arg(long)]
+    no_header_row: bool,
+
+    /// Keep the whitespace around each field.
+    #[arg(long)]
+    no_trim: bool,
+
+    /// How to handle malformed lines.
+    #[arg(long, value_enum, default_value_t)]
+    error_policy: Policy,
+}
+
+pub fn run_from_fixed_width(args: FromFixedWidthArgs) -> Result<ExitCode> {
+    let (file_path, file_data, encoding) = Input::new(args.input, args.encoding).source()?;
+    let report = FromFixedWidth::new()
+        .file_path_opt(file_path)
+        .file_data_opt(file_data)
+        .encoding(encoding)
+        .widths(args.widths)
+        .layout(args.layout)
+        .has_header_row(!args.no_header_row)
+        .trim(!args.no_trim)
+        .error_policy(args.error_policy.into())
+        .execute_with_report()?;
+    print_skipped(&report.skipped);
+    print!("{}", report.output);
+    Ok(ExitCode::SUCCESS)
+}
+
+#[derive(Args)]
+pub struct ToFixedWidthArgs {
+    #[command(flatten)]
+    input: Input,
+
+    /// The width of each column in characters, e.g. `10,6`. Defaults to the longest value of each column.
+    #[arg(short, long, value_delimiter = ',')]
+    widths: Vec<usize>,
+
+    /// The header name and width of each column to write, e.g. `fruit:10,price:6`.
+    #[arg(short, long, value_name = "NAME:WIDTH", value_delimiter = ',', value_parser = parse_layout_column, conflicts_with = "widths")]
+    layout: Vec<(String, usize)>,
+
+    /// How values are padded to their column width.
+    #[arg(long, value_enum, default_value_t)]
+    align: Align,
+
+    /// Cut values longer than their column instead of failing.
+    #[arg(long)]
+    truncate: bool,
+
+    /// Omit the header row (first row) from the output.
+    #[arg(long)]
+    no_header_row: bool,
+
+    /// How to handle malformed records.
+    #[arg(long, value_enum, default_value_t)]
+    error_policy: Policy,
+}
+
+pub fn run_to_fixed_width(args: ToFixedWidthArgs) -> Result<ExitCode> {
+    let (file_path, file_data, encoding) = args.input.source()?;
+    let report = ToFixedWidth::new()
+        .file_path_opt(file_path)
+        .file_data_opt(file_data)
+        .encoding(encoding)
+        .widths(args.widths)
+        .layout(args.layout)
+        .alignment(args.align.into())
+        .truncate(args.truncate)
+        .include_header_row(!args.no_header_row)
+        .error_policy(args.error_policy.into())
+        .execute_with_report()?;
+    print_skipped(&report.skipped);
+    print!("{}", report.output);
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,89 @@
+use crate::{print_skipped, Input, Policy};
+use clap::Args;
+use czv::json::{ArrayMode, JsonFormat};
+use czv::{Encoding, FromJson, Result, ToJson};
+use std::io::BufWriter;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+#[derive(Args)]
+pub struct ToJsonArgs {
+    #[command(flatten)]
+    input: Input,
+
+    /// Write one JSON object per line (JSON Lines) instead of a JSON array.
+    #[arg(long)]
+    lines: bool,
+
+    /// Convert values to the type inferred from their column, with empty values as null.
+    #[arg(long)]
+    infer_types: bool,
+
+    /// Turn dotted header names such as `address.city` into nested objects.
+    #[arg(long)]
+    nested: bool,
+
+    /// How to handle malformed records.
+    #[arg(long, value_enum, default_value_t)]
+    error_policy: Policy,
+}
+
+pub fn run_to_json(args: ToJsonArgs) -> Result<ExitCode> {
+    let (file_path, file_data, encoding) = args.input.source()?;
+    let format = match args.lines {
+        true => JsonFormat::Lines,
+        false => JsonFormat::Array,
+    };
+    let report = ToJson::new()
+        .file_path_opt(file_path)
+        .file_data_opt(file_data)
+        .encoding(encoding)
+        .format(format)
+        .infer_types(args.infer_types)
+        .nested(args.nested)
+        .error_policy(args.error_policy.into())
+        .execute_to_writer(BufWriter::new(std::io::stdout().lock()))?;
+    print_skipped(&report.skipped);
+    Ok(ExitCode::SUCCESS)
+}
+
+#[derive(Args)]
+pub struct FromJsonArgs {
+    /// JSON file path. Reads from stdin if omitted or `-`.
+    input: Option<PathBuf>,
+
+    /// The character encoding of the input, e.g. `latin1` or `utf-16le`, or `auto` to detect it.
+    #[arg(long, default_value = "utf-8")]
+    encoding: Encoding,
+
+    /// Write one row per array element instead of joining the elements.
+    #[arg(long)]
+    explode: bool,
+
+    /// The separator for joined array elements.
+    #[arg(long, default_value = ",")]
+    separator: String,
+
+    /// How to handle malformed records.
+    #[arg(long, value_enum, default_value_t)]
+    error_policy: Policy,
+}
+
+pub fn run_from_json(args: FromJsonArgs) -> Result<ExitCode> {
+    let (file_path, file_data, encoding) = Input::new(args.input, args.encoding).source()?;
+    let arrays = match args.explode {
+        true => ArrayMode::Explode,
+        false => ArrayMode::Join,
+    };
+    let report = FromJson::new()
+        .file_path_opt(file_path)
+        .file_data_opt(file_data)
+        .encoding(encoding)
+        .arrays(arrays)
+        .separator(args.separator)
+        .error_policy(args.error_policy.into())
+        .execute_with_report()?;
+    print_skipped(&report.skipped);
+    print!("{}", report.output);
+    Ok(ExitCode::SUCCESS)
+}
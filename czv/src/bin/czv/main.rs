@@ -0,0 +1,249 @@
+//! Command-line interface for [czv](https://github.com/rzmk/czv).
+//!
+//! Every subcommand reads CSV data from a file path, or from stdin if the path is omitted or `-`,
+//! and writes its output to stdout.
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use czv::policy::SkippedRecord;
+use czv::{Encoding, ErrorPolicy, Result};
+use std::io::Read;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+mod aggregate;
+mod apply;
+#[cfg(feature = "arrow")]
+mod arrow;
+mod count;
+mod encoding;
+mod enumerate;
+#[cfg(feature = "excel")]
+mod excel;
+mod explode;
+mod fill;
+mod fix_lengths;
+mod fixed_width;
+mod json;
+#[cfg(feature = "rhai")]
+mod map;
+#[cfg(feature = "parquet")]
+mod parquet;
+mod pivot;
+#[cfg(feature = "sqlite")]
+mod query;
+mod schema;
+mod slice;
+mod sql;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+mod table;
+mod transpose;
+mod validate;
+
+#[derive(Parser)]
+#[command(
+    name = "czv",
+    version,
+    about = "CSV operations for data engineering and analysis tasks."
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Count the number of rows.
+    Count(count::CountArgs),
+    /// Count the number of columns.
+    Columns(count::ColumnsArgs),
+    /// Extract a section of rows.
+    Slice(slice::SliceArgs),
+    /// Render as an aligned text, Markdown or HTML table.
+    Table(table::TableArgs),
+    /// Transform column values in place or into new columns.
+    Apply(apply::ApplyArgs),
+    /// Add a column of row indexes, UUIDs, constants, copies, row hashes or file names.
+    Enumerate(enumerate::EnumerateArgs),
+    /// Split the values of a column into one row per part.
+    Explode(explode::ExplodeArgs),
+    /// Collapse consecutive rows sharing keys into one row with joined values.
+    Implode(explode::ImplodeArgs),
+    /// Pad or truncate ragged rows to the same number of fields.
+    FixLengths(fix_lengths::FixLengthsArgs),
+    /// Fill empty values from other rows or with a constant.
+    Fill(fill::FillArgs),
+    /// Run a Rhai script for each row to compute columns, filter rows or keep state.
+    #[cfg(feature = "rhai")]
+    Map(map::MapArgs),
+    /// Group rows by key columns and aggregate the other columns.
+    Aggregate(aggregate::AggregateArgs),
+    /// Reshape from long to wide, turning the values of a column into columns.
+    Pivot(pivot::PivotArgs),
+    /// Reshape from wide to long, turning columns into rows.
+    Unpivot(pivot::UnpivotArgs),
+    /// Swap the rows and columns.
+    Transpose(transpose::TransposeArgs),
+    /// Check for structural problems, exiting with an error if any are found.
+    Validate(validate::ValidateArgs),
+    /// Transcode the input to UTF-8 from a detected or given encoding, or print the detected encoding.
+    Input(encoding::InputArgs),
+    /// Validate rows against a JSON Schema or generate a starter schema.
+    #[command(subcommand)]
+    Schema(schema::SchemaCommand),
+    /// Convert to a JSON array of objects or JSON Lines.
+    ToJson(json::ToJsonArgs),
+    /// Convert from a JSON array of objects or JSON Lines.
+    FromJson(json::FromJsonArgs),
+    /// Convert to fixed-width text.
+    ToFixedWidth(fixed_width::ToFixedWidthArgs),
+    /// Convert from fixed-width text.
+    FromFixedWidth(fixed_width::FromFixedWidthArgs),
+    /// Run a SQL query over CSV files loaded as tables.
+    #[cfg(feature = "sqlite")]
+    Query(query::QueryArgs),
+    /// Generate a SQL script that creates a table and inserts the rows.
+    ToSql(sql::ToSqlArgs),
+    /// Load into a table of a SQLite database, printing the number of rows inserted.
+    #[cfg(feature = "sqlite")]
+    ToSqlite(sqlite::ToSqliteArgs),
+    /// Convert to an Arrow IPC stream file.
+    #[cfg(feature = "arrow")]
+    ToArrow(arrow::ToArrowArgs),
+    /// Convert from an Arrow IPC stream file.
+    #[cfg(feature = "arrow")]
+    FromArrow(arrow::FromArrowArgs),
+    /// Convert to a Parquet file.
+    #[cfg(feature = "parquet")]
+    ToParquet(parquet::ToParquetArgs),
+    /// Convert from a Parquet file.
+    #[cfg(feature = "parquet")]
+    FromParquet(parquet::FromParquetArgs),
+    /// Convert a sheet of an Excel or OpenDocument workbook to CSV.
+    #[cfg(feature = "excel")]
+    FromExcel(excel::FromExcelArgs),
+}
+
+/// The CSV input shared by every subcommand.
+#[derive(Args)]
+pub struct Input {
+    /// CSV file path. Reads from stdin if omitted or `-`.
+    input: Option<PathBuf>,
+
+    /// The character encoding of the input, e.g. `latin1` or `utf-16le`, or `auto` to detect it.
+    #[arg(long, default_value = "utf-8")]
+    encoding: Encoding,
+}
+
+impl Input {
+    /// Wraps the path of a subcommand whose input is not CSV data.
+    pub fn new(input: Option<PathBuf>, encoding: Encoding) -> Self {
+        Self { input, encoding }
+    }
+
+    /// Returns the `(file_path, file_data, encoding)` to pass to a builder, reading stdin if needed.
+    ///
+    /// Stdin is transcoded to UTF-8 as it is read, so the encoding only applies to the file path.
+    pub fn source(self) -> Result<(Option<PathBuf>, Option<String>, Encoding)> {
+        match self.input {
+            Some(path) if path.as_os_str() != "-" => Ok((Some(path), None, self.encoding)),
+            _ => {
+                let mut data = vec![];
+                std::io::stdin().read_to_end(&mut data)?;
+                let data = czv::encoding::input(None, Some(data), self.encoding)?;
+                Ok((None, Some(data), self.encoding))
+            }
+        }
+    }
+}
+
+/// How to handle malformed records.
+#[derive(Clone, Copy, Default, ValueEnum)]
+pub enum Policy {
+    /// Stop with an error at the first malformed record.
+    #[default]
+    Fail,
+    /// Skip malformed records.
+    Skip,
+    /// Skip malformed records and print each of them to stderr.
+    Collect,
+}
+
+impl From<Policy> for ErrorPolicy {
+    fn from(value: Policy) -> Self {
+        match value {
+            Policy::Fail => ErrorPolicy::Fail,
+            Policy::Skip => ErrorPolicy::Skip,
+            Policy::Collect => ErrorPolicy::Collect,
+        }
+    }
+}
+
+/// Parses a `name=type` column type option.
+pub fn parse_column_type(value: &str) -> Result<(String, czv::infer::ColumnType)> {
+    let (name, column_type) = value.rsplit_once('=').ok_or_else(|| {
+        czv::CzvError::InvalidOptions(format!("Expected name=type but found {value:?}."))
+    })?;
+    Ok((name.to_string(), column_type.parse()?))
+}
+
+/// Prints the records skipped with `--error-policy collect` to stderr.
+pub fn print_skipped(skipped: &[SkippedRecord]) {
+    for record in skipped {
+        match record.record {
+            Some(index) => eprintln!("skipped record {index}: {}", record.reason),
+            None => eprintln!("skipped record: {}", record.reason),
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Command::Count(args) => count::run_count(args),
+        Command::Columns(args) => count::run_columns(args),
+        Command::Slice(args) => slice::run(args),
+        Command::Table(args) => table::run(args),
+        Command::Apply(args) => apply::run(args),
+        Command::Enumerate(args) => enumerate::run(args),
+        Command::Explode(args) => explode::run_explode(args),
+        Command::Implode(args) => explode::run_implode(args),
+        Command::FixLengths(args) => fix_lengths::run(args),
+        Command::Fill(args) => fill::run(args),
+        #[cfg(feature = "rhai")]
+        Command::Map(args) => map::run(args),
+        Command::Aggregate(args) => aggregate::run(args),
+        Command::Pivot(args) => pivot::run_pivot(args),
+        Command::Unpivot(args) => pivot::run_unpivot(args),
+        Command::Transpose(args) => transpose::run(args),
+        Command::Validate(args) => validate::run(args),
+        Command::Input(args) => encoding::run_input(args),
+        Command::Schema(command) => schema::run(command),
+        Command::ToJson(args) => json::run_to_json(args),
+        Command::FromJson(args) => json::run_from_json(args),
+        Command::ToFixedWidth(args) => fixed_width::run_to_fixed_width(args),
+        Command::FromFixedWidth(args) => fixed_width::run_from_fixed_width(args),
+        #[cfg(feature = "sqlite")]
+        Command::Query(args) => query::run(args),
+        Command::ToSql(args) => sql::run(args),
+        #[cfg(feature = "sqlite")]
+        Command::ToSqlite(args) => sqlite::run(args),
+        #[cfg(feature = "arrow")]
+        Command::ToArrow(args) => arrow::run_to_arrow(args),
+        #[cfg(feature = "arrow")]
+        Command::FromArrow(args) => arrow::run_from_arrow(args),
+        #[cfg(feature = "parquet")]
+        Command::ToParquet(args) => parquet::run_to_parquet(args),
+        #[cfg(feature = "parquet")]
+        Command::FromParquet(args) => parquet::run_from_parquet(args),
+        #[cfg(feature = "excel")]
+        Command::FromExcel(args) => excel::run_from_excel(args),
+    };
+    match result {
+        Ok(code) => code,
+        Err(err) => {
+            eprintln!("czv: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
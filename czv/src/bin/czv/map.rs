@@ -0,0 +1,52 @@
+use crate::{print_skipped, Input, Policy};
+use clap::Args;
+use czv::{Map, Result};
+use std::process::ExitCode;
+
+#[derive(Args)]
+pub struct MapArgs {
+    /// The Rhai script run for each row, with the row's fields in the `row` object map, e.g.
+    /// `row.total = row.price.parse_float() * 2`. The row is dropped if the script evaluates to `false`.
+    script: String,
+
+    #[command(flatten)]
+    input: Input,
+
+    /// A Rhai script run once before the first row, e.g. `let total = 0;` to keep a running total.
+    #[arg(short, long, value_name = "SCRIPT")]
+    begin: Option<String>,
+
+    /// The names of the columns added by the script, e.g. `total,label`.
+    #[arg(short, long, value_name = "NAMES", value_delimiter = ',')]
+    new_columns: Vec<String>,
+
+    /// The most operations each run of a script may perform, or 0 for no limit.
+    #[arg(long, value_name = "COUNT", default_value_t = 1_000_000)]
+    max_operations: u64,
+
+    /// Discard the output of `print` and `debug` instead of writing it to stderr.
+    #[arg(long)]
+    quiet: bool,
+
+    /// How to handle malformed records.
+    #[arg(long, value_enum, default_value_t)]
+    error_policy: Policy,
+}
+
+pub fn run(args: MapArgs) -> Result<ExitCode> {
+    let (file_path, file_data, encoding) = args.input.source()?;
+    let report = Map::new()
+        .file_path_opt(file_path)
+        .file_data_opt(file_data)
+        .encoding(encoding)
+        .script(args.script)
+        .begin_opt(args.begin)
+        .new_columns(args.new_columns)
+        .max_operations(args.max_operations)
+        .print(!args.quiet)
+        .error_policy(args.error_policy.into())
+        .execute_with_report()?;
+    print_skipped(&report.skipped);
+    print!("{}", report.output);
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,87 @@
+use crate::{print_skipped, Input, Policy};
+use clap::{Args, ValueEnum};
+use czv::infer::ColumnType;
+use czv::parquet::ParquetCompression;
+use czv::{FromParquet, Result, ToParquet};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+/// The compression codec for Parquet column data.
+#[derive(Clone, Copy, Default, ValueEnum)]
+pub enum Compression {
+    None,
+    #[default]
+    Snappy,
+    Gzip,
+    Zstd,
+    Brotli,
+    Lz4,
+}
+
+impl From<Compression> for ParquetCompression {
+    fn from(value: Compression) -> Self {
+        match value {
+            Compression::None => ParquetCompression::Uncompressed,
+            Compression::Snappy => ParquetCompression::Snappy,
+            Compression::Gzip => ParquetCompression::Gzip,
+            Compression::Zstd => ParquetCompression::Zstd,
+            Compression::Brotli => ParquetCompression::Brotli,
+            Compression::Lz4 => ParquetCompression::Lz4,
+        }
+    }
+}
+
+#[derive(Args)]
+pub struct ToParquetArgs {
+    #[command(flatten)]
+    input: Input,
+
+    /// Parquet file path to write to.
+    #[arg(short, long)]
+    output: PathBuf,
+
+    /// Set a column's type instead of inferring it, as `name=type` where type is boolean, integer, float or string.
+    #[arg(long = "type", value_name = "NAME=TYPE", value_parser = crate::parse_column_type)]
+    types: Vec<(String, ColumnType)>,
+
+    /// The compression codec for column data.
+    #[arg(long, value_enum, default_value_t)]
+    compression: Compression,
+
+    /// The maximum number of rows in a row group.
+    #[arg(long, default_value_t = 1024 * 1024)]
+    row_group_size: usize,
+
+    /// How to handle malformed records.
+    #[arg(long, value_enum, default_value_t)]
+    error_policy: Policy,
+}
+
+pub fn run_to_parquet(args: ToParquetArgs) -> Result<ExitCode> {
+    let (file_path, file_data, encoding) = args.input.source()?;
+    let report = ToParquet::new()
+        .file_path_opt(file_path)
+        .file_data_opt(file_data)
+        .encoding(encoding)
+        .column_types(args.types)
+        .compression(args.compression.into())
+        .row_group_size(args.row_group_size)
+        .error_policy(args.error_policy.into())
+        .execute_to_writer(BufWriter::new(File::create(args.output)?))?;
+    print_skipped(&report.skipped);
+    Ok(ExitCode::SUCCESS)
+}
+
+#[derive(Args)]
+pub struct FromParquetArgs {
+    /// Parquet file path.
+    input: PathBuf,
+}
+
+pub fn run_from_parquet(args: FromParquetArgs) -> Result<ExitCode> {
+    let output = FromParquet::new().file_path(args.input).execute()?;
+    print!("{output}");
+    Ok(ExitCode::SUCCESS)
+}
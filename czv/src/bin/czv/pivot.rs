@@ -0,0 +1,102 @@
+use crate::{print_skipped, Input, Policy};
+use clap::Args;
+use czv::aggregate::AggregateFunction;
+use czv::{Pivot, Result, Unpivot};
+use std::process::ExitCode;
+
+#[derive(Args)]
+pub struct PivotArgs {
+    #[command(flatten)]
+    input: Input,
+
+    /// The columns identifying each output row, e.g. `store,date`.
+    #[arg(short, long, value_name = "COLUMNS", value_delimiter = ',')]
+    index: Vec<String>,
+
+    /// The column whose values become the new columns.
+    #[arg(short, long)]
+    column: String,
+
+    /// The column whose values fill the new columns.
+    #[arg(short, long)]
+    value: String,
+
+    /// How to combine the values of rows with the same index and column: count, sum, mean, min,
+    /// max, first, last, distinct_count or concat.
+    #[arg(short, long = "agg", value_name = "FUNCTION", default_value = "first", value_parser = str::parse::<AggregateFunction>)]
+    aggregate: AggregateFunction,
+
+    /// The separator of values joined with `concat`.
+    #[arg(long, default_value = ",")]
+    separator: String,
+
+    /// How to handle malformed records.
+    #[arg(long, value_enum, default_value_t)]
+    error_policy: Policy,
+}
+
+pub fn run_pivot(args: PivotArgs) -> Result<ExitCode> {
+    let (file_path, file_data, encoding) = args.input.source()?;
+    let report = Pivot::new()
+        .file_path_opt(file_path)
+        .file_data_opt(file_data)
+        .encoding(encoding)
+        .index(args.index)
+        .pivot_column(args.column)
+        .value_column(args.value)
+        .aggregate(args.aggregate)
+        .separator(args.separator)
+        .error_policy(args.error_policy.into())
+        .execute_with_report()?;
+    print_skipped(&report.skipped);
+    print!("{}", report.output);
+    Ok(ExitCode::SUCCESS)
+}
+
+#[derive(Args)]
+pub struct UnpivotArgs {
+    #[command(flatten)]
+    input: Input,
+
+    /// The columns kept in every row, e.g. `store,date`.
+    #[arg(short, long, value_name = "COLUMNS", value_delimiter = ',')]
+    id: Vec<String>,
+
+    /// The columns turned into rows. Defaults to every column not in `--id`.
+    #[arg(short, long, value_name = "COLUMNS", value_delimiter = ',')]
+    columns: Vec<String>,
+
+    /// The header name of the column holding the column names.
+    #[arg(long, default_value = "variable")]
+    variable_name: String,
+
+    /// The header name of the column holding the values.
+    #[arg(long, default_value = "value")]
+    value_name: String,
+
+    /// Leave out rows with an empty value.
+    #[arg(long)]
+    skip_empty: bool,
+
+    /// How to handle malformed records.
+    #[arg(long, value_enum, default_value_t)]
+    error_policy: Policy,
+}
+
+pub fn run_unpivot(args: UnpivotArgs) -> Result<ExitCode> {
+    let (file_path, file_data, encoding) = args.input.source()?;
+    let report = Unpivot::new()
+        .file_path_opt(file_path)
+        .file_data_opt(file_data)
+        .encoding(encoding)
+        .id_columns(args.id)
+        .value_columns(args.columns)
+        .variable_name(args.variable_name)
+        .value_name(args.value_name)
+        .skip_empty(args.skip_empty)
+        .error_policy(args.error_policy.into())
+        .execute_with_report()?;
+    print_skipped(&report.skipped);
+    print!("{}", report.output);
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,57 @@
+use crate::{print_skipped, Input, Policy};
+use clap::Args;
+use czv::{Encoding, Query, Result};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+#[derive(Args)]
+pub struct QueryArgs {
+    /// The SQL query, e.g. `SELECT * FROM fruits WHERE price > 2`.
+    sql: String,
+
+    /// A CSV file to query as a table, as `name=path` or `path` to name the table after the file.
+    /// Use `name=-` to read a table from stdin.
+    #[arg(short, long = "table", value_name = "[NAME=]PATH", required = true)]
+    tables: Vec<String>,
+
+    /// The character encoding of the tables, e.g. `latin1` or `utf-16le`, or `auto` to detect it.
+    #[arg(long, default_value = "utf-8")]
+    encoding: Encoding,
+
+    /// How to handle malformed records.
+    #[arg(long, value_enum, default_value_t)]
+    error_policy: Policy,
+}
+
+pub fn run(args: QueryArgs) -> Result<ExitCode> {
+    let mut file_paths = vec![];
+    let mut file_data = vec![];
+    for table in args.tables {
+        let (name, path) = match table.split_once('=') {
+            Some((name, path)) => (name.to_string(), PathBuf::from(path)),
+            None => {
+                let path = PathBuf::from(table);
+                let name = path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .unwrap_or("data")
+                    .to_string();
+                (name, path)
+            }
+        };
+        match Input::new(Some(path), args.encoding).source()? {
+            (Some(path), ..) => file_paths.push((name, path)),
+            (_, data, _) => file_data.push((name, data.unwrap_or_default())),
+        }
+    }
+    let report = Query::new()
+        .file_paths(file_paths)
+        .file_data(file_data)
+        .sql(args.sql)
+        .encoding(args.encoding)
+        .error_policy(args.error_policy.into())
+        .execute_with_report()?;
+    print_skipped(&report.skipped);
+    print!("{}", report.output);
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,78 @@
+use crate::{print_skipped, Input, Policy};
+use clap::{Args, Subcommand};
+use czv::{Result, SchemaGenerate, SchemaValidate};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+#[derive(Subcommand)]
+pub enum SchemaCommand {
+    /// Print the rows that match a JSON Schema, exiting with an error if any rows do not.
+    Validate(ValidateArgs),
+    /// Print a starter JSON Schema using inferred column types.
+    Generate(GenerateArgs),
+}
+
+#[derive(Args)]
+pub struct ValidateArgs {
+    #[command(flatten)]
+    input: Input,
+
+    /// JSON Schema file path.
+    #[arg(long)]
+    schema: PathBuf,
+
+    /// Write the rows that do not match the schema to this file path.
+    #[arg(long)]
+    invalid: Option<PathBuf>,
+
+    /// How to handle malformed records.
+    #[arg(long, value_enum, default_value_t)]
+    error_policy: Policy,
+}
+
+#[derive(Args)]
+pub struct GenerateArgs {
+    #[command(flatten)]
+    input: Input,
+}
+
+pub fn run(command: SchemaCommand) -> Result<ExitCode> {
+    match command {
+        SchemaCommand::Validate(args) => {
+            let (file_path, file_data, encoding) = args.input.source()?;
+            let report = SchemaValidate::new()
+                .file_path_opt(file_path)
+                .file_data_opt(file_data)
+                .encoding(encoding)
+                .schema_path(args.schema)
+                .error_policy(args.error_policy.into())
+                .execute_with_report()?;
+            print_skipped(&report.skipped);
+            for err in &report.output.errors {
+                match &err.column {
+                    Some(column) => eprintln!("record {} ({column}): {}", err.record, err.message),
+                    None => eprintln!("record {}: {}", err.record, err.message),
+                }
+            }
+            if let Some(invalid) = args.invalid {
+                std::fs::write(invalid, &report.output.invalid)?;
+            }
+            print!("{}", report.output.valid);
+            Ok(if report.output.errors.is_empty() {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            })
+        }
+        SchemaCommand::Generate(args) => {
+            let (file_path, file_data, encoding) = args.input.source()?;
+            let output = SchemaGenerate::new()
+                .file_path_opt(file_path)
+                .file_data_opt(file_data)
+                .encoding(encoding)
+                .execute()?;
+            println!("{output}");
+            Ok(ExitCode::SUCCESS)
+        }
+    }
+}
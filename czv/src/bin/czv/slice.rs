@@ -0,0 +1,52 @@
+use crate::{print_skipped, Input, Policy};
+use clap::Args;
+use czv::{Result, Slice};
+use std::process::ExitCode;
+
+#[derive(Args)]
+pub struct SliceArgs {
+    #[command(flatten)]
+    input: Input,
+
+    /// The index of the record to slice from (0-indexed). If negative, starts from the last record.
+    #[arg(short, long, allow_negative_numbers = true)]
+    start: Option<i32>,
+
+    /// The index of the record to slice to (exclusive). If negative, starts from the last record.
+    #[arg(short, long, allow_negative_numbers = true, conflicts_with = "length")]
+    end: Option<i32>,
+
+    /// The length of the slice (alternative to `--end`).
+    #[arg(short, long)]
+    length: Option<i32>,
+
+    /// Slice a single record. If negative, starts from the last record.
+    #[arg(short, long, allow_negative_numbers = true, conflicts_with_all = ["start", "end", "length"])]
+    index: Option<i32>,
+
+    /// Omit the header row (first row) from the output.
+    #[arg(long)]
+    no_header_row: bool,
+
+    /// How to handle malformed records.
+    #[arg(long, value_enum, default_value_t)]
+    error_policy: Policy,
+}
+
+pub fn run(args: SliceArgs) -> Result<ExitCode> {
+    let (file_path, file_data, encoding) = args.input.source()?;
+    let report = Slice::new()
+        .file_path_opt(file_path)
+        .file_data_opt(file_data)
+        .encoding(encoding)
+        .start_opt(args.start)
+        .end_opt(args.end)
+        .length_opt(args.length)
+        .index_opt(args.index)
+        .include_header_row(!args.no_header_row)
+        .error_policy(args.error_policy.into())
+        .execute_with_report()?;
+    print_skipped(&report.skipped);
+    print!("{}", report.output);
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,73 @@
+use crate::{print_skipped, Input, Policy};
+use clap::{Args, ValueEnum};
+use czv::infer::ColumnType;
+use czv::sql::SqlDialect;
+use czv::{Result, ToSql};
+use std::io::BufWriter;
+use std::process::ExitCode;
+
+/// The SQL dialect of a generated script.
+#[derive(Clone, Copy, Default, ValueEnum)]
+pub enum Dialect {
+    #[default]
+    Sqlite,
+    Postgres,
+    Mysql,
+}
+
+impl From<Dialect> for SqlDialect {
+    fn from(value: Dialect) -> Self {
+        match value {
+            Dialect::Sqlite => SqlDialect::Sqlite,
+            Dialect::Postgres => SqlDialect::Postgres,
+            Dialect::Mysql => SqlDialect::Mysql,
+        }
+    }
+}
+
+#[derive(Args)]
+pub struct ToSqlArgs {
+    #[command(flatten)]
+    input: Input,
+
+    /// The name of the table. Defaults to the file name without its extension, or `data` for stdin.
+    #[arg(short, long)]
+    table: Option<String>,
+
+    /// The SQL dialect of the script.
+    #[arg(short, long, value_enum, default_value_t)]
+    dialect: Dialect,
+
+    /// Set a column's type instead of inferring it, as `name=type` where type is boolean, integer, float or string.
+    #[arg(long = "type", value_name = "NAME=TYPE", value_parser = crate::parse_column_type)]
+    types: Vec<(String, ColumnType)>,
+
+    /// Only write the `INSERT` statements.
+    #[arg(long)]
+    no_create_table: bool,
+
+    /// The maximum number of rows in an `INSERT` statement.
+    #[arg(long, default_value_t = 500)]
+    batch_size: usize,
+
+    /// How to handle malformed records.
+    #[arg(long, value_enum, default_value_t)]
+    error_policy: Policy,
+}
+
+pub fn run(args: ToSqlArgs) -> Result<ExitCode> {
+    let (file_path, file_data, encoding) = args.input.source()?;
+    let report = ToSql::new()
+        .file_path_opt(file_path)
+        .file_data_opt(file_data)
+        .encoding(encoding)
+        .table_name_opt(args.table)
+        .dialect(args.dialect.into())
+        .column_types(args.types)
+        .create_table(!args.no_create_table)
+        .batch_size(args.batch_size)
+        .error_policy(args.error_policy.into())
+        .execute_to_writer(BufWriter::new(std::io::stdout()))?;
+    print_skipped(&report.skipped);
+    Ok(ExitCode::SUCCESS)
+}
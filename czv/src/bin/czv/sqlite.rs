@@ -0,0 +1,82 @@
+use crate::{print_skipped, Input, Policy};
+use clap::{Args, ValueEnum};
+use czv::infer::ColumnType;
+use czv::sqlite::IfExists;
+use czv::{Result, ToSqlite};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+/// What to do when the table already exists.
+#[derive(Clone, Copy, Default, ValueEnum)]
+pub enum Existing {
+    /// Exit with an error without changing the database.
+    #[default]
+    Fail,
+    /// Drop the table and create it again.
+    Replace,
+    /// Insert the rows into the existing table.
+    Append,
+}
+
+impl From<Existing> for IfExists {
+    fn from(value: Existing) -> Self {
+        match value {
+            Existing::Fail => IfExists::Fail,
+            Existing::Replace => IfExists::Replace,
+            Existing::Append => IfExists::Append,
+        }
+    }
+}
+
+#[derive(Args)]
+pub struct ToSqliteArgs {
+    #[command(flatten)]
+    input: Input,
+
+    /// SQLite database file path, created if it does not exist.
+    #[arg(short, long, value_name = "DATABASE")]
+    output: PathBuf,
+
+    /// The name of the table. Defaults to the file name without its extension, or `data` for stdin.
+    #[arg(short, long)]
+    table: Option<String>,
+
+    /// Set a column's type instead of inferring it, as `name=type` where type is boolean, integer, float or string.
+    #[arg(long = "type", value_name = "NAME=TYPE", value_parser = crate::parse_column_type)]
+    types: Vec<(String, ColumnType)>,
+
+    /// Create an index on a column, e.g. `--index name` or `--index name,city`.
+    #[arg(long = "index", value_name = "COLUMN", value_delimiter = ',')]
+    indexes: Vec<String>,
+
+    /// What to do when the table already exists.
+    #[arg(long, value_enum, default_value_t)]
+    if_exists: Existing,
+
+    /// The maximum number of rows inserted in a transaction.
+    #[arg(long, default_value_t = 10_000)]
+    batch_size: usize,
+
+    /// How to handle malformed records.
+    #[arg(long, value_enum, default_value_t)]
+    error_policy: Policy,
+}
+
+pub fn run(args: ToSqliteArgs) -> Result<ExitCode> {
+    let (file_path, file_data, encoding) = args.input.source()?;
+    let report = ToSqlite::new()
+        .file_path_opt(file_path)
+        .file_data_opt(file_data)
+        .encoding(encoding)
+        .database_path(args.output)
+        .table_name_opt(args.table)
+        .column_types(args.types)
+        .indexes(args.indexes)
+        .if_exists(args.if_exists.into())
+        .batch_size(args.batch_size)
+        .error_policy(args.error_policy.into())
+        .execute_with_report()?;
+    print_skipped(&report.skipped);
+    println!("{}", report.output);
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,57 @@
+use crate::{print_skipped, Input, Policy};
+use clap::{Args, ValueEnum};
+use czv::table::TableFormat;
+use czv::{Result, Table};
+use std::process::ExitCode;
+
+/// The format of a rendered table.
+#[derive(Clone, Copy, Default, ValueEnum)]
+pub enum Format {
+    #[default]
+    Text,
+    Markdown,
+    Html,
+}
+
+impl From<Format> for TableFormat {
+    fn from(value: Format) -> Self {
+        match value {
+            Format::Text => TableFormat::Text,
+            Format::Markdown => TableFormat::Markdown,
+            Format::Html => TableFormat::Html,
+        }
+    }
+}
+
+#[derive(Args)]
+pub struct TableArgs {
+    #[command(flatten)]
+    input: Input,
+
+    /// The format of the table.
+    #[arg(short, long, value_enum, default_value_t)]
+    format: Format,
+
+    /// The maximum width of a column in characters. Longer values are cut and end with `…`.
+    #[arg(short, long)]
+    max_width: Option<usize>,
+
+    /// How to handle malformed records.
+    #[arg(long, value_enum, default_value_t)]
+    error_policy: Policy,
+}
+
+pub fn run(args: TableArgs) -> Result<ExitCode> {
+    let (file_path, file_data, encoding) = args.input.source()?;
+    let report = Table::new()
+        .file_path_opt(file_path)
+        .file_data_opt(file_data)
+        .encoding(encoding)
+        .format(args.format.into())
+        .max_width_opt(args.max_width)
+        .error_policy(args.error_policy.into())
+        .execute_with_report()?;
+    print_skipped(&report.skipped);
+    print!("{}", report.output);
+    Ok(ExitCode::SUCCESS)
+}
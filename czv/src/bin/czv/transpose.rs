@@ -0,0 +1,32 @@
+use crate::{print_skipped, Input, Policy};
+use clap::Args;
+use czv::{Result, Transpose};
+use std::io::BufWriter;
+use std::process::ExitCode;
+
+#[derive(Args)]
+pub struct TransposeArgs {
+    #[command(flatten)]
+    input: Input,
+
+    /// Read the input once per output row instead of loading every record into memory.
+    #[arg(long)]
+    multipass: bool,
+
+    /// How to handle malformed records.
+    #[arg(long, value_enum, default_value_t)]
+    error_policy: Policy,
+}
+
+pub fn run(args: TransposeArgs) -> Result<ExitCode> {
+    let (file_path, file_data, encoding) = args.input.source()?;
+    let report = Transpose::new()
+        .file_path_opt(file_path)
+        .file_data_opt(file_data)
+        .encoding(encoding)
+        .multipass(args.multipass)
+        .error_policy(args.error_policy.into())
+        .execute_to_writer(BufWriter::new(std::io::stdout().lock()))?;
+    print_skipped(&report.skipped);
+    Ok(ExitCode::SUCCESS)
+}
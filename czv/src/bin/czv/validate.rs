@@ -0,0 +1,27 @@
+use crate::Input;
+use clap::Args;
+use czv::{Result, Validate};
+use std::process::ExitCode;
+
+#[derive(Args)]
+pub struct ValidateArgs {
+    #[command(flatten)]
+    input: Input,
+}
+
+pub fn run(args: ValidateArgs) -> Result<ExitCode> {
+    let (file_path, file_data, encoding) = args.input.source()?;
+    let problems = Validate::new()
+        .file_path_opt(file_path)
+        .file_data_opt(file_data)
+        .encoding(encoding)
+        .execute()?;
+    for problem in &problems {
+        println!("{problem}");
+    }
+    Ok(if problems.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
@@ -1,4 +1,7 @@
-use crate::{bail, Result};
+use crate::encoding::Encoding;
+use crate::policy::{ErrorPolicy, Report, Skipper};
+use crate::util::reader_from;
+use crate::Result;
 use csv::ReaderBuilder;
 use std::path::PathBuf;
 use typed_builder::TypedBuilder;
@@ -10,6 +13,7 @@ use typed_builder::TypedBuilder;
 /// ## Notes
 ///
 /// - Specify whether to include the header row in the count with `include_header_row`.
+/// - Malformed records are not counted when skipped with `error_policy`.
 /// - You may not use `file_path` and `file_data` together, only one may be specified.
 ///
 /// ## Arguments
@@ -17,27 +21,44 @@ use typed_builder::TypedBuilder;
 /// * `file_path` - CSV file path (alternative to `file_data`).
 /// * `file_data` - CSV file data (alternative to `file_path`).
 /// * `include_header_row` - Specify whether to include the header row (first row) in the row count.
+/// * `error_policy` - How to handle malformed records.
+/// * `encoding` - The character encoding of `file_path`.
 pub fn row_count(
     file_path: Option<PathBuf>,
     file_data: Option<String>,
     include_header_row: bool,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
 ) -> Result<usize> {
+    Ok(row_count_with_report(
+        file_path,
+        file_data,
+        include_header_row,
+        error_policy,
+        encoding,
+    )?
+    .output)
+}
+
+fn row_count_with_report(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    include_header_row: bool,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<Report<usize>> {
     let mut rdr = ReaderBuilder::new();
+    rdr.has_headers(!include_header_row);
+    let mut rdr = reader_from(&rdr, file_path.as_deref(), file_data.as_deref(), encoding)?;
 
-    // file_path
-    if let Some(file_path) = file_path {
-        rdr.has_headers(!include_header_row);
-        return Ok(rdr.from_path(file_path)?.records().count());
-    }
-    // file_data
-    else if let Some(file_data) = file_data {
-        rdr.has_headers(!include_header_row);
-        return Ok(rdr.from_reader(file_data.as_bytes()).records().count());
-    }
-    // file_path and file_data were not provided
-    else {
-        bail!("Could not determine a file path or file data for row_count_builder.");
+    let mut skipper = Skipper::new(error_policy);
+    let mut count = 0;
+    for record in rdr.byte_records() {
+        if skipper.check(record)?.is_some() {
+            count += 1;
+        }
     }
+    Ok(skipper.finish(count))
 }
 
 #[derive(TypedBuilder)]
@@ -70,7 +91,7 @@ pub struct RowCount {
         default,
         setter(
             doc = "CSV file path (alternative to `file_data`).",
-            strip_option,
+            strip_option(fallback = file_path_opt),
             into
         )
     )]
@@ -80,7 +101,7 @@ pub struct RowCount {
         default,
         setter(
             doc = "CSV file data (alternative to `file_path`).",
-            strip_option,
+            strip_option(fallback = file_data_opt),
             into
         )
     )]
@@ -93,22 +114,50 @@ pub struct RowCount {
         )
     )]
     include_header_row: bool,
+
+    #[builder(
+        default,
+        setter(doc = "How to handle malformed records. Defaults to `ErrorPolicy::Fail`.")
+    )]
+    error_policy: ErrorPolicy,
+
+    #[builder(
+        default,
+        setter(doc = "The character encoding of `file_path`. Defaults to `Encoding::Utf8`.")
+    )]
+    encoding: Encoding,
 }
 
 #[allow(non_camel_case_types)]
 impl<
+        __encoding: typed_builder::Optional<Encoding>,
+        __error_policy: typed_builder::Optional<ErrorPolicy>,
         __include_header_row: typed_builder::Optional<bool>,
         __file_data: typed_builder::Optional<Option<String>>,
         __file_path: typed_builder::Optional<Option<PathBuf>>,
-    > RowCountBuilder<(__file_path, __file_data, __include_header_row)>
+    >
+    RowCountBuilder<(
+        __file_path,
+        __file_data,
+        __include_header_row,
+        __error_policy,
+        __encoding,
+    )>
 {
     /// Returns the row count.
     pub fn execute(self) -> Result<usize> {
+        Ok(self.execute_with_report()?.output)
+    }
+
+    /// Returns the row count along with the records skipped with `ErrorPolicy::Collect`.
+    pub fn execute_with_report(self) -> Result<Report<usize>> {
         let builder = self.__build();
-        row_count(
+        row_count_with_report(
             builder.file_path,
             builder.file_data,
             builder.include_header_row,
+            builder.error_policy,
+            builder.encoding,
         )
     }
 }
@@ -141,21 +190,15 @@ impl<
 ///
 /// * `file_path` - CSV file path (alternative to `file_data`).
 /// * `file_data` - CSV file data (alternative to `file_path`).
-pub fn column_count(file_path: Option<PathBuf>, file_data: Option<String>) -> Result<usize> {
+/// * `encoding` - The character encoding of `file_path`.
+pub fn column_count(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    encoding: Encoding,
+) -> Result<usize> {
     let rdr = ReaderBuilder::new();
-
-    // file_path
-    if let Some(file_path) = file_path {
-        Ok(rdr.from_path(file_path)?.headers()?.len())
-    }
-    // file_data
-    else if let Some(file_data) = file_data {
-        return Ok(rdr.from_reader(file_data.as_bytes()).headers()?.len());
-    }
-    // file_path and file_data were not provided
-    else {
-        bail!("Could not determine a file path or file data for column_count_builder.");
-    }
+    let mut rdr = reader_from(&rdr, file_path.as_deref(), file_data.as_deref(), encoding)?;
+    Ok(rdr.headers()?.len())
 }
 
 #[derive(TypedBuilder)]
@@ -184,7 +227,7 @@ pub struct ColumnCount {
         default,
         setter(
             doc = "CSV file path (alternative to `file_data`).",
-            strip_option,
+            strip_option(fallback = file_path_opt),
             into
         )
     )]
@@ -194,22 +237,29 @@ pub struct ColumnCount {
         default,
         setter(
             doc = "CSV file path (alternative to `file_path`).",
-            strip_option,
+            strip_option(fallback = file_data_opt),
             into
         )
     )]
     file_data: Option<String>,
+
+    #[builder(
+        default,
+        setter(doc = "The character encoding of `file_path`. Defaults to `Encoding::Utf8`.")
+    )]
+    encoding: Encoding,
 }
 
 #[allow(non_camel_case_types)]
 impl<
+        __encoding: typed_builder::Optional<Encoding>,
         __file_data: typed_builder::Optional<Option<String>>,
         __file_path: typed_builder::Optional<Option<PathBuf>>,
-    > ColumnCountBuilder<(__file_path, __file_data)>
+    > ColumnCountBuilder<(__file_path, __file_data, __encoding)>
 {
     /// Returns the column count.
     pub fn execute(self) -> Result<usize> {
         let builder = self.__build();
-        column_count(builder.file_path, builder.file_data)
+        column_count(builder.file_path, builder.file_data, builder.encoding)
     }
 }
@@ -0,0 +1,268 @@
+use crate::{CzvError, Result};
+use encoding_rs::{UTF_16BE, UTF_16LE, UTF_8, WINDOWS_1252};
+use encoding_rs_io::DecodeReaderBytesBuilder;
+use std::fs::File;
+use std::io::{Cursor, Read};
+use std::path::PathBuf;
+use std::str::FromStr;
+use typed_builder::TypedBuilder;
+
+/// The number of bytes read from the start of the data to detect its encoding.
+const SAMPLE_SIZE: u64 = 64 * 1024;
+
+/// The character encoding of CSV data read from a file.
+///
+/// Whatever the encoding, a byte order mark (BOM) at the start of the data takes precedence and is
+/// stripped, so UTF-16 data with a BOM is always read correctly.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Encoding {
+    /// UTF-8, with invalid UTF-8 reported as malformed data.
+    #[default]
+    Utf8,
+    /// Detected from the first 64 KiB: UTF-16 if it looks like UTF-16 text without a BOM, UTF-8 if
+    /// it is valid UTF-8, and Windows-1252 (a superset of Latin-1) otherwise.
+    Auto,
+    /// Any other encoding, usually parsed from a
+    /// [WHATWG label](https://encoding.spec.whatwg.org/#names-and-labels) such as `latin1`,
+    /// `windows-1252`, `utf-16le` or `shift_jis`.
+    Named(&'static encoding_rs::Encoding),
+}
+
+impl Encoding {
+    /// Returns the encoding of data starting with `sample` and the length of its BOM, if any.
+    fn resolve(self, sample: &[u8]) -> (&'static encoding_rs::Encoding, usize) {
+        if let Some(bom) = encoding_rs::Encoding::for_bom(sample) {
+            return bom;
+        }
+        match self {
+            Self::Utf8 => (UTF_8, 0),
+            Self::Auto => (detect(sample), 0),
+            Self::Named(encoding) => (encoding, 0),
+        }
+    }
+}
+
+impl FromStr for Encoding {
+    type Err = CzvError;
+
+    /// Parses `auto`, `utf-8` or any other WHATWG label, e.g. `latin1`.
+    fn from_str(s: &str) -> Result<Self> {
+        if s.eq_ignore_ascii_case("auto") {
+            return Ok(Self::Auto);
+        }
+        match encoding_rs::Encoding::for_label(s.as_bytes()) {
+            Some(encoding) if encoding == UTF_8 => Ok(Self::Utf8),
+            Some(encoding) => Ok(Self::Named(encoding)),
+            None => Err(CzvError::InvalidOptions(format!(
+                "Unknown encoding {s:?}, expected auto or a label such as utf-8, latin1 or utf-16le."
+            ))),
+        }
+    }
+}
+
+/// Returns the likely encoding of data starting with `sample`.
+fn detect(sample: &[u8]) -> &'static encoding_rs::Encoding {
+    if let Some((encoding, _)) = encoding_rs::Encoding::for_bom(sample) {
+        return encoding;
+    }
+    // Mostly ASCII text in UTF-16 has a zero byte in every other position. Characters such as
+    // U+0100 or U+4E00 put a few zero bytes in the other positions too.
+    let pairs = sample.len() / 2;
+    let zeros = |offset: usize| {
+        sample
+            .iter()
+            .skip(offset)
+            .step_by(2)
+            .filter(|byte| **byte == 0)
+            .count()
+    };
+    let (even, odd) = (zeros(0), zeros(1));
+    if pairs > 0 && odd > pairs / 2 && even * 4 < odd {
+        return UTF_16LE;
+    }
+    if pairs > 0 && even > pairs / 2 && odd * 4 < even {
+        return UTF_16BE;
+    }
+    match std::str::from_utf8(sample) {
+        Ok(_) => UTF_8,
+        // The sample may end in the middle of a character.
+        Err(err) if err.error_len().is_none() => UTF_8,
+        Err(_) => WINDOWS_1252,
+    }
+}
+
+/// Returns a reader transcoding `rdr` from `encoding` to UTF-8 and stripping any BOM.
+///
+/// UTF-8 data is read as it is, only skipping its BOM, so invalid UTF-8 is left for the reader of
+/// the data to report.
+pub(crate) fn decode<'a>(
+    mut rdr: Box<dyn Read + 'a>,
+    encoding: Encoding,
+) -> Result<Box<dyn Read + 'a>> {
+    // A BOM is at most 3 bytes, which is all that is needed unless the encoding is detected.
+    let sample_size = match encoding {
+        Encoding::Auto => SAMPLE_SIZE,
+        _ => 3,
+    };
+    let mut sample = vec![];
+    rdr.by_ref().take(sample_size).read_to_end(&mut sample)?;
+    let (encoding, bom_length) = encoding.resolve(&sample);
+    if encoding == UTF_8 {
+        sample.drain(..bom_length);
+        return Ok(Box::new(Cursor::new(sample).chain(rdr)));
+    }
+    Ok(Box::new(
+        DecodeReaderBytesBuilder::new()
+            .encoding(Some(encoding))
+            .bom_override(true)
+            .strip_bom(true)
+            .build(Cursor::new(sample).chain(rdr)),
+    ))
+}
+
+/// Returns a reader over the bytes of either `file_path` or `file_data`, only one may be specified.
+fn bytes_from(file_path: Option<PathBuf>, file_data: Option<Vec<u8>>) -> Result<Box<dyn Read>> {
+    match (file_path, file_data) {
+        (Some(_), Some(_)) => Err(CzvError::InvalidOptions(
+            "Cannot have both file_path and file_data, specify one only.".to_string(),
+        )),
+        (None, None) => Err(CzvError::InvalidOptions(
+            "Must provide either file_path or file_data.".to_string(),
+        )),
+        (Some(file_path), None) => Ok(Box::new(File::open(file_path)?)),
+        (None, Some(file_data)) => Ok(Box::new(Cursor::new(file_data))),
+    }
+}
+
+/// Returns the CSV data transcoded to UTF-8. Recommended alternative: [`czv::encoding::Input`](crate::encoding::Input).
+///
+/// See [`czv::encoding::Input`](crate::encoding::Input) for a builder version (recommended) of this function.
+///
+/// ## Notes
+///
+/// - The data is only transcoded, not parsed, so it does not need to be valid CSV data.
+/// - A BOM at the start of the data is stripped.
+/// - Bytes that are not valid in the encoding are replaced with U+FFFD, except for UTF-8, where
+///   they are reported as malformed data.
+///
+/// ## Arguments
+///
+/// * `file_path` - CSV file path (alternative to `file_data`).
+/// * `file_data` - CSV file data as bytes (alternative to `file_path`).
+/// * `encoding` - The character encoding of the data.
+pub fn input(
+    file_path: Option<PathBuf>,
+    file_data: Option<Vec<u8>>,
+    encoding: Encoding,
+) -> Result<String> {
+    let mut bytes = vec![];
+    decode(bytes_from(file_path, file_data)?, encoding)?.read_to_end(&mut bytes)?;
+    String::from_utf8(bytes).map_err(|err| {
+        let byte = err.utf8_error().valid_up_to();
+        CzvError::Parse {
+            message: format!(
+                "Invalid UTF-8 at byte {byte}, specify the encoding, e.g. latin1, or auto to detect it."
+            ),
+            line: None,
+            byte: Some(byte as u64),
+            record: None,
+        }
+    })
+}
+
+/// Returns the name of the encoding the CSV data is read with, e.g. `windows-1252`.
+///
+/// ## Arguments
+///
+/// * `file_path` - CSV file path (alternative to `file_data`).
+/// * `file_data` - CSV file data as bytes (alternative to `file_path`).
+/// * `encoding` - The character encoding of the data.
+pub fn input_encoding(
+    file_path: Option<PathBuf>,
+    file_data: Option<Vec<u8>>,
+    encoding: Encoding,
+) -> Result<String> {
+    let mut sample = vec![];
+    bytes_from(file_path, file_data)?
+        .take(SAMPLE_SIZE)
+        .read_to_end(&mut sample)?;
+    let (encoding, _) = encoding.resolve(&sample);
+    Ok(encoding.name().to_ascii_lowercase())
+}
+
+#[derive(TypedBuilder)]
+#[builder(doc, builder_method(name=new, doc="Returns an [`InputBuilder`](crate::encoding::InputBuilder) to customize encoding options by running other methods before getting the output with the [`execute`](crate::encoding::InputBuilder::execute) method."), build_method(vis="", name=__build))]
+/// Returns the CSV data transcoded to UTF-8 from a detected or given encoding.
+///
+/// The entry point for setting up a [`czv::encoding::InputBuilder`](crate::encoding::InputBuilder) by running [`Input::new()`](crate::encoding::Input::new).
+///
+/// # Example
+///
+/// ```rust
+/// use czv::{Input, Result};
+///
+/// fn main() -> Result<()> {
+///     // "café" in Latin-1.
+///     let data = b"fruit,price\ncaf\xe9,2.50\n".to_vec();
+///     let output = Input::new().file_data(data.clone()).execute()?;
+///     assert_eq!(output, "fruit,price\ncafé,2.50\n");
+///
+///     let encoding = Input::new().file_data(data).detect()?;
+///     assert_eq!(encoding, "windows-1252");
+///     Ok(())
+/// }
+/// ```
+///
+/// ## Notes
+///
+/// - See [`input`](crate::encoding::input) for how the data is transcoded.
+/// - The other operations reading CSV data from a file also have an `encoding` option, so the data
+///   only needs to be transcoded first to keep a UTF-8 copy.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+pub struct Input {
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file path (alternative to `file_data`).",
+            strip_option(fallback = file_path_opt),
+            into
+        )
+    )]
+    file_path: Option<PathBuf>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file data as bytes (alternative to `file_path`).",
+            strip_option(fallback = file_data_opt),
+            into
+        )
+    )]
+    file_data: Option<Vec<u8>>,
+
+    #[builder(
+        default = Encoding::Auto,
+        setter(doc = "The character encoding of the data. Defaults to `Encoding::Auto`.")
+    )]
+    encoding: Encoding,
+}
+
+#[allow(non_camel_case_types)]
+impl<
+        __encoding: typed_builder::Optional<Encoding>,
+        __file_data: typed_builder::Optional<Option<Vec<u8>>>,
+        __file_path: typed_builder::Optional<Option<PathBuf>>,
+    > InputBuilder<(__file_path, __file_data, __encoding)>
+{
+    /// Returns the CSV data transcoded to UTF-8.
+    pub fn execute(self) -> Result<String> {
+        let builder = self.__build();
+        input(builder.file_path, builder.file_data, builder.encoding)
+    }
+
+    /// Returns the name of the encoding the CSV data is read with, e.g. `windows-1252`.
+    pub fn detect(self) -> Result<String> {
+        let builder = self.__build();
+        input_encoding(builder.file_path, builder.file_data, builder.encoding)
+    }
+}
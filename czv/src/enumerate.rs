@@ -0,0 +1,281 @@
+use crate::apply::HashAlgorithm;
+use crate::encoding::Encoding;
+use crate::policy::{ErrorPolicy, Report, Skipper};
+use crate::util::{column_index, into_string, reader_from, writer};
+use crate::{CzvError, Result};
+use csv::{ReaderBuilder, StringRecord};
+use std::path::PathBuf;
+use typed_builder::TypedBuilder;
+use uuid::Uuid;
+
+/// The value written to the added column of each row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnumerateValue {
+    /// The index of the row, `start` for the first row and increasing by `step` for each row after it.
+    Index {
+        /// The index of the first row.
+        start: i64,
+        /// The difference between the indexes of consecutive rows, which may be negative.
+        step: i64,
+    },
+    /// A random (version 4) UUID, e.g. `67e55044-10b1-426f-9247-bb680e5fe0c8`.
+    Uuid,
+    /// The same value for every row.
+    Constant(String),
+    /// The value of the column with this header name.
+    Copy(String),
+    /// The digest of the row's fields joined with the ASCII unit separator (`\x1f`), the same for
+    /// rows with the same values.
+    Hash(HashAlgorithm),
+    /// The file name of `file_path`, or an empty value for `file_data`.
+    FileName,
+}
+
+impl Default for EnumerateValue {
+    /// Returns [`EnumerateValue::Index`] starting at 0 with a step of 1.
+    fn default() -> Self {
+        Self::Index { start: 0, step: 1 }
+    }
+}
+
+impl EnumerateValue {
+    /// Returns the header name used when no `new_column` is given.
+    fn default_header(&self) -> String {
+        match self {
+            Self::Index { .. } => "index".to_string(),
+            Self::Uuid => "uuid".to_string(),
+            Self::Constant(_) => "constant".to_string(),
+            Self::Copy(column) => format!("{column}_copy"),
+            Self::Hash(_) => "hash".to_string(),
+            Self::FileName => "file_name".to_string(),
+        }
+    }
+}
+
+/// Returns the CSV data with a column added to each row. Recommended alternative: [`czv::enumerate::Enumerate`](crate::enumerate::Enumerate).
+///
+/// See [`czv::enumerate::Enumerate`](crate::enumerate::Enumerate) for a builder version (recommended) of this function.
+///
+/// ## Notes
+///
+/// - The column is added after the existing columns.
+/// - Without `new_column`, the column is named `index`, `uuid`, `constant`, `{column}_copy`, `hash`
+///   or `file_name` depending on `value`.
+/// - Indexes count the rows written, so records skipped with `error_policy` do not use an index.
+/// - Records are read one at a time.
+///
+/// ## Arguments
+///
+/// * `file_path` - CSV file path (alternative to `file_data`).
+/// * `file_data` - CSV file data (alternative to `file_path`).
+/// * `value` - The value written to the added column of each row.
+/// * `new_column` - The header name of the added column.
+/// * `error_policy` - How to handle malformed records.
+/// * `encoding` - The character encoding of `file_path`.
+pub fn enumerate(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    value: EnumerateValue,
+    new_column: Option<String>,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<String> {
+    Ok(enumerate_with_report(
+        file_path,
+        file_data,
+        value,
+        new_column,
+        error_policy,
+        encoding,
+    )?
+    .output)
+}
+
+fn enumerate_with_report(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    value: EnumerateValue,
+    new_column: Option<String>,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<Report<String>> {
+    let file_name = file_path
+        .as_deref()
+        .and_then(|path| path.file_name())
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let rdr = ReaderBuilder::new();
+    let mut rdr = reader_from(&rdr, file_path.as_deref(), file_data.as_deref(), encoding)?;
+    let headers = rdr.headers()?.clone();
+    let copied = match &value {
+        EnumerateValue::Copy(column) => Some(column_index(&headers, column)?),
+        _ => None,
+    };
+    let new_column = new_column.unwrap_or_else(|| value.default_header());
+    if headers.iter().any(|header| header == new_column) {
+        return Err(CzvError::InvalidOptions(format!(
+            "New column {new_column:?} already exists."
+        )));
+    }
+
+    let mut wtr = writer();
+    wtr.write_record(headers.iter().chain([new_column.as_str()]))?;
+    let mut skipper = Skipper::new(error_policy);
+    let mut record = StringRecord::new();
+    let mut index = match value {
+        EnumerateValue::Index { start, .. } => Some(start),
+        _ => None,
+    };
+    let mut fields = vec![];
+    while skipper.read_record(&mut rdr, &mut record)? {
+        let added = match &value {
+            EnumerateValue::Index { step, .. } => {
+                let current = index.ok_or_else(|| {
+                    CzvError::InvalidOptions("The index is out of the range of i64.".to_string())
+                })?;
+                index = current.checked_add(*step);
+                current.to_string()
+            }
+            EnumerateValue::Uuid => Uuid::new_v4().to_string(),
+            EnumerateValue::Constant(constant) => constant.clone(),
+            EnumerateValue::Copy(_) => copied.map(|i| record[i].to_string()).unwrap_or_default(),
+            EnumerateValue::Hash(algorithm) => {
+                fields.clear();
+                for (i, field) in record.iter().enumerate() {
+                    if i > 0 {
+                        fields.push(0x1f);
+                    }
+                    fields.extend_from_slice(field.as_bytes());
+                }
+                algorithm.digest(&fields)
+            }
+            EnumerateValue::FileName => file_name.clone(),
+        };
+        wtr.write_record(record.iter().chain([added.as_str()]))?;
+    }
+    Ok(skipper.finish(into_string(wtr)?))
+}
+
+#[derive(TypedBuilder)]
+#[builder(doc, builder_method(name=new, doc="Returns an [`EnumerateBuilder`](crate::enumerate::EnumerateBuilder) to customize enumerate options by running other methods before getting the output with the [`execute`](crate::enumerate::EnumerateBuilder::execute) method."), build_method(vis="", name=__build))]
+/// Returns the CSV data with a column of row indexes, UUIDs, constants, copies, row hashes or file names added.
+///
+/// The entry point for setting up a [`czv::enumerate::EnumerateBuilder`](crate::enumerate::EnumerateBuilder) by running [`Enumerate::new()`](crate::enumerate::Enumerate::new).
+///
+/// # Example
+///
+/// ```rust
+/// use czv::enumerate::EnumerateValue;
+/// use czv::{Enumerate, Result};
+///
+/// fn main() -> Result<()> {
+///     let data = "\
+/// fruit,price
+/// apple,2.50
+/// banana,3.00
+/// ";
+///     let output = Enumerate::new().file_data(data).execute()?;
+///     assert_eq!(output, "fruit,price,index\napple,2.50,0\nbanana,3.00,1\n");
+///
+///     let output = Enumerate::new()
+///         .file_data(data)
+///         .value(EnumerateValue::Index { start: 10, step: 10 })
+///         .new_column("id")
+///         .execute()?;
+///     assert_eq!(output, "fruit,price,id\napple,2.50,10\nbanana,3.00,20\n");
+///     Ok(())
+/// }
+/// ```
+///
+/// ## Notes
+///
+/// - See [`enumerate`](crate::enumerate::enumerate) for where the column is added and how it is named.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+pub struct Enumerate {
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file path (alternative to `file_data`).",
+            strip_option(fallback = file_path_opt),
+            into
+        )
+    )]
+    file_path: Option<PathBuf>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file data (alternative to `file_path`).",
+            strip_option(fallback = file_data_opt),
+            into
+        )
+    )]
+    file_data: Option<String>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "The value written to the added column of each row. Defaults to indexes starting at 0."
+        )
+    )]
+    value: EnumerateValue,
+
+    #[builder(
+        default,
+        setter(
+            doc = "The header name of the added column. Defaults to a name based on `value`, e.g. `index`.",
+            strip_option(fallback = new_column_opt),
+            into
+        )
+    )]
+    new_column: Option<String>,
+
+    #[builder(
+        default,
+        setter(doc = "How to handle malformed records. Defaults to `ErrorPolicy::Fail`.")
+    )]
+    error_policy: ErrorPolicy,
+
+    #[builder(
+        default,
+        setter(doc = "The character encoding of `file_path`. Defaults to `Encoding::Utf8`.")
+    )]
+    encoding: Encoding,
+}
+
+#[allow(non_camel_case_types)]
+impl<
+        __encoding: typed_builder::Optional<Encoding>,
+        __error_policy: typed_builder::Optional<ErrorPolicy>,
+        __new_column: typed_builder::Optional<Option<String>>,
+        __value: typed_builder::Optional<EnumerateValue>,
+        __file_data: typed_builder::Optional<Option<String>>,
+        __file_path: typed_builder::Optional<Option<PathBuf>>,
+    >
+    EnumerateBuilder<(
+        __file_path,
+        __file_data,
+        __value,
+        __new_column,
+        __error_policy,
+        __encoding,
+    )>
+{
+    /// Returns the CSV data with the added column.
+    pub fn execute(self) -> Result<String> {
+        Ok(self.execute_with_report()?.output)
+    }
+
+    /// Returns the CSV data with the added column along with the records skipped with `ErrorPolicy::Collect`.
+    pub fn execute_with_report(self) -> Result<Report<String>> {
+        let builder = self.__build();
+        enumerate_with_report(
+            builder.file_path,
+            builder.file_data,
+            builder.value,
+            builder.new_column,
+            builder.error_policy,
+            builder.encoding,
+        )
+    }
+}
@@ -0,0 +1,460 @@
+use crate::util::{into_string, writer};
+use crate::{CzvError, Result};
+use calamine::{open_workbook_auto_from_rs, Data, Range, Reader, Sheets};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use std::fmt::Write;
+use std::io::Cursor;
+use std::path::PathBuf;
+use typed_builder::TypedBuilder;
+
+/// The date format used for date cells without a time of day.
+const DATE_FORMAT: &str = "%Y-%m-%d";
+/// The date format used for date cells with a time of day.
+const DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+type Workbook = Sheets<Cursor<Vec<u8>>>;
+
+/// A 0-indexed `(row, column)` cell position.
+type Cell = (u32, u32);
+
+fn open_workbook(file_path: Option<PathBuf>, file_data: Option<Vec<u8>>) -> Result<Workbook> {
+    let data = match (file_path, file_data) {
+        (Some(_), Some(_)) => {
+            return Err(CzvError::InvalidOptions(
+                "Cannot have both file_path and file_data, specify one only.".to_string(),
+            ))
+        }
+        (None, None) => {
+            return Err(CzvError::InvalidOptions(
+                "Must provide either file_path or file_data.".to_string(),
+            ))
+        }
+        (Some(file_path), None) => std::fs::read(file_path)?,
+        (None, Some(file_data)) => file_data,
+    };
+    Ok(open_workbook_auto_from_rs(Cursor::new(data))?)
+}
+
+/// Returns the used range of the sheet selected by name or index, defaulting to the first sheet.
+fn sheet_range(
+    workbook: &mut Workbook,
+    sheet_name: Option<&str>,
+    sheet_index: Option<usize>,
+) -> Result<Range<Data>> {
+    let index = match (sheet_name, sheet_index) {
+        (Some(_), Some(_)) => {
+            return Err(CzvError::InvalidOptions(
+                "Cannot have both sheet_name and sheet_index, specify one only.".to_string(),
+            ))
+        }
+        (Some(sheet_name), None) => workbook
+            .sheet_names()
+            .iter()
+            .position(|name| name == sheet_name)
+            .ok_or_else(|| CzvError::InvalidOptions(format!("Sheet not found: {sheet_name}")))?,
+        (None, sheet_index) => sheet_index.unwrap_or(0),
+    };
+    let sheet_count = workbook.sheet_names().len();
+    match workbook.worksheet_range_at(index) {
+        Some(range) => Ok(range?),
+        None => Err(CzvError::InvalidOptions(format!(
+            "Sheet index {index} is out of bounds, the workbook has {sheet_count} sheet(s)."
+        ))),
+    }
+}
+
+/// Parses a cell reference such as `B3` into its position.
+fn parse_cell(cell: &str) -> Option<Cell> {
+    let split = cell.find(|c: char| c.is_ascii_digit())?;
+    let (letters, digits) = cell.split_at(split);
+    if letters.is_empty() || !letters.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let column = letters.chars().try_fold(0u32, |column, c| {
+        column
+            .checked_mul(26)?
+            .checked_add(c.to_ascii_uppercase() as u32 - 'A' as u32 + 1)
+    })?;
+    let row = digits.parse::<u32>().ok()?;
+    Some((row.checked_sub(1)?, column - 1))
+}
+
+/// Parses a range such as `A1:C10`, or a start cell such as `B3` that extends to the end of the
+/// used range, into its start and end cells.
+fn parse_range(range: &str) -> Result<(Cell, Option<Cell>)> {
+    let invalid = || {
+        CzvError::InvalidOptions(format!(
+            "Invalid range {range:?}, expected a range such as \"A1:C10\" or a start cell such as \"B3\"."
+        ))
+    };
+    let (start, end) = match range.split_once(':') {
+        Some((start, "")) => (start, None),
+        Some((start, end)) => (start, Some(end)),
+        None => (range, None),
+    };
+    let start = parse_cell(start.trim()).ok_or_else(invalid)?;
+    let end = end
+        .map(|end| parse_cell(end.trim()).ok_or_else(invalid))
+        .transpose()?;
+    if end.is_some_and(|end| end.0 < start.0 || end.1 < start.1) {
+        return Err(invalid());
+    }
+    Ok((start, end))
+}
+
+/// Returns `datetime` formatted with `date_format`, or as a date with an optional time of day.
+fn format_datetime(datetime: NaiveDateTime, date_format: Option<&str>) -> Result<String> {
+    let date_format = date_format.unwrap_or(if datetime.time() == NaiveTime::MIN {
+        DATE_FORMAT
+    } else {
+        DATETIME_FORMAT
+    });
+    let mut output = String::new();
+    write!(output, "{}", datetime.format(date_format)).map_err(|_| {
+        CzvError::InvalidOptions(format!("Cannot format dates with {date_format:?}."))
+    })?;
+    Ok(output)
+}
+
+/// Returns the text written for `cell`, with durations as `H:MM:SS` and dates and times formatted
+/// with `date_format`.
+fn format_cell(cell: &Data, date_format: Option<&str>) -> Result<String> {
+    match cell {
+        Data::DateTime(datetime) if datetime.is_duration() => match datetime.as_duration() {
+            Some(duration) => {
+                let seconds = duration.num_seconds();
+                let sign = if seconds < 0 { "-" } else { "" };
+                let seconds = seconds.abs();
+                Ok(format!(
+                    "{sign}{}:{:02}:{:02}",
+                    seconds / 3600,
+                    seconds % 3600 / 60,
+                    seconds % 60
+                ))
+            }
+            None => Ok(cell.to_string()),
+        },
+        Data::DateTime(datetime) => match datetime.as_datetime() {
+            Some(datetime) => format_datetime(datetime, date_format),
+            None => Ok(cell.to_string()),
+        },
+        Data::DateTimeIso(value) => {
+            let datetime =
+                NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f").or_else(|_| {
+                    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                        .map(|date| date.and_time(NaiveTime::MIN))
+                });
+            match datetime {
+                Ok(datetime) => format_datetime(datetime, date_format),
+                Err(_) => Ok(value.clone()),
+            }
+        }
+        _ => Ok(cell.to_string()),
+    }
+}
+
+/// Returns the sheet data converted to CSV. Recommended alternative: [`czv::excel::FromExcel`](crate::excel::FromExcel).
+///
+/// See [`czv::excel::FromExcel`](crate::excel::FromExcel) for a builder version (recommended) of this function.
+///
+/// ## Notes
+///
+/// - Reads `.xlsx`, `.xlsm`, `.xlsb`, `.xls` and `.ods` workbooks, detected from the data.
+/// - The first sheet is read unless `sheet_name` or `sheet_index` selects another one. You may not
+///   use `sheet_name` and `sheet_index` together.
+/// - The used range of the sheet is read unless `range` selects cells in A1 notation, e.g. `A1:C10`,
+///   or a start cell such as `B3` to read from there to the end of the used range. The first row read
+///   is written as the header row.
+/// - Rows and columns of `range` after the end of the used range are not written, and a `range`
+///   that does not overlap the used range returns empty CSV data. Empty rows and columns before the
+///   used range are written.
+/// - Date cells are written with `date_format` (a [`chrono` format string](https://docs.rs/chrono/latest/chrono/format/strftime/index.html))
+///   if provided, otherwise as `2024-01-31`, or `2024-01-31 18:30:00` when they have a time of day.
+///   Duration cells are written as `hours:minutes:seconds`.
+/// - Error cells are written as Excel displays them, e.g. `#DIV/0!`.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+///
+/// ## Arguments
+///
+/// * `file_path` - Workbook file path (alternative to `file_data`).
+/// * `file_data` - Workbook file data (alternative to `file_path`).
+/// * `sheet_name` - The name of the sheet to read (alternative to `sheet_index`).
+/// * `sheet_index` - The 0-indexed position of the sheet to read (alternative to `sheet_name`).
+/// * `range` - The cells to read in A1 notation.
+/// * `date_format` - The format for date cells.
+pub fn from_excel(
+    file_path: Option<PathBuf>,
+    file_data: Option<Vec<u8>>,
+    sheet_name: Option<String>,
+    sheet_index: Option<usize>,
+    range: Option<String>,
+    date_format: Option<String>,
+) -> Result<String> {
+    if let Some(date_format) = &date_format {
+        if StrftimeItems::new(date_format).any(|item| matches!(item, Item::Error)) {
+            return Err(CzvError::InvalidOptions(format!(
+                "Invalid date_format {date_format:?}."
+            )));
+        }
+    }
+    let selection = range.as_deref().map(parse_range).transpose()?;
+
+    let mut workbook = open_workbook(file_path, file_data)?;
+    let mut cells = sheet_range(&mut workbook, sheet_name.as_deref(), sheet_index)?;
+    if let (Some((start, end)), Some(used_start), Some(used_end)) =
+        (selection, cells.start(), cells.end())
+    {
+        // Cells past the used range are empty, so the end is clamped to avoid allocating them.
+        let end = end.map_or(used_end, |end| {
+            (end.0.min(used_end.0), end.1.min(used_end.1))
+        });
+        let overlaps =
+            start.0 <= end.0 && start.1 <= end.1 && end.0 >= used_start.0 && end.1 >= used_start.1;
+        if !overlaps {
+            return Ok(String::new());
+        }
+        cells = cells.range(start, end);
+    }
+
+    let mut wtr = writer();
+    for row in cells.rows() {
+        let record = row
+            .iter()
+            .map(|cell| format_cell(cell, date_format.as_deref()))
+            .collect::<Result<Vec<_>>>()?;
+        wtr.write_record(record)?;
+    }
+    into_string(wtr)
+}
+
+#[derive(TypedBuilder)]
+#[builder(doc, builder_method(name=new, doc="Returns a [`FromExcelBuilder`](crate::excel::FromExcelBuilder) to customize sheet conversion options by running other methods before getting the CSV with the [`execute`](crate::excel::FromExcelBuilder::execute) method."), build_method(vis="", name=__build))]
+/// Returns the sheet data converted to CSV.
+///
+/// The entry point for setting up a [`czv::excel::FromExcelBuilder`](crate::excel::FromExcelBuilder) by running [`FromExcel::new()`](crate::excel::FromExcel::new).
+///
+/// # Example
+///
+/// ```rust
+/// use czv::{FromExcel, Result};
+///
+/// fn main() -> Result<()> {
+///     let output = FromExcel::new()
+///         .file_path("tests/resources/fruits.xlsx")
+///         .sheet_name("fruits")
+///         .range("A1:B3")
+///         .execute()?;
+///     assert_eq!(output, "fruit,price\napple,2.5\nbanana,3\n");
+///     Ok(())
+/// }
+/// ```
+///
+/// ## Notes
+///
+/// - See [`from_excel`](crate::excel::from_excel) for how sheets, ranges and cells are read.
+/// - List the sheets of a workbook with [`ExcelSheets`](crate::excel::ExcelSheets).
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+pub struct FromExcel {
+    #[builder(
+        default,
+        setter(
+            doc = "Workbook file path (alternative to `file_data`).",
+            strip_option(fallback = file_path_opt),
+            into
+        )
+    )]
+    file_path: Option<PathBuf>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "Workbook file data (alternative to `file_path`).",
+            strip_option(fallback = file_data_opt),
+            into
+        )
+    )]
+    file_data: Option<Vec<u8>>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "The name of the sheet to read (alternative to `sheet_index`). Defaults to the first sheet.",
+            strip_option(fallback = sheet_name_opt),
+            into
+        )
+    )]
+    sheet_name: Option<String>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "The 0-indexed position of the sheet to read (alternative to `sheet_name`). Defaults to the first sheet.",
+            strip_option(fallback = sheet_index_opt)
+        )
+    )]
+    sheet_index: Option<usize>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "The cells to read in A1 notation, e.g. `A1:C10`, or a start cell such as `B3`. Defaults to the used range of the sheet.",
+            strip_option(fallback = range_opt),
+            into
+        )
+    )]
+    range: Option<String>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "The `chrono` format string for date cells. Defaults to `%Y-%m-%d`, or `%Y-%m-%d %H:%M:%S` for dates with a time of day.",
+            strip_option(fallback = date_format_opt),
+            into
+        )
+    )]
+    date_format: Option<String>,
+}
+
+#[allow(non_camel_case_types)]
+impl<
+        __date_format: typed_builder::Optional<Option<String>>,
+        __range: typed_builder::Optional<Option<String>>,
+        __sheet_index: typed_builder::Optional<Option<usize>>,
+        __sheet_name: typed_builder::Optional<Option<String>>,
+        __file_data: typed_builder::Optional<Option<Vec<u8>>>,
+        __file_path: typed_builder::Optional<Option<PathBuf>>,
+    >
+    FromExcelBuilder<(
+        __file_path,
+        __file_data,
+        __sheet_name,
+        __sheet_index,
+        __range,
+        __date_format,
+    )>
+{
+    /// Returns the CSV output.
+    pub fn execute(self) -> Result<String> {
+        let builder = self.__build();
+        from_excel(
+            builder.file_path,
+            builder.file_data,
+            builder.sheet_name,
+            builder.sheet_index,
+            builder.range,
+            builder.date_format,
+        )
+    }
+}
+
+/// The name and size of a sheet in a workbook.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SheetInfo {
+    /// The sheet name.
+    pub name: String,
+    /// The number of rows in the used range of the sheet, including the header row.
+    pub rows: usize,
+    /// The number of columns in the used range of the sheet.
+    pub columns: usize,
+}
+
+/// Returns the name and size of each sheet in the workbook. Recommended alternative: [`czv::excel::ExcelSheets`](crate::excel::ExcelSheets).
+///
+/// See [`czv::excel::ExcelSheets`](crate::excel::ExcelSheets) for a builder version (recommended) of this function.
+///
+/// ## Notes
+///
+/// - Sheets are listed in workbook order, so a sheet's position is the `sheet_index` to pass to
+///   [`FromExcel`](crate::excel::FromExcel).
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+///
+/// ## Arguments
+///
+/// * `file_path` - Workbook file path (alternative to `file_data`).
+/// * `file_data` - Workbook file data (alternative to `file_path`).
+pub fn excel_sheets(
+    file_path: Option<PathBuf>,
+    file_data: Option<Vec<u8>>,
+) -> Result<Vec<SheetInfo>> {
+    let mut workbook = open_workbook(file_path, file_data)?;
+    let names = workbook.sheet_names();
+    names
+        .into_iter()
+        .enumerate()
+        .map(|(index, name)| {
+            let (rows, columns) = sheet_range(&mut workbook, None, Some(index))?.get_size();
+            Ok(SheetInfo {
+                name,
+                rows,
+                columns,
+            })
+        })
+        .collect()
+}
+
+#[derive(TypedBuilder)]
+#[builder(doc, builder_method(name=new, doc="Returns an [`ExcelSheetsBuilder`](crate::excel::ExcelSheetsBuilder) to customize options by running other methods before getting the sheets with the [`execute`](crate::excel::ExcelSheetsBuilder::execute) method."), build_method(vis="", name=__build))]
+/// Returns the name and size of each sheet in the workbook.
+///
+/// The entry point for setting up a [`czv::excel::ExcelSheetsBuilder`](crate::excel::ExcelSheetsBuilder) by running [`ExcelSheets::new()`](crate::excel::ExcelSheets::new).
+///
+/// # Example
+///
+/// ```rust
+/// use czv::excel::SheetInfo;
+/// use czv::{ExcelSheets, Result};
+///
+/// fn main() -> Result<()> {
+///     let sheets = ExcelSheets::new()
+///         .file_path("tests/resources/fruits.xlsx")
+///         .execute()?;
+///     assert_eq!(
+///         sheets[0],
+///         SheetInfo {
+///             name: "fruits".to_string(),
+///             rows: 4,
+///             columns: 4,
+///         }
+///     );
+///     Ok(())
+/// }
+/// ```
+///
+/// ## Notes
+///
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+pub struct ExcelSheets {
+    #[builder(
+        default,
+        setter(
+            doc = "Workbook file path (alternative to `file_data`).",
+            strip_option(fallback = file_path_opt),
+            into
+        )
+    )]
+    file_path: Option<PathBuf>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "Workbook file data (alternative to `file_path`).",
+            strip_option(fallback = file_data_opt),
+            into
+        )
+    )]
+    file_data: Option<Vec<u8>>,
+}
+
+#[allow(non_camel_case_types)]
+impl<
+        __file_data: typed_builder::Optional<Option<Vec<u8>>>,
+        __file_path: typed_builder::Optional<Option<PathBuf>>,
+    > ExcelSheetsBuilder<(__file_path, __file_data)>
+{
+    /// Returns the name and size of each sheet.
+    pub fn execute(self) -> Result<Vec<SheetInfo>> {
+        let builder = self.__build();
+        excel_sheets(builder.file_path, builder.file_data)
+    }
+}
@@ -0,0 +1,454 @@
+use crate::encoding::Encoding;
+use crate::policy::{ErrorPolicy, Report, Skipper};
+use crate::util::{column_index, column_indexes, into_string, reader_from, writer};
+use crate::{CzvError, Result};
+use csv::{ReaderBuilder, StringRecord};
+use std::path::PathBuf;
+use typed_builder::TypedBuilder;
+
+/// Returns the column name, or an error if it or the separator is missing.
+fn check_options(column: Option<String>, separator: &str) -> Result<String> {
+    let column =
+        column.ok_or_else(|| CzvError::InvalidOptions("Must provide column.".to_string()))?;
+    if separator.is_empty() {
+        return Err(CzvError::InvalidOptions(
+            "The separator must not be empty.".to_string(),
+        ));
+    }
+    Ok(column)
+}
+
+/// Returns the CSV data with each value of a column split into one row per part. Recommended alternative: [`czv::explode::Explode`](crate::explode::Explode).
+///
+/// See [`czv::explode::Explode`](crate::explode::Explode) for a builder version (recommended) of this function.
+///
+/// ## Notes
+///
+/// - The other columns of the record are repeated on every row of its parts.
+/// - Parts are not trimmed, and empty parts are kept, so an empty value is written as one row.
+/// - Records are read one at a time.
+///
+/// ## Arguments
+///
+/// * `file_path` - CSV file path (alternative to `file_data`).
+/// * `file_data` - CSV file data (alternative to `file_path`).
+/// * `column` - The header name of the column to split.
+/// * `separator` - The separator between the parts of a value.
+/// * `error_policy` - How to handle malformed records.
+/// * `encoding` - The character encoding of `file_path`.
+pub fn explode(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    column: Option<String>,
+    separator: String,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<String> {
+    Ok(explode_with_report(
+        file_path,
+        file_data,
+        column,
+        separator,
+        error_policy,
+        encoding,
+    )?
+    .output)
+}
+
+fn explode_with_report(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    column: Option<String>,
+    separator: String,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<Report<String>> {
+    let column = check_options(column, &separator)?;
+    let rdr = ReaderBuilder::new();
+    let mut rdr = reader_from(&rdr, file_path.as_deref(), file_data.as_deref(), encoding)?;
+    let headers = rdr.headers()?.clone();
+    let index = column_index(&headers, &column)?;
+
+    let mut wtr = writer();
+    wtr.write_record(&headers)?;
+    let mut skipper = Skipper::new(error_policy);
+    let mut record = StringRecord::new();
+    while skipper.read_record(&mut rdr, &mut record)? {
+        let mut values: Vec<&str> = record.iter().collect();
+        for part in record[index].split(separator.as_str()) {
+            values[index] = part;
+            wtr.write_record(&values)?;
+        }
+    }
+    Ok(skipper.finish(into_string(wtr)?))
+}
+
+#[derive(TypedBuilder)]
+#[builder(doc, builder_method(name=new, doc="Returns an [`ExplodeBuilder`](crate::explode::ExplodeBuilder) to customize explode options by running other methods before getting the output with the [`execute`](crate::explode::ExplodeBuilder::execute) method."), build_method(vis="", name=__build))]
+/// Returns the CSV data with each value of a column split into one row per part.
+///
+/// The entry point for setting up a [`czv::explode::ExplodeBuilder`](crate::explode::ExplodeBuilder) by running [`Explode::new()`](crate::explode::Explode::new).
+///
+/// # Example
+///
+/// ```rust
+/// use czv::{Explode, Result};
+///
+/// fn main() -> Result<()> {
+///     let data = "\
+/// store,fruits
+/// north,apple;banana
+/// south,cherry
+/// ";
+///     let output = Explode::new()
+///         .file_data(data)
+///         .column("fruits")
+///         .separator(";")
+///         .execute()?;
+///     assert_eq!(
+///         output,
+///         "store,fruits\nnorth,apple\nnorth,banana\nsouth,cherry\n"
+///     );
+///     Ok(())
+/// }
+/// ```
+///
+/// ## Notes
+///
+/// - See [`explode`](crate::explode::explode) for how values are split.
+/// - [`Implode`](crate::explode::Implode) joins the rows back together.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+pub struct Explode {
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file path (alternative to `file_data`).",
+            strip_option(fallback = file_path_opt),
+            into
+        )
+    )]
+    file_path: Option<PathBuf>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file data (alternative to `file_path`).",
+            strip_option(fallback = file_data_opt),
+            into
+        )
+    )]
+    file_data: Option<String>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "The header name of the column to split.",
+            strip_option(fallback = column_opt),
+            into
+        )
+    )]
+    column: Option<String>,
+
+    #[builder(
+        default = ",".to_string(),
+        setter(
+            doc = "The separator between the parts of a value. Defaults to `,`.",
+            into
+        )
+    )]
+    separator: String,
+
+    #[builder(
+        default,
+        setter(doc = "How to handle malformed records. Defaults to `ErrorPolicy::Fail`.")
+    )]
+    error_policy: ErrorPolicy,
+
+    #[builder(
+        default,
+        setter(doc = "The character encoding of `file_path`. Defaults to `Encoding::Utf8`.")
+    )]
+    encoding: Encoding,
+}
+
+#[allow(non_camel_case_types)]
+impl<
+        __encoding: typed_builder::Optional<Encoding>,
+        __error_policy: typed_builder::Optional<ErrorPolicy>,
+        __separator: typed_builder::Optional<String>,
+        __column: typed_builder::Optional<Option<String>>,
+        __file_data: typed_builder::Optional<Option<String>>,
+        __file_path: typed_builder::Optional<Option<PathBuf>>,
+    >
+    ExplodeBuilder<(
+        __file_path,
+        __file_data,
+        __column,
+        __separator,
+        __error_policy,
+        __encoding,
+    )>
+{
+    /// Returns the exploded CSV data.
+    pub fn execute(self) -> Result<String> {
+        Ok(self.execute_with_report()?.output)
+    }
+
+    /// Returns the exploded CSV data along with the records skipped with `ErrorPolicy::Collect`.
+    pub fn execute_with_report(self) -> Result<Report<String>> {
+        let builder = self.__build();
+        explode_with_report(
+            builder.file_path,
+            builder.file_data,
+            builder.column,
+            builder.separator,
+            builder.error_policy,
+            builder.encoding,
+        )
+    }
+}
+
+/// Returns the CSV data with consecutive rows sharing keys collapsed into one row. Recommended alternative: [`czv::explode::Implode`](crate::explode::Implode).
+///
+/// See [`czv::explode::Implode`](crate::explode::Implode) for a builder version (recommended) of this function.
+///
+/// ## Notes
+///
+/// - Only consecutive rows are collapsed, so rows with the same keys elsewhere in the data start a
+///   new row. The rows written by [`explode`](crate::explode::explode) for a record are always
+///   consecutive.
+/// - The values of `column` are joined with `separator`, keeping empty values.
+/// - Without `keys`, rows are collapsed when every other column is equal. With `keys`, columns
+///   that are neither keys nor `column` keep the values of the first row.
+/// - Records are read one at a time, and only the current row is kept in memory.
+///
+/// ## Arguments
+///
+/// * `file_path` - CSV file path (alternative to `file_data`).
+/// * `file_data` - CSV file data (alternative to `file_path`).
+/// * `column` - The header name of the column whose values are joined.
+/// * `keys` - The header names of the key columns, or empty for every column except `column`.
+/// * `separator` - The separator between the joined values.
+/// * `error_policy` - How to handle malformed records.
+/// * `encoding` - The character encoding of `file_path`.
+pub fn implode(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    column: Option<String>,
+    keys: Vec<String>,
+    separator: String,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<String> {
+    Ok(implode_with_report(
+        file_path,
+        file_data,
+        column,
+        keys,
+        separator,
+        error_policy,
+        encoding,
+    )?
+    .output)
+}
+
+fn implode_with_report(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    column: Option<String>,
+    keys: Vec<String>,
+    separator: String,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<Report<String>> {
+    let column = check_options(column, &separator)?;
+    let rdr = ReaderBuilder::new();
+    let mut rdr = reader_from(&rdr, file_path.as_deref(), file_data.as_deref(), encoding)?;
+    let headers = rdr.headers()?.clone();
+    let index = column_index(&headers, &column)?;
+    let keys = if keys.is_empty() {
+        (0..headers.len()).filter(|i| *i != index).collect()
+    } else {
+        column_indexes(&headers, &keys)?
+    };
+    if keys.contains(&index) {
+        return Err(CzvError::InvalidOptions(format!(
+            "Column {column:?} cannot also be a key."
+        )));
+    }
+
+    let mut wtr = writer();
+    wtr.write_record(&headers)?;
+    let mut skipper = Skipper::new(error_policy);
+    let mut record = StringRecord::new();
+    // The first record of the current row and the values joined so far.
+    let mut current: Option<(StringRecord, String)> = None;
+    while skipper.read_record(&mut rdr, &mut record)? {
+        match &mut current {
+            Some((first, joined)) if keys.iter().all(|i| first[*i] == record[*i]) => {
+                joined.push_str(&separator);
+                joined.push_str(&record[index]);
+            }
+            _ => {
+                if let Some((first, joined)) = current.take() {
+                    write_imploded(&mut wtr, &first, index, &joined)?;
+                }
+                current = Some((record.clone(), record[index].to_string()));
+            }
+        }
+    }
+    if let Some((first, joined)) = current {
+        write_imploded(&mut wtr, &first, index, &joined)?;
+    }
+    Ok(skipper.finish(into_string(wtr)?))
+}
+
+/// Writes `record` with the value at `index` replaced by `joined`.
+fn write_imploded(
+    wtr: &mut csv::Writer<Vec<u8>>,
+    record: &StringRecord,
+    index: usize,
+    joined: &str,
+) -> Result<()> {
+    wtr.write_record(
+        record
+            .iter()
+            .enumerate()
+            .map(|(i, value)| if i == index { joined } else { value }),
+    )?;
+    Ok(())
+}
+
+#[derive(TypedBuilder)]
+#[builder(doc, builder_method(name=new, doc="Returns an [`ImplodeBuilder`](crate::explode::ImplodeBuilder) to customize implode options by running other methods before getting the output with the [`execute`](crate::explode::ImplodeBuilder::execute) method."), build_method(vis="", name=__build))]
+/// Returns the CSV data with consecutive rows sharing keys collapsed into one row with joined values.
+///
+/// The entry point for setting up a [`czv::explode::ImplodeBuilder`](crate::explode::ImplodeBuilder) by running [`Implode::new()`](crate::explode::Implode::new).
+///
+/// # Example
+///
+/// ```rust
+/// use czv::{Implode, Result};
+///
+/// fn main() -> Result<()> {
+///     let data = "\
+/// store,fruits
+/// north,apple
+/// north,banana
+/// south,cherry
+/// ";
+///     let output = Implode::new()
+///         .file_data(data)
+///         .column("fruits")
+///         .separator(";")
+///         .execute()?;
+///     assert_eq!(output, "store,fruits\nnorth,apple;banana\nsouth,cherry\n");
+///     Ok(())
+/// }
+/// ```
+///
+/// ## Notes
+///
+/// - See [`implode`](crate::explode::implode) for which rows are collapsed.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+pub struct Implode {
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file path (alternative to `file_data`).",
+            strip_option(fallback = file_path_opt),
+            into
+        )
+    )]
+    file_path: Option<PathBuf>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file data (alternative to `file_path`).",
+            strip_option(fallback = file_data_opt),
+            into
+        )
+    )]
+    file_data: Option<String>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "The header name of the column whose values are joined.",
+            strip_option(fallback = column_opt),
+            into
+        )
+    )]
+    column: Option<String>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "The header names of the key columns. Defaults to every column except `column`.",
+            transform = |keys: impl IntoIterator<Item = String>| keys.into_iter().collect()
+        )
+    )]
+    keys: Vec<String>,
+
+    #[builder(
+        default = ",".to_string(),
+        setter(
+            doc = "The separator between the joined values. Defaults to `,`.",
+            into
+        )
+    )]
+    separator: String,
+
+    #[builder(
+        default,
+        setter(doc = "How to handle malformed records. Defaults to `ErrorPolicy::Fail`.")
+    )]
+    error_policy: ErrorPolicy,
+
+    #[builder(
+        default,
+        setter(doc = "The character encoding of `file_path`. Defaults to `Encoding::Utf8`.")
+    )]
+    encoding: Encoding,
+}
+
+#[allow(non_camel_case_types)]
+impl<
+        __encoding: typed_builder::Optional<Encoding>,
+        __error_policy: typed_builder::Optional<ErrorPolicy>,
+        __separator: typed_builder::Optional<String>,
+        __keys: typed_builder::Optional<Vec<String>>,
+        __column: typed_builder::Optional<Option<String>>,
+        __file_data: typed_builder::Optional<Option<String>>,
+        __file_path: typed_builder::Optional<Option<PathBuf>>,
+    >
+    ImplodeBuilder<(
+        __file_path,
+        __file_data,
+        __column,
+        __keys,
+        __separator,
+        __error_policy,
+        __encoding,
+    )>
+{
+    /// Returns the imploded CSV data.
+    pub fn execute(self) -> Result<String> {
+        Ok(self.execute_with_report()?.output)
+    }
+
+    /// Returns the imploded CSV data along with the records skipped with `ErrorPolicy::Collect`.
+    pub fn execute_with_report(self) -> Result<Report<String>> {
+        let builder = self.__build();
+        implode_with_report(
+            builder.file_path,
+            builder.file_data,
+            builder.column,
+            builder.keys,
+            builder.separator,
+            builder.error_policy,
+            builder.encoding,
+        )
+    }
+}
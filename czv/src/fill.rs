@@ -0,0 +1,303 @@
+use crate::encoding::Encoding;
+use crate::policy::{ErrorPolicy, Report, Skipper};
+use crate::util::{column_indexes, into_string, reader_from, writer};
+use crate::{CzvError, Result};
+use csv::{ReaderBuilder, StringRecord};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use typed_builder::TypedBuilder;
+
+/// How empty values are filled.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum FillMethod {
+    /// The previous non-empty value of the column.
+    #[default]
+    Forward,
+    /// The next non-empty value of the column.
+    Backward,
+    /// The first non-empty value of the column.
+    First,
+    /// A constant value.
+    Constant(String),
+}
+
+/// The values used to fill each column, kept separately for each group.
+struct Filler<'a> {
+    method: &'a FillMethod,
+    keys: Vec<usize>,
+    columns: Vec<usize>,
+    groups: HashMap<Vec<String>, Vec<Option<String>>>,
+}
+
+impl Filler<'_> {
+    /// Fills the empty values of a row read in fill order, i.e. backwards for [`FillMethod::Backward`].
+    fn fill(&mut self, values: &mut [String]) {
+        if let FillMethod::Constant(constant) = self.method {
+            for index in &self.columns {
+                if values[*index].is_empty() {
+                    values[*index].clone_from(constant);
+                }
+            }
+            return;
+        }
+        let key: Vec<String> = self.keys.iter().map(|i| values[*i].clone()).collect();
+        let fills = self
+            .groups
+            .entry(key)
+            .or_insert_with(|| vec![None; self.columns.len()]);
+        for (fill, index) in fills.iter_mut().zip(&self.columns) {
+            let value = &mut values[*index];
+            if value.is_empty() {
+                if let Some(fill) = fill {
+                    value.clone_from(fill);
+                }
+            } else if fill.is_none() || *self.method != FillMethod::First {
+                *fill = Some(value.clone());
+            }
+        }
+    }
+}
+
+/// Returns the CSV data with empty values filled. Recommended alternative: [`czv::fill::Fill`](crate::fill::Fill).
+///
+/// See [`czv::fill::Fill`](crate::fill::Fill) for a builder version (recommended) of this function.
+///
+/// ## Notes
+///
+/// - Only empty values are filled, and values stay empty when there is nothing to fill them with,
+///   e.g. before the first non-empty value of a column with [`FillMethod::Forward`].
+/// - With `group_by`, values are only filled from rows with the same values in the key columns, as
+///   if each group were a separate file. Groups do not affect [`FillMethod::Constant`].
+/// - Records are read one at a time, except with [`FillMethod::Backward`] which keeps every record
+///   in memory.
+///
+/// ## Arguments
+///
+/// * `file_path` - CSV file path (alternative to `file_data`).
+/// * `file_data` - CSV file data (alternative to `file_path`).
+/// * `columns` - The header names of the columns to fill, or empty for every column not in `group_by`.
+/// * `method` - How empty values are filled.
+/// * `group_by` - The header names of the key columns.
+/// * `error_policy` - How to handle malformed records.
+/// * `encoding` - The character encoding of `file_path`.
+pub fn fill(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    columns: Vec<String>,
+    method: FillMethod,
+    group_by: Vec<String>,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<String> {
+    Ok(fill_with_report(
+        file_path,
+        file_data,
+        columns,
+        method,
+        group_by,
+        error_policy,
+        encoding,
+    )?
+    .output)
+}
+
+fn fill_with_report(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    columns: Vec<String>,
+    method: FillMethod,
+    group_by: Vec<String>,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<Report<String>> {
+    let rdr = ReaderBuilder::new();
+    let mut rdr = reader_from(&rdr, file_path.as_deref(), file_data.as_deref(), encoding)?;
+    let headers = rdr.headers()?.clone();
+    let keys = column_indexes(&headers, &group_by)?;
+    let columns = if columns.is_empty() {
+        (0..headers.len())
+            .filter(|index| !keys.contains(index))
+            .collect()
+    } else {
+        column_indexes(&headers, &columns)?
+    };
+    if let Some(index) = columns.iter().find(|index| keys.contains(index)) {
+        return Err(CzvError::InvalidOptions(format!(
+            "Column {:?} cannot be both filled and in group_by.",
+            &headers[*index]
+        )));
+    }
+    let mut filler = Filler {
+        method: &method,
+        keys,
+        columns,
+        groups: HashMap::new(),
+    };
+
+    let mut wtr = writer();
+    wtr.write_record(&headers)?;
+    let mut skipper = Skipper::new(error_policy);
+    let mut record = StringRecord::new();
+    let mut rows: Vec<Vec<String>> = vec![];
+    while skipper.read_record(&mut rdr, &mut record)? {
+        let mut values: Vec<String> = record.iter().map(String::from).collect();
+        if method == FillMethod::Backward {
+            rows.push(values);
+        } else {
+            filler.fill(&mut values);
+            wtr.write_record(&values)?;
+        }
+    }
+    for values in rows.iter_mut().rev() {
+        filler.fill(values);
+    }
+    for values in &rows {
+        wtr.write_record(values)?;
+    }
+    Ok(skipper.finish(into_string(wtr)?))
+}
+
+#[derive(TypedBuilder)]
+#[builder(doc, builder_method(name=new, doc="Returns a [`FillBuilder`](crate::fill::FillBuilder) to customize fill options by running other methods before getting the output with the [`execute`](crate::fill::FillBuilder::execute) method."), build_method(vis="", name=__build))]
+/// Returns the CSV data with empty values filled from other rows or with a constant.
+///
+/// The entry point for setting up a [`czv::fill::FillBuilder`](crate::fill::FillBuilder) by running [`Fill::new()`](crate::fill::Fill::new).
+///
+/// # Example
+///
+/// ```rust
+/// use czv::fill::FillMethod;
+/// use czv::{Fill, Result};
+///
+/// fn main() -> Result<()> {
+///     let data = "\
+/// store,day,price
+/// north,mon,2.50
+/// north,tue,
+/// south,mon,
+/// south,tue,3.00
+/// ";
+///     let output = Fill::new()
+///         .file_data(data)
+///         .columns(["price".to_string()])
+///         .execute()?;
+///     assert_eq!(
+///         output,
+///         "store,day,price\nnorth,mon,2.50\nnorth,tue,2.50\nsouth,mon,2.50\nsouth,tue,3.00\n"
+///     );
+///
+///     let output = Fill::new()
+///         .file_data(data)
+///         .columns(["price".to_string()])
+///         .method(FillMethod::Backward)
+///         .group_by(["store".to_string()])
+///         .execute()?;
+///     assert_eq!(
+///         output,
+///         "store,day,price\nnorth,mon,2.50\nnorth,tue,\nsouth,mon,3.00\nsouth,tue,3.00\n"
+///     );
+///     Ok(())
+/// }
+/// ```
+///
+/// ## Notes
+///
+/// - See [`fill`](crate::fill::fill) for which values are filled and how groups are handled.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+pub struct Fill {
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file path (alternative to `file_data`).",
+            strip_option(fallback = file_path_opt),
+            into
+        )
+    )]
+    file_path: Option<PathBuf>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file data (alternative to `file_path`).",
+            strip_option(fallback = file_data_opt),
+            into
+        )
+    )]
+    file_data: Option<String>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "The header names of the columns to fill. Defaults to every column not in `group_by`.",
+            transform = |columns: impl IntoIterator<Item = String>| columns.into_iter().collect()
+        )
+    )]
+    columns: Vec<String>,
+
+    #[builder(
+        default,
+        setter(doc = "How empty values are filled. Defaults to `FillMethod::Forward`.")
+    )]
+    method: FillMethod,
+
+    #[builder(
+        default,
+        setter(
+            doc = "The header names of the key columns. Values are only filled from rows with the same key.",
+            transform = |group_by: impl IntoIterator<Item = String>| group_by.into_iter().collect()
+        )
+    )]
+    group_by: Vec<String>,
+
+    #[builder(
+        default,
+        setter(doc = "How to handle malformed records. Defaults to `ErrorPolicy::Fail`.")
+    )]
+    error_policy: ErrorPolicy,
+
+    #[builder(
+        default,
+        setter(doc = "The character encoding of `file_path`. Defaults to `Encoding::Utf8`.")
+    )]
+    encoding: Encoding,
+}
+
+#[allow(non_camel_case_types)]
+impl<
+        __encoding: typed_builder::Optional<Encoding>,
+        __error_policy: typed_builder::Optional<ErrorPolicy>,
+        __group_by: typed_builder::Optional<Vec<String>>,
+        __method: typed_builder::Optional<FillMethod>,
+        __columns: typed_builder::Optional<Vec<String>>,
+        __file_data: typed_builder::Optional<Option<String>>,
+        __file_path: typed_builder::Optional<Option<PathBuf>>,
+    >
+    FillBuilder<(
+        __file_path,
+        __file_data,
+        __columns,
+        __method,
+        __group_by,
+        __error_policy,
+        __encoding,
+    )>
+{
+    /// Returns the filled CSV data.
+    pub fn execute(self) -> Result<String> {
+        Ok(self.execute_with_report()?.output)
+    }
+
+    /// Returns the filled CSV data along with the records skipped with `ErrorPolicy::Collect`.
+    pub fn execute_with_report(self) -> Result<Report<String>> {
+        let builder = self.__build();
+        fill_with_report(
+            builder.file_path,
+            builder.file_data,
+            builder.columns,
+            builder.method,
+            builder.group_by,
+            builder.error_policy,
+            builder.encoding,
+        )
+    }
+}
@@ -0,0 +1,213 @@
+use crate::encoding::Encoding;
+use crate::policy::{ErrorPolicy, Report, Skipper};
+use crate::util::{into_string, reader_from, writer};
+use crate::{CzvError, Result};
+use csv::{ReaderBuilder, StringRecord};
+use std::path::PathBuf;
+use typed_builder::TypedBuilder;
+
+/// Returns the CSV data with every row padded or truncated to the same number of fields. Recommended alternative: [`czv::fix_lengths::FixLengths`](crate::fix_lengths::FixLengths).
+///
+/// See [`czv::fix_lengths::FixLengths`](crate::fix_lengths::FixLengths) for a builder version (recommended) of this function.
+///
+/// ## Notes
+///
+/// - Unlike other operations, records may have any number of fields, including the header row.
+/// - Short rows are padded with empty fields and long rows are truncated, dropping their last fields.
+/// - Without `length` or `longest`, rows are fixed to the length of the header row, which is
+///   written unchanged. With either of them, the header row is padded or truncated like other rows.
+/// - With `longest`, the data is read twice, once to find the length of the longest row.
+///
+/// ## Arguments
+///
+/// * `file_path` - CSV file path (alternative to `file_data`).
+/// * `file_data` - CSV file data (alternative to `file_path`).
+/// * `length` - The number of fields of every row.
+/// * `longest` - Specify whether to use the number of fields of the longest row as the length.
+/// * `error_policy` - How to handle malformed records.
+/// * `encoding` - The character encoding of `file_path`.
+pub fn fix_lengths(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    length: Option<usize>,
+    longest: bool,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<String> {
+    Ok(fix_lengths_with_report(
+        file_path,
+        file_data,
+        length,
+        longest,
+        error_policy,
+        encoding,
+    )?
+    .output)
+}
+
+fn fix_lengths_with_report(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    length: Option<usize>,
+    longest: bool,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<Report<String>> {
+    match length {
+        Some(_) if longest => {
+            return Err(CzvError::InvalidOptions(
+                "Cannot have both length and longest, specify one only.".to_string(),
+            ))
+        }
+        Some(0) => {
+            return Err(CzvError::InvalidOptions(
+                "The length must be greater than 0.".to_string(),
+            ))
+        }
+        _ => {}
+    }
+    let mut rdr = ReaderBuilder::new();
+    rdr.flexible(true).has_headers(false);
+    let mut record = StringRecord::new();
+    let length = if longest {
+        // Records skipped here are skipped again, and reported, when writing.
+        let mut skipper = Skipper::new(error_policy);
+        let mut longest_rdr =
+            reader_from(&rdr, file_path.as_deref(), file_data.as_deref(), encoding)?;
+        let mut length = 0;
+        while skipper.read_record(&mut longest_rdr, &mut record)? {
+            length = length.max(record.len());
+        }
+        Some(length)
+    } else {
+        length
+    };
+
+    let mut rdr = reader_from(&rdr, file_path.as_deref(), file_data.as_deref(), encoding)?;
+    let mut wtr = writer();
+    let mut skipper = Skipper::new(error_policy);
+    let mut length = length;
+    while skipper.read_record(&mut rdr, &mut record)? {
+        let length = *length.get_or_insert(record.len());
+        wtr.write_record(record.iter().chain(std::iter::repeat("")).take(length))?;
+    }
+    Ok(skipper.finish(into_string(wtr)?))
+}
+
+#[derive(TypedBuilder)]
+#[builder(doc, builder_method(name=new, doc="Returns a [`FixLengthsBuilder`](crate::fix_lengths::FixLengthsBuilder) to customize length options by running other methods before getting the output with the [`execute`](crate::fix_lengths::FixLengthsBuilder::execute) method."), build_method(vis="", name=__build))]
+/// Returns the CSV data with ragged rows padded with empty fields or truncated to the same length.
+///
+/// The entry point for setting up a [`czv::fix_lengths::FixLengthsBuilder`](crate::fix_lengths::FixLengthsBuilder) by running [`FixLengths::new()`](crate::fix_lengths::FixLengths::new).
+///
+/// # Example
+///
+/// ```rust
+/// use czv::{FixLengths, Result};
+///
+/// fn main() -> Result<()> {
+///     let data = "\
+/// fruit,price
+/// apple
+/// banana,3.00,ripe
+/// ";
+///     let output = FixLengths::new().file_data(data).execute()?;
+///     assert_eq!(output, "fruit,price\napple,\nbanana,3.00\n");
+///
+///     let output = FixLengths::new().file_data(data).longest(true).execute()?;
+///     assert_eq!(output, "fruit,price,\napple,,\nbanana,3.00,ripe\n");
+///     Ok(())
+/// }
+/// ```
+///
+/// ## Notes
+///
+/// - See [`fix_lengths`](crate::fix_lengths::fix_lengths) for how the length is chosen.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+pub struct FixLengths {
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file path (alternative to `file_data`).",
+            strip_option(fallback = file_path_opt),
+            into
+        )
+    )]
+    file_path: Option<PathBuf>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file data (alternative to `file_path`).",
+            strip_option(fallback = file_data_opt),
+            into
+        )
+    )]
+    file_data: Option<String>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "The number of fields of every row. Defaults to the length of the header row.",
+            strip_option(fallback = length_opt)
+        )
+    )]
+    length: Option<usize>,
+
+    #[builder(
+        default = false,
+        setter(
+            doc = "Specify whether to use the number of fields of the longest row as the length. Defaults to false."
+        )
+    )]
+    longest: bool,
+
+    #[builder(
+        default,
+        setter(doc = "How to handle malformed records. Defaults to `ErrorPolicy::Fail`.")
+    )]
+    error_policy: ErrorPolicy,
+
+    #[builder(
+        default,
+        setter(doc = "The character encoding of `file_path`. Defaults to `Encoding::Utf8`.")
+    )]
+    encoding: Encoding,
+}
+
+#[allow(non_camel_case_types)]
+impl<
+        __encoding: typed_builder::Optional<Encoding>,
+        __error_policy: typed_builder::Optional<ErrorPolicy>,
+        __longest: typed_builder::Optional<bool>,
+        __length: typed_builder::Optional<Option<usize>>,
+        __file_data: typed_builder::Optional<Option<String>>,
+        __file_path: typed_builder::Optional<Option<PathBuf>>,
+    >
+    FixLengthsBuilder<(
+        __file_path,
+        __file_data,
+        __length,
+        __longest,
+        __error_policy,
+        __encoding,
+    )>
+{
+    /// Returns the CSV data with fixed lengths.
+    pub fn execute(self) -> Result<String> {
+        Ok(self.execute_with_report()?.output)
+    }
+
+    /// Returns the CSV data with fixed lengths along with the records skipped with `ErrorPolicy::Collect`.
+    pub fn execute_with_report(self) -> Result<Report<String>> {
+        let builder = self.__build();
+        fix_lengths_with_report(
+            builder.file_path,
+            builder.file_data,
+            builder.length,
+            builder.longest,
+            builder.error_policy,
+            builder.encoding,
+        )
+    }
+}
@@ -0,0 +1,635 @@
+use crate::encoding::Encoding;
+use crate::policy::{ErrorPolicy, Report, Skipper};
+use crate::util::{into_string, reader_from, source_from, writer};
+use crate::{CzvError, Result};
+use csv::{ReaderBuilder, StringRecord};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use typed_builder::TypedBuilder;
+
+/// How values are padded to their column width.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Alignment {
+    /// Pad values on the right. This is the default.
+    #[default]
+    Left,
+    /// Pad values on the left, e.g. for numbers.
+    Right,
+}
+
+/// Returns the column widths and, if a layout is given, the column names.
+fn columns_from(
+    widths: Vec<usize>,
+    layout: Vec<(String, usize)>,
+) -> Result<(Vec<usize>, Option<Vec<String>>)> {
+    let (widths, names) = match (widths.is_empty(), layout.is_empty()) {
+        (false, false) => {
+            return Err(CzvError::InvalidOptions(
+                "Cannot have both widths and layout, specify one only.".to_string(),
+            ))
+        }
+        (true, false) => {
+            let (names, widths) = layout.into_iter().unzip();
+            (widths, Some(names))
+        }
+        _ => (widths, None),
+    };
+    if widths.contains(&0) {
+        return Err(CzvError::InvalidOptions(
+            "Column widths must be greater than 0.".to_string(),
+        ));
+    }
+    Ok((widths, names))
+}
+
+/// Splits a line into fields of `widths` characters, or returns `None` if the line has characters
+/// other than whitespace after the widths combined.
+fn split_line(line: &str, widths: &[usize], trim: bool) -> Option<Vec<String>> {
+    let mut chars = line.chars();
+    let fields = widths
+        .iter()
+        .map(|width| {
+            let field: String = chars.by_ref().take(*width).collect();
+            if trim {
+                field.trim().to_string()
+            } else {
+                field
+            }
+        })
+        .collect();
+    // Records padded to a fixed length may end with spaces after the last column.
+    chars.all(char::is_whitespace).then_some(fields)
+}
+
+/// Returns the fixed-width data converted to CSV. Recommended alternative: [`czv::fixed_width::FromFixedWidth`](crate::fixed_width::FromFixedWidth).
+///
+/// See [`czv::fixed_width::FromFixedWidth`](crate::fixed_width::FromFixedWidth) for a builder version (recommended) of this function.
+///
+/// ## Notes
+///
+/// - Columns are set with either `widths`, or `layout` which also names them. Widths are counted in
+///   characters.
+/// - With `widths`, the header row is split from the first line. With `layout`, the header row is
+///   the layout's names and the first line is skipped if `has_header_row` is `true`.
+/// - Without a header row (`has_header_row` is `false` and no `layout`), no header row is written.
+/// - Lines shorter than the widths combined have empty trailing fields, and blank lines are ignored.
+/// - Whitespace after the widths combined is ignored, but lines with other characters there are
+///   malformed, they are left out of the output when skipped with `error_policy`.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+///
+/// ## Arguments
+///
+/// * `file_path` - Fixed-width file path (alternative to `file_data`).
+/// * `file_data` - Fixed-width file data (alternative to `file_path`).
+/// * `widths` - The width of each column (alternative to `layout`).
+/// * `layout` - The name and width of each column (alternative to `widths`).
+/// * `has_header_row` - Specify whether the first line is a header row.
+/// * `trim` - Specify whether to trim whitespace around each field.
+/// * `error_policy` - How to handle malformed lines.
+/// * `encoding` - The character encoding of `file_path`.
+#[allow(clippy::too_many_arguments)]
+pub fn from_fixed_width(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    widths: Vec<usize>,
+    layout: Vec<(String, usize)>,
+    has_header_row: bool,
+    trim: bool,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<String> {
+    Ok(from_fixed_width_with_report(
+        file_path.as_deref(),
+        file_data.as_deref(),
+        widths,
+        layout,
+        has_header_row,
+        trim,
+        error_policy,
+        encoding,
+    )?
+    .output)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn from_fixed_width_with_report(
+    file_path: Option<&Path>,
+    file_data: Option<&str>,
+    widths: Vec<usize>,
+    layout: Vec<(String, usize)>,
+    has_header_row: bool,
+    trim: bool,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<Report<String>> {
+    let (widths, names) = columns_from(widths, layout)?;
+    if widths.is_empty() {
+        return Err(CzvError::InvalidOptions(
+            "Must provide either widths or layout.".to_string(),
+        ));
+    }
+    let total_width: usize = widths.iter().sum();
+
+    let mut rdr = BufReader::new(source_from(file_path, file_data, encoding)?);
+    let mut wtr = writer();
+    let mut skipper = Skipper::new(error_policy);
+    if let Some(names) = &names {
+        wtr.write_record(names)?;
+    }
+
+    let mut text = String::new();
+    let mut line = 0;
+    let mut byte = 0;
+    let mut record = 0;
+    let mut skip_header_row = has_header_row && names.is_some();
+    loop {
+        text.clear();
+        let read = rdr.read_line(&mut text)?;
+        if read == 0 {
+            break;
+        }
+        line += 1;
+        let start = byte;
+        byte += read as u64;
+
+        let content = text.trim_end_matches(['\n', '\r']);
+        if content.trim().is_empty() {
+            continue;
+        }
+        if skip_header_row {
+            skip_header_row = false;
+            record += 1;
+            continue;
+        }
+        match split_line(content, &widths, trim) {
+            Some(fields) => wtr.write_record(fields)?,
+            None => skipper.skip(CzvError::Parse {
+                message: format!(
+                    "Line {line} is longer than the {total_width} characters of the columns."
+                ),
+                line: Some(line),
+                byte: Some(start),
+                record: Some(record),
+            })?,
+        }
+        record += 1;
+    }
+
+    Ok(skipper.finish(into_string(wtr)?))
+}
+
+#[derive(TypedBuilder)]
+#[builder(doc, builder_method(name=new, doc="Returns a [`FromFixedWidthBuilder`](crate::fixed_width::FromFixedWidthBuilder) to customize fixed-width conversion options by running other methods before getting the CSV with the [`execute`](crate::fixed_width::FromFixedWidthBuilder::execute) method."), build_method(vis="", name=__build))]
+/// Returns the fixed-width data converted to CSV.
+///
+/// The entry point for setting up a [`czv::fixed_width::FromFixedWidthBuilder`](crate::fixed_width::FromFixedWidthBuilder) by running [`FromFixedWidth::new()`](crate::fixed_width::FromFixedWidth::new).
+///
+/// # Example
+///
+/// ```rust
+/// use czv::{FromFixedWidth, Result};
+///
+/// fn main() -> Result<()> {
+///     let data = "\
+/// apple       2.50
+/// banana      3.00
+/// ";
+///     let output = FromFixedWidth::new()
+///         .file_data(data)
+///         .layout([("fruit".to_string(), 10), ("price".to_string(), 6)])
+///         .has_header_row(false)
+///         .execute()?;
+///     assert_eq!(output, "fruit,price\napple,2.50\nbanana,3.00\n");
+///     Ok(())
+/// }
+/// ```
+///
+/// ## Notes
+///
+/// - See [`from_fixed_width`](crate::fixed_width::from_fixed_width) for how lines are split.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+pub struct FromFixedWidth {
+    #[builder(
+        default,
+        setter(
+            doc = "Fixed-width file path (alternative to `file_data`).",
+            strip_option(fallback = file_path_opt),
+            into
+        )
+    )]
+    file_path: Option<PathBuf>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "Fixed-width file data (alternative to `file_path`).",
+            strip_option(fallback = file_data_opt),
+            into
+        )
+    )]
+    file_data: Option<String>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "The width of each column in characters (alternative to `layout`).",
+            transform = |widths: impl IntoIterator<Item = usize>| widths.into_iter().collect()
+        )
+    )]
+    widths: Vec<usize>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "The name and width in characters of each column (alternative to `widths`).",
+            transform = |layout: impl IntoIterator<Item = (String, usize)>| layout.into_iter().collect()
+        )
+    )]
+    layout: Vec<(String, usize)>,
+
+    #[builder(
+        default = true,
+        setter(doc = "Specify whether the first line is a header row. Defaults to `true`.")
+    )]
+    has_header_row: bool,
+
+    #[builder(
+        default = true,
+        setter(doc = "Specify whether to trim whitespace around each field. Defaults to `true`.")
+    )]
+    trim: bool,
+
+    #[builder(
+        default,
+        setter(doc = "How to handle malformed lines. Defaults to `ErrorPolicy::Fail`.")
+    )]
+    error_policy: ErrorPolicy,
+
+    #[builder(
+        default,
+        setter(doc = "The character encoding of `file_path`. Defaults to `Encoding::Utf8`.")
+    )]
+    encoding: Encoding,
+}
+
+#[allow(non_camel_case_types)]
+impl<
+        __encoding: typed_builder::Optional<Encoding>,
+        __error_policy: typed_builder::Optional<ErrorPolicy>,
+        __trim: typed_builder::Optional<bool>,
+        __has_header_row: typed_builder::Optional<bool>,
+        __layout: typed_builder::Optional<Vec<(String, usize)>>,
+        __widths: typed_builder::Optional<Vec<usize>>,
+        __file_data: typed_builder::Optional<Option<String>>,
+        __file_path: typed_builder::Optional<Option<PathBuf>>,
+    >
+    FromFixedWidthBuilder<(
+        __file_path,
+        __file_data,
+        __widths,
+        __layout,
+        __has_header_row,
+        __trim,
+        __error_policy,
+        __encoding,
+    )>
+{
+    /// Returns the CSV output.
+    pub fn execute(self) -> Result<String> {
+        Ok(self.execute_with_report()?.output)
+    }
+
+    /// Returns the CSV output along with the lines skipped with `ErrorPolicy::Collect`.
+    pub fn execute_with_report(self) -> Result<Report<String>> {
+        let builder = self.__build();
+        from_fixed_width_with_report(
+            builder.file_path.as_deref(),
+            builder.file_data.as_deref(),
+            builder.widths,
+            builder.layout,
+            builder.has_header_row,
+            builder.trim,
+            builder.error_policy,
+            builder.encoding,
+        )
+    }
+}
+
+/// Pads `value` with spaces to `width` characters.
+fn pad(value: &str, width: usize, alignment: Alignment) -> String {
+    match alignment {
+        Alignment::Left => format!("{value:<width$}"),
+        Alignment::Right => format!("{value:>width$}"),
+    }
+}
+
+/// Returns the CSV data converted to fixed-width text. Recommended alternative: [`czv::fixed_width::ToFixedWidth`](crate::fixed_width::ToFixedWidth).
+///
+/// See [`czv::fixed_width::ToFixedWidth`](crate::fixed_width::ToFixedWidth) for a builder version (recommended) of this function.
+///
+/// ## Notes
+///
+/// - Columns are set with either `widths` for every column in order, or `layout` which selects
+///   columns by header name. Without either, each column is as wide as its longest value, which
+///   reads the input twice.
+/// - Values shorter than their column are padded with spaces according to `alignment`.
+/// - Records with a value longer than its column are malformed unless `truncate` is `true`. They
+///   are left out of the output when skipped with `error_policy`.
+/// - A header name longer than its column is an invalid option unless `truncate` is `true` or
+///   `include_header_row` is `false`, as the header row cannot be skipped.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+///
+/// ## Arguments
+///
+/// * `file_path` - CSV file path (alternative to `file_data`).
+/// * `file_data` - CSV file data (alternative to `file_path`).
+/// * `widths` - The width of each column (alternative to `layout`).
+/// * `layout` - The header name and width of each column to write (alternative to `widths`).
+/// * `alignment` - How values are padded to their column width.
+/// * `truncate` - Specify whether to cut values longer than their column instead of failing.
+/// * `include_header_row` - Specify whether to include the header row (first row) in the output.
+/// * `error_policy` - How to handle malformed records.
+/// * `encoding` - The character encoding of `file_path`.
+#[allow(clippy::too_many_arguments)]
+pub fn to_fixed_width(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    widths: Vec<usize>,
+    layout: Vec<(String, usize)>,
+    alignment: Alignment,
+    truncate: bool,
+    include_header_row: bool,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<String> {
+    Ok(to_fixed_width_with_report(
+        file_path.as_deref(),
+        file_data.as_deref(),
+        widths,
+        layout,
+        alignment,
+        truncate,
+        include_header_row,
+        error_policy,
+        encoding,
+    )?
+    .output)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn to_fixed_width_with_report(
+    file_path: Option<&Path>,
+    file_data: Option<&str>,
+    widths: Vec<usize>,
+    layout: Vec<(String, usize)>,
+    alignment: Alignment,
+    truncate: bool,
+    include_header_row: bool,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<Report<String>> {
+    let (widths, names) = columns_from(widths, layout)?;
+    let rdr = ReaderBuilder::new();
+    let mut reader = reader_from(&rdr, file_path, file_data, encoding)?;
+    let headers = reader.headers()?.clone();
+
+    let columns: Vec<usize> = match &names {
+        Some(names) => names
+            .iter()
+            .map(|name| {
+                headers
+                    .iter()
+                    .position(|header| header == name)
+                    .ok_or_else(|| CzvError::ColumnNotFound(name.clone()))
+            })
+            .collect::<Result<_>>()?,
+        None => (0..headers.len()).collect(),
+    };
+    let widths = if widths.is_empty() {
+        let mut widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
+        let mut skipper = Skipper::new(error_policy);
+        let mut record = StringRecord::new();
+        while skipper.read_record(&mut reader, &mut record)? {
+            for (width, value) in widths.iter_mut().zip(record.iter()) {
+                *width = (*width).max(value.chars().count());
+            }
+        }
+        reader = reader_from(&rdr, file_path, file_data, encoding)?;
+        reader.headers()?;
+        widths.into_iter().map(|width| width.max(1)).collect()
+    } else if names.is_none() && widths.len() != headers.len() {
+        return Err(CzvError::InvalidOptions(format!(
+            "Expected {} widths for the {} columns but found {}.",
+            headers.len(),
+            headers.len(),
+            widths.len()
+        )));
+    } else {
+        widths
+    };
+
+    if include_header_row && !truncate {
+        for (column, width) in columns.iter().zip(&widths) {
+            let header = headers.get(*column).unwrap_or_default();
+            if header.chars().count() > *width {
+                return Err(CzvError::InvalidOptions(format!(
+                    "Header {header:?} is longer than the {width} characters of its column, \
+                     set truncate or a wider column."
+                )));
+            }
+        }
+    }
+
+    let mut output = String::new();
+    let mut write_line = |record: &StringRecord| -> Result<()> {
+        let mut line = String::with_capacity(widths.iter().sum());
+        for (column, width) in columns.iter().zip(&widths) {
+            let value = record.get(*column).unwrap_or_default();
+            if value.chars().count() > *width {
+                if !truncate {
+                    return Err(CzvError::parse_at(
+                        format!(
+                            "Value {value:?} is longer than the {width} characters of column {:?}.",
+                            headers.get(*column).unwrap_or_default()
+                        ),
+                        record,
+                    ));
+                }
+                line.extend(value.chars().take(*width));
+            } else {
+                line.push_str(&pad(value, *width, alignment));
+            }
+        }
+        output.push_str(&line);
+        output.push('\n');
+        Ok(())
+    };
+
+    if include_header_row {
+        write_line(&headers)?;
+    }
+    let mut skipper = Skipper::new(error_policy);
+    let mut record = StringRecord::new();
+    while skipper.read_record(&mut reader, &mut record)? {
+        match write_line(&record) {
+            Err(err @ CzvError::Parse { .. }) => skipper.skip(err)?,
+            result => result?,
+        }
+    }
+
+    Ok(skipper.finish(output))
+}
+
+#[derive(TypedBuilder)]
+#[builder(doc, builder_method(name=new, doc="Returns a [`ToFixedWidthBuilder`](crate::fixed_width::ToFixedWidthBuilder) to customize fixed-width conversion options by running other methods before getting the output with the [`execute`](crate::fixed_width::ToFixedWidthBuilder::execute) method."), build_method(vis="", name=__build))]
+/// Returns the CSV data converted to fixed-width text.
+///
+/// The entry point for setting up a [`czv::fixed_width::ToFixedWidthBuilder`](crate::fixed_width::ToFixedWidthBuilder) by running [`ToFixedWidth::new()`](crate::fixed_width::ToFixedWidth::new).
+///
+/// # Example
+///
+/// ```rust
+/// use czv::fixed_width::Alignment;
+/// use czv::{Result, ToFixedWidth};
+///
+/// fn main() -> Result<()> {
+///     let data = "\
+/// fruit,price
+/// apple,2.50
+/// banana,3.00
+/// ";
+///     let output = ToFixedWidth::new()
+///         .file_data(data)
+///         .widths([8, 6])
+///         .alignment(Alignment::Right)
+///         .execute()?;
+///     assert_eq!(output, "   fruit price\n   apple  2.50\n  banana  3.00\n");
+///     Ok(())
+/// }
+/// ```
+///
+/// ## Notes
+///
+/// - See [`to_fixed_width`](crate::fixed_width::to_fixed_width) for how column widths are chosen.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+pub struct ToFixedWidth {
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file path (alternative to `file_data`).",
+            strip_option(fallback = file_path_opt),
+            into
+        )
+    )]
+    file_path: Option<PathBuf>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file data (alternative to `file_path`).",
+            strip_option(fallback = file_data_opt),
+            into
+        )
+    )]
+    file_data: Option<String>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "The width of each column in characters (alternative to `layout`). Defaults to the longest value of each column.",
+            transform = |widths: impl IntoIterator<Item = usize>| widths.into_iter().collect()
+        )
+    )]
+    widths: Vec<usize>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "The header name and width in characters of each column to write (alternative to `widths`).",
+            transform = |layout: impl IntoIterator<Item = (String, usize)>| layout.into_iter().collect()
+        )
+    )]
+    layout: Vec<(String, usize)>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "How values are padded to their column width. Defaults to `Alignment::Left`."
+        )
+    )]
+    alignment: Alignment,
+
+    #[builder(
+        default,
+        setter(
+            doc = "Specify whether to cut values longer than their column instead of failing. Defaults to `false`."
+        )
+    )]
+    truncate: bool,
+
+    #[builder(
+        default = true,
+        setter(
+            doc = "Specify whether to include the header row (first row) in the output. Defaults to `true`."
+        )
+    )]
+    include_header_row: bool,
+
+    #[builder(
+        default,
+        setter(doc = "How to handle malformed records. Defaults to `ErrorPolicy::Fail`.")
+    )]
+    error_policy: ErrorPolicy,
+
+    #[builder(
+        default,
+        setter(doc = "The character encoding of `file_path`. Defaults to `Encoding::Utf8`.")
+    )]
+    encoding: Encoding,
+}
+
+#[allow(non_camel_case_types)]
+impl<
+        __encoding: typed_builder::Optional<Encoding>,
+        __error_policy: typed_builder::Optional<ErrorPolicy>,
+        __include_header_row: typed_builder::Optional<bool>,
+        __truncate: typed_builder::Optional<bool>,
+        __alignment: typed_builder::Optional<Alignment>,
+        __layout: typed_builder::Optional<Vec<(String, usize)>>,
+        __widths: typed_builder::Optional<Vec<usize>>,
+        __file_data: typed_builder::Optional<Option<String>>,
+        __file_path: typed_builder::Optional<Option<PathBuf>>,
+    >
+    ToFixedWidthBuilder<(
+        __file_path,
+        __file_data,
+        __widths,
+        __layout,
+        __alignment,
+        __truncate,
+        __include_header_row,
+        __error_policy,
+        __encoding,
+    )>
+{
+    /// Returns the fixed-width output.
+    pub fn execute(self) -> Result<String> {
+        Ok(self.execute_with_report()?.output)
+    }
+
+    /// Returns the fixed-width output along with the records skipped with `ErrorPolicy::Collect`.
+    pub fn execute_with_report(self) -> Result<Report<String>> {
+        let builder = self.__build();
+        to_fixed_width_with_report(
+            builder.file_path.as_deref(),
+            builder.file_data.as_deref(),
+            builder.widths,
+            builder.layout,
+            builder.alignment,
+            builder.truncate,
+            builder.include_header_row,
+            builder.error_policy,
+            builder.encoding,
+        )
+    }
+}
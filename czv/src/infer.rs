@@ -0,0 +1,127 @@
+use crate::policy::{ErrorPolicy, Skipper};
+use crate::{CzvError, Result};
+use csv::{Reader, StringRecord};
+use std::fmt;
+use std::io::Read;
+use std::str::FromStr;
+
+/// The type of a column's values as inferred from the CSV data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnType {
+    /// `true` or `false` (case-insensitive).
+    Boolean,
+    /// A whole number that fits in an `i64`.
+    Integer,
+    /// A finite floating-point number.
+    Float,
+    /// Any other text.
+    String,
+}
+
+impl ColumnType {
+    /// Returns the narrowest type that `value` can be read as, or `None` if the value is empty.
+    pub fn of(value: &str) -> Option<Self> {
+        if value.is_empty() {
+            None
+        } else if value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("false") {
+            Some(Self::Boolean)
+        } else if has_leading_zero(value) {
+            // Keep identifiers such as zip codes as text.
+            Some(Self::String)
+        } else if value.parse::<i64>().is_ok() {
+            Some(Self::Integer)
+        } else if value.parse::<f64>().is_ok_and(f64::is_finite) {
+            Some(Self::Float)
+        } else {
+            Some(Self::String)
+        }
+    }
+
+    /// Returns the narrowest type that both `self` and `other` values can be read as.
+    pub fn merge(self, other: Self) -> Self {
+        match (self, other) {
+            (a, b) if a == b => a,
+            (Self::Integer, Self::Float) | (Self::Float, Self::Integer) => Self::Float,
+            _ => Self::String,
+        }
+    }
+}
+
+impl fmt::Display for ColumnType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Boolean => "boolean",
+            Self::Integer => "integer",
+            Self::Float => "float",
+            Self::String => "string",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for ColumnType {
+    type Err = CzvError;
+
+    /// Parses a type name as written by [`Display`](fmt::Display), e.g. `integer`.
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "boolean" => Ok(Self::Boolean),
+            "integer" => Ok(Self::Integer),
+            "float" => Ok(Self::Float),
+            "string" => Ok(Self::String),
+            _ => Err(CzvError::InvalidOptions(format!(
+                "Unknown column type {s:?}, expected boolean, integer, float or string."
+            ))),
+        }
+    }
+}
+
+fn has_leading_zero(value: &str) -> bool {
+    let digits = value.strip_prefix(['-', '+']).unwrap_or(value).as_bytes();
+    digits.len() > 1 && digits[0] == b'0' && digits[1].is_ascii_digit()
+}
+
+/// A column's name and inferred type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InferredColumn {
+    /// The header name.
+    pub name: String,
+    /// The inferred type, which is [`ColumnType::String`] if every value is empty.
+    pub column_type: ColumnType,
+    /// Whether any value in the column is empty.
+    pub nullable: bool,
+}
+
+/// Infers the type of every column from the headers and all records of `rdr`.
+///
+/// Malformed records are left out of the inference when skipped with `error_policy`, and are not reported.
+pub(crate) fn infer_columns<R: Read>(
+    rdr: &mut Reader<R>,
+    error_policy: ErrorPolicy,
+) -> Result<Vec<InferredColumn>> {
+    let headers = rdr.headers()?.clone();
+    let mut types: Vec<Option<ColumnType>> = vec![None; headers.len()];
+    let mut nullable = vec![false; headers.len()];
+    let mut skipper = Skipper::new(error_policy);
+    let mut record = StringRecord::new();
+    while skipper.read_record(rdr, &mut record)? {
+        for (i, value) in record.iter().enumerate() {
+            match ColumnType::of(value) {
+                None => nullable[i] = true,
+                Some(value_type) => {
+                    types[i] = Some(types[i].map_or(value_type, |t| t.merge(value_type)));
+                }
+            }
+        }
+    }
+    Ok(headers
+        .iter()
+        .zip(types)
+        .zip(nullable)
+        .map(|((name, column_type), nullable)| InferredColumn {
+            name: name.to_string(),
+            column_type: column_type.unwrap_or(ColumnType::String),
+            nullable,
+        })
+        .collect())
+}
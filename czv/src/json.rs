@@ -0,0 +1,691 @@
+use crate::encoding::Encoding;
+use crate::infer::{infer_columns, ColumnType, InferredColumn};
+use crate::policy::{ErrorPolicy, Report, Skipper};
+use crate::util::{into_string, reader_from, source_from, writer};
+use crate::{CzvError, Result};
+use csv::{ReaderBuilder, StringRecord};
+use serde_json::{Map, Number, Value};
+use std::collections::{HashMap, HashSet};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use typed_builder::TypedBuilder;
+
+/// The layout of JSON output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum JsonFormat {
+    /// A single JSON array of objects. This is the default.
+    #[default]
+    Array,
+    /// One JSON object per line, also known as JSON Lines or NDJSON.
+    Lines,
+}
+
+/// Returns the keys each header is written to, which is more than one key for dotted header names when `nested`.
+fn key_paths(headers: &StringRecord, nested: bool) -> Result<Vec<Vec<String>>> {
+    let paths = headers
+        .iter()
+        .map(|name| match nested {
+            true => name.split('.').map(str::to_string).collect(),
+            false => vec![name.to_string()],
+        })
+        .collect::<Vec<Vec<_>>>();
+    for (i, a) in paths.iter().enumerate() {
+        for b in &paths[i + 1..] {
+            let shortest = a.len().min(b.len());
+            if a[..shortest] == b[..shortest] {
+                return Err(CzvError::parse(format!(
+                    "Header {:?} conflicts with header {:?}.",
+                    a.join("."),
+                    b.join(".")
+                )));
+            }
+        }
+    }
+    Ok(paths)
+}
+
+/// Inserts `value` at `path`, creating the objects along the way.
+fn insert(object: &mut Map<String, Value>, path: &[String], value: Value) {
+    match path {
+        [] => {}
+        [key] => {
+            object.insert(key.clone(), value);
+        }
+        [key, rest @ ..] => {
+            let child = object
+                .entry(key.clone())
+                .or_insert_with(|| Value::Object(Map::new()));
+            if let Value::Object(child) = child {
+                insert(child, rest, value);
+            }
+        }
+    }
+}
+
+/// Converts a CSV value to JSON using its column's inferred type, or to a string if types are not inferred.
+fn convert(value: &str, column: Option<&InferredColumn>) -> Value {
+    let Some(column) = column else {
+        return Value::String(value.to_string());
+    };
+    if value.is_empty() {
+        return Value::Null;
+    }
+    let converted = match column.column_type {
+        ColumnType::Boolean => Some(Value::Bool(value.eq_ignore_ascii_case("true"))),
+        ColumnType::Integer => value.parse::<i64>().ok().map(Value::from),
+        ColumnType::Float => value
+            .parse::<f64>()
+            .ok()
+            .and_then(Number::from_f64)
+            .map(Value::Number),
+        ColumnType::String => None,
+    };
+    converted.unwrap_or_else(|| Value::String(value.to_string()))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn write_json<W: Write>(
+    file_path: Option<&Path>,
+    file_data: Option<&str>,
+    format: JsonFormat,
+    infer_types: bool,
+    nested: bool,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+    mut wtr: W,
+) -> Result<Report<()>> {
+    let rdr = ReaderBuilder::new();
+    let columns = if infer_types {
+        let mut rdr = reader_from(&rdr, file_path, file_data, encoding)?;
+        Some(infer_columns(&mut rdr, error_policy)?)
+    } else {
+        None
+    };
+
+    let mut rdr = reader_from(&rdr, file_path, file_data, encoding)?;
+    let paths = key_paths(rdr.headers()?, nested)?;
+    let mut skipper = Skipper::new(error_policy);
+    let mut record = StringRecord::new();
+    let mut first = true;
+
+    if format == JsonFormat::Array {
+        wtr.write_all(b"[")?;
+    }
+    while skipper.read_record(&mut rdr, &mut record)? {
+        let mut object = Map::new();
+        for (i, (value, path)) in record.iter().zip(&paths).enumerate() {
+            let column = columns.as_ref().map(|columns| &columns[i]);
+            insert(&mut object, path, convert(value, column));
+        }
+        match format {
+            JsonFormat::Array if !first => wtr.write_all(b",")?,
+            _ => {}
+        }
+        serde_json::to_writer(&mut wtr, &object)?;
+        if format == JsonFormat::Lines {
+            wtr.write_all(b"\n")?;
+        }
+        first = false;
+    }
+    if format == JsonFormat::Array {
+        wtr.write_all(b"]\n")?;
+    }
+    wtr.flush()?;
+
+    Ok(skipper.finish(()))
+}
+
+/// Returns the CSV data converted to JSON. Recommended alternative: [`czv::json::ToJson`](crate::json::ToJson).
+///
+/// See [`czv::json::ToJson`](crate::json::ToJson) for a builder version (recommended) of this function.
+///
+/// ## Notes
+///
+/// - Each row becomes a JSON object keyed by header name.
+/// - With `infer_types`, each column's values are converted to the type inferred from the whole
+///   column (number, boolean or string) and empty values become `null`. This reads the input twice.
+///   Otherwise every value is a string.
+/// - With `nested`, dotted header names such as `address.city` become nested objects.
+/// - Malformed records are left out of the output when skipped with `error_policy`.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+///
+/// ## Arguments
+///
+/// * `file_path` - CSV file path (alternative to `file_data`).
+/// * `file_data` - CSV file data (alternative to `file_path`).
+/// * `format` - Whether to write a JSON array or JSON Lines.
+/// * `infer_types` - Specify whether to convert values to the inferred type of their column.
+/// * `nested` - Specify whether dotted header names become nested objects.
+/// * `error_policy` - How to handle malformed records.
+/// * `encoding` - The character encoding of `file_path`.
+pub fn to_json(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    format: JsonFormat,
+    infer_types: bool,
+    nested: bool,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<String> {
+    let mut output = vec![];
+    write_json(
+        file_path.as_deref(),
+        file_data.as_deref(),
+        format,
+        infer_types,
+        nested,
+        error_policy,
+        encoding,
+        &mut output,
+    )?;
+    String::from_utf8(output).map_err(|err| CzvError::parse(err.to_string()))
+}
+
+#[derive(TypedBuilder)]
+#[builder(doc, builder_method(name=new, doc="Returns a [`ToJsonBuilder`](crate::json::ToJsonBuilder) to customize JSON conversion options by running other methods before getting the JSON with the [`execute`](crate::json::ToJsonBuilder::execute) method."), build_method(vis="", name=__build))]
+/// Returns the CSV data converted to a JSON array of objects or JSON Lines.
+///
+/// The entry point for setting up a [`czv::json::ToJsonBuilder`](crate::json::ToJsonBuilder) by running [`ToJson::new()`](crate::json::ToJson::new).
+///
+/// # Example
+///
+/// ```rust
+/// use czv::{Result, ToJson};
+/// use czv::json::JsonFormat;
+///
+/// fn main() -> Result<()> {
+///     let data = "\
+/// fruit,price.amount,price.currency
+/// apple,2.50,USD
+/// banana,,USD
+/// ";
+///     let output = ToJson::new()
+///         .file_data(data)
+///         .format(JsonFormat::Lines)
+///         .infer_types(true)
+///         .nested(true)
+///         .execute()?;
+///     assert_eq!(
+///         output,
+///         r#"{"fruit":"apple","price":{"amount":2.5,"currency":"USD"}}
+/// {"fruit":"banana","price":{"amount":null,"currency":"USD"}}
+/// "#
+///     );
+///     Ok(())
+/// }
+/// ```
+///
+/// For large files use [`execute_to_writer`](crate::json::ToJsonBuilder::execute_to_writer) to
+/// stream the output instead of holding it in memory.
+///
+/// ## Notes
+///
+/// - See [`to_json`](crate::json::to_json) for how values are converted.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+pub struct ToJson {
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file path (alternative to `file_data`).",
+            strip_option(fallback = file_path_opt),
+            into
+        )
+    )]
+    file_path: Option<PathBuf>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file data (alternative to `file_path`).",
+            strip_option(fallback = file_data_opt),
+            into
+        )
+    )]
+    file_data: Option<String>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "Whether to write a JSON array or JSON Lines. Defaults to `JsonFormat::Array`."
+        )
+    )]
+    format: JsonFormat,
+
+    #[builder(
+        default = false,
+        setter(
+            doc = "Specify whether to convert values to the inferred type of their column. Defaults to false."
+        )
+    )]
+    infer_types: bool,
+
+    #[builder(
+        default = false,
+        setter(
+            doc = "Specify whether dotted header names become nested objects. Defaults to false."
+        )
+    )]
+    nested: bool,
+
+    #[builder(
+        default,
+        setter(doc = "How to handle malformed records. Defaults to `ErrorPolicy::Fail`.")
+    )]
+    error_policy: ErrorPolicy,
+
+    #[builder(
+        default,
+        setter(doc = "The character encoding of `file_path`. Defaults to `Encoding::Utf8`.")
+    )]
+    encoding: Encoding,
+}
+
+#[allow(non_camel_case_types)]
+impl<
+        __encoding: typed_builder::Optional<Encoding>,
+        __error_policy: typed_builder::Optional<ErrorPolicy>,
+        __nested: typed_builder::Optional<bool>,
+        __infer_types: typed_builder::Optional<bool>,
+        __format: typed_builder::Optional<JsonFormat>,
+        __file_data: typed_builder::Optional<Option<String>>,
+        __file_path: typed_builder::Optional<Option<PathBuf>>,
+    >
+    ToJsonBuilder<(
+        __file_path,
+        __file_data,
+        __format,
+        __infer_types,
+        __nested,
+        __error_policy,
+        __encoding,
+    )>
+{
+    /// Returns the JSON output.
+    pub fn execute(self) -> Result<String> {
+        Ok(self.execute_with_report()?.output)
+    }
+
+    /// Returns the JSON output along with the records skipped with `ErrorPolicy::Collect`.
+    pub fn execute_with_report(self) -> Result<Report<String>> {
+        let mut output = vec![];
+        let report = self.execute_to_writer(&mut output)?;
+        let output = String::from_utf8(output).map_err(|err| CzvError::parse(err.to_string()))?;
+        Ok(Report {
+            output,
+            skipped: report.skipped,
+        })
+    }
+
+    /// Writes the JSON output to `wtr` as each row is converted, returning the records skipped with `ErrorPolicy::Collect`.
+    pub fn execute_to_writer<W: Write>(self, wtr: W) -> Result<Report<()>> {
+        let builder = self.__build();
+        write_json(
+            builder.file_path.as_deref(),
+            builder.file_data.as_deref(),
+            builder.format,
+            builder.infer_types,
+            builder.nested,
+            builder.error_policy,
+            builder.encoding,
+            wtr,
+        )
+    }
+}
+
+/// How [`FromJson`](crate::json::FromJson) writes JSON arrays to CSV.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ArrayMode {
+    /// Join the array's elements into a single value with the separator. This is the default.
+    #[default]
+    Join,
+    /// Write one row per element, repeating the record's other values.
+    Explode,
+}
+
+/// The `(column, value)` pairs of one output row.
+type Row = Vec<(String, String)>;
+
+/// Returns the CSV value of a JSON scalar, or the JSON text of an object or array.
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(value) => value.clone(),
+        value => value.to_string(),
+    }
+}
+
+fn key(prefix: &str, name: &str) -> String {
+    match prefix {
+        "" => name.to_string(),
+        prefix => format!("{prefix}.{name}"),
+    }
+}
+
+/// Returns the rows `value` is flattened into, which is more than one row when arrays are exploded.
+fn flatten(prefix: &str, value: &Value, arrays: ArrayMode, separator: &str) -> Vec<Row> {
+    match value {
+        Value::Object(object) if !object.is_empty() => {
+            let mut rows = vec![vec![]];
+            for (name, value) in object {
+                let children = flatten(&key(prefix, name), value, arrays, separator);
+                rows = rows
+                    .iter()
+                    .flat_map(|row| {
+                        children.iter().map(move |child| {
+                            let mut row = row.clone();
+                            row.extend(child.iter().cloned());
+                            row
+                        })
+                    })
+                    .collect();
+            }
+            rows
+        }
+        Value::Array(elements) if arrays == ArrayMode::Explode && !elements.is_empty() => elements
+            .iter()
+            .flat_map(|element| flatten(prefix, element, arrays, separator))
+            .collect(),
+        Value::Array(elements) if arrays == ArrayMode::Join => {
+            let joined = elements
+                .iter()
+                .map(scalar)
+                .collect::<Vec<_>>()
+                .join(separator);
+            vec![vec![(prefix.to_string(), joined)]]
+        }
+        // An empty record has no values rather than a value for a column without a name.
+        Value::Object(_) if prefix.is_empty() => vec![vec![]],
+        Value::Object(_) | Value::Array(_) => vec![vec![(prefix.to_string(), String::new())]],
+        value => vec![vec![(prefix.to_string(), scalar(value))]],
+    }
+}
+
+/// A record read from JSON data.
+struct JsonRecord {
+    value: Value,
+    /// The record index (0-indexed).
+    record: u64,
+    /// The line and byte offset where the record starts, only known for JSON Lines.
+    position: Option<(u64, u64)>,
+}
+
+/// Returns the records of a JSON array or JSON Lines.
+///
+/// Records that are not valid JSON are passed to `skipper`.
+fn read_records(data: &str, skipper: &mut Skipper) -> Result<Vec<JsonRecord>> {
+    if data.trim_start().starts_with('[') {
+        let records: Vec<Value> = serde_json::from_str(data)?;
+        return Ok(records
+            .into_iter()
+            .zip(0..)
+            .map(|(value, record)| JsonRecord {
+                value,
+                record,
+                position: None,
+            })
+            .collect());
+    }
+    let mut records = vec![];
+    let mut byte = 0;
+    let mut index = 0;
+    for (text, line) in data.split_inclusive('\n').zip(1..) {
+        if !text.trim().is_empty() {
+            match serde_json::from_str(text) {
+                Ok(value) => records.push(JsonRecord {
+                    value,
+                    record: index,
+                    position: Some((line, byte)),
+                }),
+                Err(err) => skipper.skip(CzvError::Parse {
+                    message: err.to_string(),
+                    line: Some(line),
+                    byte: Some(byte),
+                    record: Some(index),
+                })?,
+            }
+            index += 1;
+        }
+        byte += text.len() as u64;
+    }
+    Ok(records)
+}
+
+/// Returns CSV data converted from a JSON array of objects or JSON Lines. Recommended alternative: [`czv::json::FromJson`](crate::json::FromJson).
+///
+/// See [`czv::json::FromJson`](crate::json::FromJson) for a builder version (recommended) of this function.
+///
+/// ## Notes
+///
+/// - The input is read as a JSON array if it starts with `[`, otherwise as JSON Lines.
+/// - Nested objects are flattened into dotted column names such as `address.city`.
+/// - The header is the union of every record's keys, in the order they first appear.
+///   Values missing from a record are empty, as are `null` values.
+/// - Arrays are joined with `separator`, or exploded into one row per element with `ArrayMode::Explode`.
+///   Exploding several arrays in one record writes a row for every combination of their elements.
+/// - Records that are not JSON objects, records with keys that flatten to the same column name such as `{"a": {"b": 1}, "a.b": 2}`,
+///   and JSON Lines records that are not valid JSON, are malformed.
+///   They are left out of the output when skipped with `error_policy`, which reports records as 0-indexed positions in the JSON data.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+///
+/// ## Arguments
+///
+/// * `file_path` - JSON file path (alternative to `file_data`).
+/// * `file_data` - JSON file data (alternative to `file_path`).
+/// * `arrays` - Whether to join or explode arrays.
+/// * `separator` - The separator for joined array elements.
+/// * `error_policy` - How to handle malformed records.
+/// * `encoding` - The character encoding of `file_path`.
+pub fn from_json(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    arrays: ArrayMode,
+    separator: String,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<String> {
+    Ok(from_json_with_report(
+        file_path,
+        file_data,
+        arrays,
+        separator,
+        error_policy,
+        encoding,
+    )?
+    .output)
+}
+
+fn from_json_with_report(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    arrays: ArrayMode,
+    separator: String,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<Report<String>> {
+    let mut data = String::new();
+    source_from(file_path.as_deref(), file_data.as_deref(), encoding)?.read_to_string(&mut data)?;
+
+    let mut skipper = Skipper::new(error_policy);
+    let mut headers: Vec<String> = vec![];
+    let mut columns: HashMap<String, usize> = HashMap::new();
+    let mut rows = vec![];
+    for record in read_records(&data, &mut skipper)? {
+        let malformed = |message: String| CzvError::Parse {
+            message,
+            line: record.position.map(|(line, _)| line),
+            byte: record.position.map(|(_, byte)| byte),
+            record: Some(record.record),
+        };
+        if !record.value.is_object() {
+            skipper.skip(malformed(format!(
+                "Record {} is not a JSON object.",
+                record.record
+            )))?;
+            continue;
+        }
+        let record_rows = flatten("", &record.value, arrays, &separator);
+        // Keys such as `{"a": {"b": 1}}` and `"a.b"` flatten to the same column.
+        let collision = record_rows.iter().find_map(|row| {
+            let mut names = HashSet::new();
+            row.iter()
+                .find(|(name, _)| !names.insert(name.as_str()))
+                .map(|(name, _)| name.clone())
+        });
+        if let Some(name) = collision {
+            skipper.skip(malformed(format!(
+                "Record {} has more than one value for column {name:?}.",
+                record.record
+            )))?;
+            continue;
+        }
+        for row in record_rows {
+            let row = row
+                .into_iter()
+                .map(|(name, value)| {
+                    let column = *columns.entry(name).or_insert_with_key(|name| {
+                        headers.push(name.clone());
+                        headers.len() - 1
+                    });
+                    (column, value)
+                })
+                .collect::<Vec<_>>();
+            rows.push(row);
+        }
+    }
+
+    let mut wtr = writer();
+    if !headers.is_empty() {
+        wtr.write_record(&headers)?;
+    }
+    for row in rows {
+        let mut output_row = vec![""; headers.len()];
+        for (column, value) in &row {
+            output_row[*column] = value;
+        }
+        wtr.write_record(output_row)?;
+    }
+
+    Ok(skipper.finish(into_string(wtr)?))
+}
+
+#[derive(TypedBuilder)]
+#[builder(doc, builder_method(name=new, doc="Returns a [`FromJsonBuilder`](crate::json::FromJsonBuilder) to customize JSON conversion options by running other methods before getting the CSV with the [`execute`](crate::json::FromJsonBuilder::execute) method."), build_method(vis="", name=__build))]
+/// Returns CSV data converted from a JSON array of objects or JSON Lines.
+///
+/// The entry point for setting up a [`czv::json::FromJsonBuilder`](crate::json::FromJsonBuilder) by running [`FromJson::new()`](crate::json::FromJson::new).
+///
+/// # Example
+///
+/// ```rust
+/// use czv::{FromJson, Result};
+/// use czv::json::ArrayMode;
+///
+/// fn main() -> Result<()> {
+///     let data = r#"[
+///         {"fruit": "apple", "price": {"amount": 2.5}, "tags": ["red", "sweet"]},
+///         {"fruit": "lemon", "stock": 4, "tags": []}
+///     ]"#;
+///     let output = FromJson::new().file_data(data).separator(";").execute()?;
+///     assert_eq!(
+///         output,
+///         "fruit,price.amount,tags,stock\napple,2.5,red;sweet,\nlemon,,,4\n"
+///     );
+///
+///     let output = FromJson::new()
+///         .file_data(data)
+///         .arrays(ArrayMode::Explode)
+///         .execute()?;
+///     assert_eq!(
+///         output,
+///         "fruit,price.amount,tags,stock\napple,2.5,red,\napple,2.5,sweet,\nlemon,,,4\n"
+///     );
+///     Ok(())
+/// }
+/// ```
+///
+/// ## Notes
+///
+/// - See [`from_json`](crate::json::from_json) for how records are flattened.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+pub struct FromJson {
+    #[builder(
+        default,
+        setter(
+            doc = "JSON file path (alternative to `file_data`).",
+            strip_option(fallback = file_path_opt),
+            into
+        )
+    )]
+    file_path: Option<PathBuf>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "JSON file data (alternative to `file_path`).",
+            strip_option(fallback = file_data_opt),
+            into
+        )
+    )]
+    file_data: Option<String>,
+
+    #[builder(
+        default,
+        setter(doc = "Whether to join or explode arrays. Defaults to `ArrayMode::Join`.")
+    )]
+    arrays: ArrayMode,
+
+    #[builder(
+        default = ",".to_string(),
+        setter(doc = "The separator for joined array elements. Defaults to `,`.", into)
+    )]
+    separator: String,
+
+    #[builder(
+        default,
+        setter(doc = "How to handle malformed records. Defaults to `ErrorPolicy::Fail`.")
+    )]
+    error_policy: ErrorPolicy,
+
+    #[builder(
+        default,
+        setter(doc = "The character encoding of `file_path`. Defaults to `Encoding::Utf8`.")
+    )]
+    encoding: Encoding,
+}
+
+#[allow(non_camel_case_types)]
+impl<
+        __encoding: typed_builder::Optional<Encoding>,
+        __error_policy: typed_builder::Optional<ErrorPolicy>,
+        __separator: typed_builder::Optional<String>,
+        __arrays: typed_builder::Optional<ArrayMode>,
+        __file_data: typed_builder::Optional<Option<String>>,
+        __file_path: typed_builder::Optional<Option<PathBuf>>,
+    >
+    FromJsonBuilder<(
+        __file_path,
+        __file_data,
+        __arrays,
+        __separator,
+        __error_policy,
+        __encoding,
+    )>
+{
+    /// Returns the CSV output.
+    pub fn execute(self) -> Result<String> {
+        Ok(self.execute_with_report()?.output)
+    }
+
+    /// Returns the CSV output along with the records skipped with `ErrorPolicy::Collect`.
+    pub fn execute_with_report(self) -> Result<Report<String>> {
+        let builder = self.__build();
+        from_json_with_report(
+            builder.file_path,
+            builder.file_data,
+            builder.arrays,
+            builder.separator,
+            builder.error_policy,
+            builder.encoding,
+        )
+    }
+}
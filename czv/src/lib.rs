@@ -41,49 +41,269 @@
 pub mod count;
 #[doc(inline)]
 pub use count::{ColumnCount, RowCount};
-// Extract a section of rows.
-// pub mod slice;
-// #[doc(inline)]
-// pub use slice::Slice;
+/// Swap the rows and columns of CSV data.
+pub mod transpose;
+#[doc(inline)]
+pub use transpose::Transpose;
+/// Check CSV data for structural problems.
+pub mod validate;
+#[doc(inline)]
+pub use validate::Validate;
+/// Validate rows against a JSON Schema and generate starter schemas.
+pub mod schema;
+#[doc(inline)]
+pub use schema::{SchemaGenerate, SchemaValidate};
+/// Column type inference.
+pub mod infer;
+/// Error policies for handling malformed records.
+pub mod policy;
+#[doc(inline)]
+pub use policy::ErrorPolicy;
+/// Detect character encodings and transcode CSV data to UTF-8.
+pub mod encoding;
+#[doc(inline)]
+pub use encoding::{Encoding, Input};
+/// Extract a section of rows.
+pub mod slice;
+#[doc(inline)]
+pub use slice::Slice;
+/// Convert between CSV and JSON.
+pub mod json;
+#[doc(inline)]
+pub use json::{FromJson, ToJson};
+/// Convert between CSV and fixed-width text.
+pub mod fixed_width;
+#[doc(inline)]
+pub use fixed_width::{FromFixedWidth, ToFixedWidth};
+/// Render as an aligned text, Markdown or HTML table.
+pub mod table;
+#[doc(inline)]
+pub use table::Table;
+/// Generate SQL scripts that create a table and insert the rows.
+pub mod sql;
+#[doc(inline)]
+pub use sql::ToSql;
+/// Group rows by key columns and aggregate the other columns.
+pub mod aggregate;
+#[doc(inline)]
+pub use aggregate::Aggregate;
+/// Reshape CSV data between long and wide layouts.
+pub mod pivot;
+#[doc(inline)]
+pub use pivot::{Pivot, Unpivot};
+/// Transform column values with built-in operations.
+pub mod apply;
+#[doc(inline)]
+pub use apply::Apply;
+/// Fill empty values from other rows or with a constant.
+pub mod fill;
+#[doc(inline)]
+pub use fill::Fill;
+/// Split multi-valued fields into rows and join them back.
+pub mod explode;
+#[doc(inline)]
+pub use explode::{Explode, Implode};
+/// Add a column of row indexes, UUIDs, constants, copies, row hashes or file names.
+pub mod enumerate;
+#[doc(inline)]
+pub use enumerate::Enumerate;
+/// Pad or truncate ragged rows to the same length.
+pub mod fix_lengths;
+#[doc(inline)]
+pub use fix_lengths::FixLengths;
+/// Load CSV data into a SQLite database.
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+#[cfg(feature = "sqlite")]
+#[doc(inline)]
+pub use sqlite::ToSqlite;
+/// Run SQL queries over CSV data.
+#[cfg(feature = "sqlite")]
+pub mod query;
+#[cfg(feature = "sqlite")]
+#[doc(inline)]
+pub use query::Query;
+/// Transform rows with Rhai scripts.
+#[cfg(feature = "rhai")]
+pub mod map;
+#[cfg(feature = "rhai")]
+#[doc(inline)]
+pub use map::Map;
+/// Convert between CSV and Apache Arrow record batches.
+#[cfg(feature = "arrow")]
+pub mod arrow;
+#[cfg(feature = "arrow")]
+#[doc(inline)]
+pub use arrow::{FromArrow, ToArrow};
+/// Convert between CSV and Parquet.
+#[cfg(feature = "parquet")]
+pub mod parquet;
+#[cfg(feature = "parquet")]
+#[doc(inline)]
+pub use parquet::{FromParquet, ToParquet};
+/// Convert Excel and OpenDocument spreadsheets to CSV.
+#[cfg(feature = "excel")]
+pub mod excel;
+#[cfg(feature = "excel")]
+#[doc(inline)]
+pub use excel::{ExcelSheets, FromExcel};
+
+// Shared reader and writer helpers
+mod util;
 
 // Error-handling helpers
-#[allow(dead_code)]
 #[derive(thiserror::Error, Debug)]
-#[error("{0}")]
-/// Common catch-all error type based on [anyhow::Error].
-pub struct CzvError(pub anyhow::Error);
+#[non_exhaustive]
+/// Common error type returned by every operation.
+///
+/// Match on the variant to tell apart problems with the input source, the CSV data itself, and the
+/// options passed to an operation.
+pub enum CzvError {
+    /// Reading or writing a file failed, e.g. the file does not exist.
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+
+    /// The input data is malformed.
+    ///
+    /// The position is provided when it is known, e.g. for CSV data with an unexpected number of fields.
+    #[error("{message}")]
+    Parse {
+        /// A description of the problem.
+        message: String,
+        /// The line the problem was found on (1-indexed).
+        line: Option<u64>,
+        /// The byte offset from the start of the data where the problem was found.
+        byte: Option<u64>,
+        /// The record index (0-indexed, the header row is record 0).
+        record: Option<u64>,
+    },
+
+    /// The options passed to an operation are missing or cannot be used together.
+    #[error("{0}")]
+    InvalidOptions(String),
+
+    /// A column selected by name does not exist in the header row.
+    #[error("Column not found: {0}")]
+    ColumnNotFound(String),
+
+    /// Any other error.
+    #[error("{0}")]
+    Other(#[from] anyhow::Error),
+}
 
-impl From<anyhow::Error> for CzvError {
-    fn from(value: anyhow::Error) -> Self {
-        Self(value)
+impl CzvError {
+    /// Returns a [`CzvError::Parse`] without a known position.
+    pub(crate) fn parse(message: impl Into<String>) -> Self {
+        Self::Parse {
+            message: message.into(),
+            line: None,
+            byte: None,
+            record: None,
+        }
+    }
+
+    /// Returns a [`CzvError::Parse`] at the position of `record`.
+    pub(crate) fn parse_at(message: impl Into<String>, record: &csv::StringRecord) -> Self {
+        let position = record.position();
+        Self::Parse {
+            message: message.into(),
+            line: position.map(|pos| pos.line()),
+            byte: position.map(|pos| pos.byte()),
+            record: position.map(|pos| pos.record()),
+        }
     }
 }
 
 impl From<csv::Error> for CzvError {
     fn from(value: csv::Error) -> Self {
-        Self(anyhow::anyhow!(value))
+        let message = value.to_string();
+        let position = value.position().cloned();
+        match value.into_kind() {
+            csv::ErrorKind::Io(err) => Self::Io(err),
+            _ => Self::Parse {
+                message,
+                line: position.as_ref().map(|pos| pos.line()),
+                byte: position.as_ref().map(|pos| pos.byte()),
+                record: position.as_ref().map(|pos| pos.record()),
+            },
+        }
+    }
+}
+
+impl From<serde_json::Error> for CzvError {
+    fn from(value: serde_json::Error) -> Self {
+        if value.is_io() {
+            return Self::Io(value.into());
+        }
+        Self::Parse {
+            message: value.to_string(),
+            line: Some(value.line() as u64),
+            byte: None,
+            record: None,
+        }
+    }
+}
+
+#[cfg(feature = "parquet")]
+impl From<::parquet::errors::ParquetError> for CzvError {
+    fn from(value: ::parquet::errors::ParquetError) -> Self {
+        match value {
+            ::parquet::errors::ParquetError::External(err) => {
+                match err.downcast::<std::io::Error>() {
+                    Ok(err) => Self::Io(*err),
+                    Err(err) => Self::parse(err.to_string()),
+                }
+            }
+            err => Self::parse(err.to_string()),
+        }
+    }
+}
+
+#[cfg(feature = "arrow")]
+impl From<::arrow::error::ArrowError> for CzvError {
+    fn from(value: ::arrow::error::ArrowError) -> Self {
+        match value {
+            ::arrow::error::ArrowError::IoError(_, err) => Self::Io(err),
+            err => Self::parse(err.to_string()),
+        }
+    }
+}
+
+#[cfg(feature = "excel")]
+impl From<calamine::Error> for CzvError {
+    fn from(value: calamine::Error) -> Self {
+        match value {
+            calamine::Error::Io(err) => Self::Io(err),
+            err => Self::parse(err.to_string()),
+        }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl From<rusqlite::Error> for CzvError {
+    fn from(value: rusqlite::Error) -> Self {
+        Self::Other(value.into())
     }
 }
 
-/// Common Result type based on [anyhow::Result] and [czv::CzvError](crate::CzvError).
-pub type Result<T, E = CzvError> = anyhow::Result<T, E>;
+/// Common Result type based on [czv::CzvError](crate::CzvError).
+pub type Result<T, E = CzvError> = std::result::Result<T, E>;
 
 #[macro_export]
 #[allow(unused_macros)]
-/// Function-like macro you may pass a `&str` to return a [czv::CzvError](crate::CzvError).
+/// Function-like macro you may pass a `&str` to return a [czv::CzvError::Other](crate::CzvError::Other).
 ///
 /// For example:
 ///
 /// ```should_panic
-/// use czv::{bail, CzvError, Result};
+/// use czv::{bail, Result};
 ///
 /// fn main() -> Result<()> {
 ///     bail!("This is an example of an error message using `bail!`.");
 /// }
 /// ```
-#[allow(clippy::crate_in_macro_def)]
 macro_rules! bail {
     ($err:expr $(,)?) => {
-        return Err(crate::CzvError(anyhow::anyhow!($err)))
+        return Err($crate::CzvError::Other(anyhow::anyhow!($err)))
     };
 }
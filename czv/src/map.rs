@@ -0,0 +1,340 @@
+use crate::encoding::Encoding;
+use crate::policy::{ErrorPolicy, Report, Skipper};
+use crate::util::{into_string, reader_from, writer};
+use crate::{CzvError, Result};
+use csv::{ReaderBuilder, StringRecord};
+use rhai::{Dynamic, Engine, Scope};
+use std::path::PathBuf;
+use typed_builder::TypedBuilder;
+
+/// The most nested function calls a script may make, so that runaway recursion fails the row.
+const MAX_CALL_LEVELS: usize = 64;
+
+/// Returns the text written for a value set by a script, with `()` written as an empty value.
+fn value_string(value: &Dynamic) -> String {
+    if value.is_unit() {
+        String::new()
+    } else {
+        value.to_string()
+    }
+}
+
+/// Returns the CSV data with each row passed through a [Rhai](https://rhai.rs) script. Recommended alternative: [`czv::map::Map`](crate::map::Map).
+///
+/// See [`czv::map::Map`](crate::map::Map) for a builder version (recommended) of this function.
+///
+/// ## Notes
+///
+/// - The script runs once per row with the row's fields as strings in an object map named `row`,
+///   e.g. `row.price` or `row["unit price"]`. Use `parse_int` and `parse_float` to convert them.
+/// - Fields assigned to `row` replace the values of existing columns or fill `new_columns`. `()`
+///   is written as an empty value, and new columns that are not assigned are left empty.
+/// - The row is dropped if the script evaluates to `false`.
+/// - Variables declared by `begin`, which runs once before the first row, keep their values across
+///   rows, as do the functions it defines. Variables declared by `script` only last for one row.
+/// - Each run of `script` or `begin` may perform at most `max_operations` operations, or any number
+///   if it is `0`, and nest at most 64 function calls, so that a runaway script fails instead of
+///   hanging. A row that exceeds either limit is a malformed record for `error_policy`.
+/// - `print` and `debug` output is discarded unless `print` is `true`, when it is written to
+///   stderr so that it does not mix with the output.
+/// - A row for which the script fails, e.g. with `throw`, is a malformed record for `error_policy`.
+/// - With duplicate header names, the value of the last of the columns is written for all of them.
+///
+/// ## Arguments
+///
+/// * `file_path` - CSV file path (alternative to `file_data`).
+/// * `file_data` - CSV file data (alternative to `file_path`).
+/// * `script` - The Rhai script run for each row.
+/// * `begin` - A Rhai script run once before the first row.
+/// * `new_columns` - The header names of the columns added by the script.
+/// * `max_operations` - The most operations each run of a script may perform, or `0` for no limit.
+/// * `print` - Whether to write the script's `print` and `debug` output to stderr.
+/// * `error_policy` - How to handle malformed records.
+/// * `encoding` - The character encoding of `file_path`.
+#[allow(clippy::too_many_arguments)]
+pub fn map(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    script: Option<String>,
+    begin: Option<String>,
+    new_columns: Vec<String>,
+    max_operations: u64,
+    print: bool,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<String> {
+    Ok(map_with_report(
+        file_path,
+        file_data,
+        script,
+        begin,
+        new_columns,
+        max_operations,
+        print,
+        error_policy,
+        encoding,
+    )?
+    .output)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn map_with_report(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    script: Option<String>,
+    begin: Option<String>,
+    new_columns: Vec<String>,
+    max_operations: u64,
+    print: bool,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<Report<String>> {
+    let script =
+        script.ok_or_else(|| CzvError::InvalidOptions("Must provide script.".to_string()))?;
+    let mut engine = Engine::new();
+    engine.set_max_operations(max_operations);
+    engine.set_max_call_levels(MAX_CALL_LEVELS);
+    if print {
+        engine.on_print(|text| eprintln!("{text}"));
+        engine.on_debug(|text, _, position| eprintln!("{position:?} {text}"));
+    } else {
+        engine.on_print(|_| {});
+        engine.on_debug(|_, _, _| {});
+    }
+    let mut ast = engine
+        .compile(&script)
+        .map_err(|err| CzvError::InvalidOptions(format!("Invalid script: {err}")))?;
+    let begin = begin
+        .map(|begin| engine.compile(begin))
+        .transpose()
+        .map_err(|err| CzvError::InvalidOptions(format!("Invalid begin script: {err}")))?;
+
+    let rdr = ReaderBuilder::new();
+    let mut rdr = reader_from(&rdr, file_path.as_deref(), file_data.as_deref(), encoding)?;
+    let headers = rdr.headers()?.clone();
+    if let Some(name) = new_columns
+        .iter()
+        .find(|name| headers.iter().any(|header| header == name.as_str()))
+    {
+        return Err(CzvError::InvalidOptions(format!(
+            "New column {name:?} already exists."
+        )));
+    }
+
+    let mut scope = Scope::new();
+    if let Some(begin) = begin {
+        engine
+            .run_ast_with_scope(&mut scope, &begin)
+            .map_err(|err| CzvError::InvalidOptions(format!("Begin script failed: {err}")))?;
+        ast = begin.clone_functions_only().merge(&ast);
+    }
+    let state = scope.len();
+
+    let mut wtr = writer();
+    wtr.write_record(headers.iter().chain(new_columns.iter().map(String::as_str)))?;
+    let mut skipper = Skipper::new(error_policy);
+    let mut record = StringRecord::new();
+    while skipper.read_record(&mut rdr, &mut record)? {
+        let row: rhai::Map = headers
+            .iter()
+            .zip(record.iter())
+            .map(|(header, value)| (header.into(), value.into()))
+            .collect();
+        scope.push("row", row);
+        let result = engine.eval_ast_with_scope::<Dynamic>(&mut scope, &ast);
+        let row = scope.get_value::<rhai::Map>("row");
+        scope.rewind(state);
+
+        let row = match (result, row) {
+            (Ok(value), Some(row)) if value.as_bool().ok() != Some(false) => row,
+            (Ok(_), Some(_)) => continue,
+            (Ok(_), None) => {
+                skipper.skip(CzvError::parse_at(
+                    "Script failed: `row` is no longer an object map.",
+                    &record,
+                ))?;
+                continue;
+            }
+            (Err(err), _) => {
+                skipper.skip(CzvError::parse_at(format!("Script failed: {err}"), &record))?;
+                continue;
+            }
+        };
+        wtr.write_record(
+            headers
+                .iter()
+                .chain(new_columns.iter().map(String::as_str))
+                .map(|name| row.get(name).map(value_string).unwrap_or_default()),
+        )?;
+    }
+    Ok(skipper.finish(into_string(wtr)?))
+}
+
+#[derive(TypedBuilder)]
+#[builder(doc, builder_method(name=new, doc="Returns a [`MapBuilder`](crate::map::MapBuilder) to customize scripting options by running other methods before getting the output with the [`execute`](crate::map::MapBuilder::execute) method."), build_method(vis="", name=__build))]
+/// Returns the CSV data with each row passed through a [Rhai](https://rhai.rs) script.
+///
+/// The entry point for setting up a [`czv::map::MapBuilder`](crate::map::MapBuilder) by running [`Map::new()`](crate::map::Map::new).
+///
+/// # Example
+///
+/// ```rust
+/// use czv::{Map, Result};
+///
+/// fn main() -> Result<()> {
+///     let data = "\
+/// fruit,price,count
+/// apple,2.5,4
+/// banana,3.0,0
+/// cherry,0.5,10
+/// ";
+///     let output = Map::new()
+///         .file_data(data)
+///         .begin("let total = 0.0;")
+///         .script(
+///             "
+///             let value = row.price.parse_float() * row.count.parse_int();
+///             total += value;
+///             row.value = value;
+///             row.total = total;
+///             value > 0.0
+///             ",
+///         )
+///         .new_columns(["value".to_string(), "total".to_string()])
+///         .execute()?;
+///     assert_eq!(
+///         output,
+///         "fruit,price,count,value,total\napple,2.5,4,10.0,10.0\ncherry,0.5,10,5.0,15.0\n"
+///     );
+///     Ok(())
+/// }
+/// ```
+///
+/// ## Notes
+///
+/// - See [`map`](crate::map::map) for what the script may access and how its results are written.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+pub struct Map {
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file path (alternative to `file_data`).",
+            strip_option(fallback = file_path_opt),
+            into
+        )
+    )]
+    file_path: Option<PathBuf>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file data (alternative to `file_path`).",
+            strip_option(fallback = file_data_opt),
+            into
+        )
+    )]
+    file_data: Option<String>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "The Rhai script run for each row, with the row's fields in the `row` object map.",
+            strip_option(fallback = script_opt),
+            into
+        )
+    )]
+    script: Option<String>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "A Rhai script run once before the first row, e.g. to declare variables that keep their values across rows.",
+            strip_option(fallback = begin_opt),
+            into
+        )
+    )]
+    begin: Option<String>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "The header names of the columns added by the script, appended after the existing columns.",
+            transform = |new_columns: impl IntoIterator<Item = String>| new_columns.into_iter().collect()
+        )
+    )]
+    new_columns: Vec<String>,
+
+    #[builder(
+        default = 1_000_000,
+        setter(
+            doc = "The most operations each run of a script may perform, or `0` for no limit. Defaults to 1,000,000."
+        )
+    )]
+    max_operations: u64,
+
+    #[builder(
+        default = false,
+        setter(
+            doc = "Specify whether the script's `print` and `debug` output is written to stderr. Defaults to false."
+        )
+    )]
+    print: bool,
+
+    #[builder(
+        default,
+        setter(doc = "How to handle malformed records. Defaults to `ErrorPolicy::Fail`.")
+    )]
+    error_policy: ErrorPolicy,
+
+    #[builder(
+        default,
+        setter(doc = "The character encoding of `file_path`. Defaults to `Encoding::Utf8`.")
+    )]
+    encoding: Encoding,
+}
+
+#[allow(non_camel_case_types)]
+impl<
+        __encoding: typed_builder::Optional<Encoding>,
+        __error_policy: typed_builder::Optional<ErrorPolicy>,
+        __print: typed_builder::Optional<bool>,
+        __max_operations: typed_builder::Optional<u64>,
+        __new_columns: typed_builder::Optional<Vec<String>>,
+        __begin: typed_builder::Optional<Option<String>>,
+        __script: typed_builder::Optional<Option<String>>,
+        __file_data: typed_builder::Optional<Option<String>>,
+        __file_path: typed_builder::Optional<Option<PathBuf>>,
+    >
+    MapBuilder<(
+        __file_path,
+        __file_data,
+        __script,
+        __begin,
+        __new_columns,
+        __max_operations,
+        __print,
+        __error_policy,
+        __encoding,
+    )>
+{
+    /// Returns the mapped CSV data.
+    pub fn execute(self) -> Result<String> {
+        Ok(self.execute_with_report()?.output)
+    }
+
+    /// Returns the mapped CSV data along with the records skipped with `ErrorPolicy::Collect`.
+    pub fn execute_with_report(self) -> Result<Report<String>> {
+        let builder = self.__build();
+        map_with_report(
+            builder.file_path,
+            builder.file_data,
+            builder.script,
+            builder.begin,
+            builder.new_columns,
+            builder.max_operations,
+            builder.print,
+            builder.error_policy,
+            builder.encoding,
+        )
+    }
+}
@@ -0,0 +1,362 @@
+use crate::arrow::{write_csv, CsvBatches};
+use crate::encoding::Encoding;
+use crate::infer::ColumnType;
+use crate::policy::{ErrorPolicy, Report};
+use crate::{CzvError, Result};
+use ::parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use ::parquet::arrow::ArrowWriter;
+use ::parquet::basic::{BrotliLevel, Compression, GzipLevel, ZstdLevel};
+use ::parquet::file::properties::WriterProperties;
+use arrow::array::RecordBatchReader;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use typed_builder::TypedBuilder;
+
+/// The number of rows converted to Arrow arrays at a time before they are written.
+const BATCH_SIZE: usize = 8192;
+
+/// The compression codec for Parquet column data.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParquetCompression {
+    /// No compression.
+    Uncompressed,
+    /// Snappy compression. This is the default.
+    #[default]
+    Snappy,
+    /// Gzip compression at the default level.
+    Gzip,
+    /// Zstandard compression at the default level.
+    Zstd,
+    /// Brotli compression at the default level.
+    Brotli,
+    /// LZ4 compression (the `LZ4_RAW` codec).
+    Lz4,
+}
+
+impl From<ParquetCompression> for Compression {
+    fn from(value: ParquetCompression) -> Self {
+        match value {
+            ParquetCompression::Uncompressed => Compression::UNCOMPRESSED,
+            ParquetCompression::Snappy => Compression::SNAPPY,
+            ParquetCompression::Gzip => Compression::GZIP(GzipLevel::default()),
+            ParquetCompression::Zstd => Compression::ZSTD(ZstdLevel::default()),
+            ParquetCompression::Brotli => Compression::BROTLI(BrotliLevel::default()),
+            ParquetCompression::Lz4 => Compression::LZ4_RAW,
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn write_parquet<W: Write + Send>(
+    file_path: Option<&Path>,
+    file_data: Option<&str>,
+    column_types: &HashMap<String, ColumnType>,
+    compression: ParquetCompression,
+    row_group_size: usize,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+    wtr: W,
+) -> Result<Report<()>> {
+    if row_group_size == 0 {
+        return Err(CzvError::InvalidOptions(
+            "row_group_size must be greater than 0.".to_string(),
+        ));
+    }
+    let mut reader = CsvBatches::new(
+        file_path,
+        file_data,
+        column_types,
+        BATCH_SIZE,
+        error_policy,
+        encoding,
+    )?;
+    let properties = WriterProperties::builder()
+        .set_compression(compression.into())
+        .set_max_row_group_size(row_group_size)
+        .build();
+    let mut parquet_writer = ArrowWriter::try_new(wtr, reader.schema(), Some(properties))?;
+    while let Some(batch) = reader.next_batch()? {
+        parquet_writer.write(&batch)?;
+    }
+    parquet_writer.close()?;
+
+    Ok(reader.finish(()))
+}
+
+/// Returns the CSV data converted to Parquet. Recommended alternative: [`czv::parquet::ToParquet`](crate::parquet::ToParquet).
+///
+/// See [`czv::parquet::ToParquet`](crate::parquet::ToParquet) for a builder version (recommended) of this function.
+///
+/// ## Notes
+///
+/// - Each column is written as a nullable boolean, 64-bit integer, 64-bit float or string column.
+///   The type comes from `column_types` if the column is named there, otherwise it is inferred from
+///   the whole column, which reads the input twice.
+/// - Empty values are written as null, except in string columns where they stay empty strings.
+/// - Records with a value that does not match its column type are malformed. They are left out of
+///   the output when skipped with `error_policy`.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+///
+/// ## Arguments
+///
+/// * `file_path` - CSV file path (alternative to `file_data`).
+/// * `file_data` - CSV file data (alternative to `file_path`).
+/// * `column_types` - Column types by header name, overriding the inferred types.
+/// * `compression` - The compression codec for column data.
+/// * `row_group_size` - The maximum number of rows in a row group.
+/// * `error_policy` - How to handle malformed records.
+/// * `encoding` - The character encoding of `file_path`.
+pub fn to_parquet(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    column_types: HashMap<String, ColumnType>,
+    compression: ParquetCompression,
+    row_group_size: usize,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<Vec<u8>> {
+    let mut output = vec![];
+    write_parquet(
+        file_path.as_deref(),
+        file_data.as_deref(),
+        &column_types,
+        compression,
+        row_group_size,
+        error_policy,
+        encoding,
+        &mut output,
+    )?;
+    Ok(output)
+}
+
+#[derive(TypedBuilder)]
+#[builder(doc, builder_method(name=new, doc="Returns a [`ToParquetBuilder`](crate::parquet::ToParquetBuilder) to customize Parquet conversion options by running other methods before getting the Parquet data with the [`execute`](crate::parquet::ToParquetBuilder::execute) method."), build_method(vis="", name=__build))]
+/// Returns the CSV data converted to Parquet.
+///
+/// The entry point for setting up a [`czv::parquet::ToParquetBuilder`](crate::parquet::ToParquetBuilder) by running [`ToParquet::new()`](crate::parquet::ToParquet::new).
+///
+/// # Example
+///
+/// ```rust
+/// use czv::infer::ColumnType;
+/// use czv::parquet::ParquetCompression;
+/// use czv::{FromParquet, Result, ToParquet};
+///
+/// fn main() -> Result<()> {
+///     let data = "\
+/// zip,price
+/// 02134,2.50
+/// 10001,3.00
+/// ";
+///     let output: Vec<u8> = ToParquet::new()
+///         .file_data(data)
+///         .column_types([("zip".to_string(), ColumnType::String)])
+///         .compression(ParquetCompression::Zstd)
+///         .execute()?;
+///
+///     let csv = FromParquet::new().file_data(output).execute()?;
+///     assert_eq!(csv, "zip,price\n02134,2.5\n10001,3.0\n");
+///     Ok(())
+/// }
+/// ```
+///
+/// Use [`execute_to_writer`](crate::parquet::ToParquetBuilder::execute_to_writer) to write to a
+/// file as each batch of rows is converted.
+///
+/// ## Notes
+///
+/// - See [`to_parquet`](crate::parquet::to_parquet) for how column types are chosen.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+pub struct ToParquet {
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file path (alternative to `file_data`).",
+            strip_option(fallback = file_path_opt),
+            into
+        )
+    )]
+    file_path: Option<PathBuf>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file data (alternative to `file_path`).",
+            strip_option(fallback = file_data_opt),
+            into
+        )
+    )]
+    file_data: Option<String>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "Column types by header name, overriding the inferred types.",
+            transform = |column_types: impl IntoIterator<Item = (String, ColumnType)>| column_types.into_iter().collect()
+        )
+    )]
+    column_types: HashMap<String, ColumnType>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "The compression codec for column data. Defaults to `ParquetCompression::Snappy`."
+        )
+    )]
+    compression: ParquetCompression,
+
+    #[builder(
+        default = 1024 * 1024,
+        setter(doc = "The maximum number of rows in a row group. Defaults to 1048576.")
+    )]
+    row_group_size: usize,
+
+    #[builder(
+        default,
+        setter(doc = "How to handle malformed records. Defaults to `ErrorPolicy::Fail`.")
+    )]
+    error_policy: ErrorPolicy,
+
+    #[builder(
+        default,
+        setter(doc = "The character encoding of `file_path`. Defaults to `Encoding::Utf8`.")
+    )]
+    encoding: Encoding,
+}
+
+#[allow(non_camel_case_types)]
+impl<
+        __encoding: typed_builder::Optional<Encoding>,
+        __error_policy: typed_builder::Optional<ErrorPolicy>,
+        __row_group_size: typed_builder::Optional<usize>,
+        __compression: typed_builder::Optional<ParquetCompression>,
+        __column_types: typed_builder::Optional<HashMap<String, ColumnType>>,
+        __file_data: typed_builder::Optional<Option<String>>,
+        __file_path: typed_builder::Optional<Option<PathBuf>>,
+    >
+    ToParquetBuilder<(
+        __file_path,
+        __file_data,
+        __column_types,
+        __compression,
+        __row_group_size,
+        __error_policy,
+        __encoding,
+    )>
+{
+    /// Returns the Parquet data.
+    pub fn execute(self) -> Result<Vec<u8>> {
+        Ok(self.execute_with_report()?.output)
+    }
+
+    /// Returns the Parquet data along with the records skipped with `ErrorPolicy::Collect`.
+    pub fn execute_with_report(self) -> Result<Report<Vec<u8>>> {
+        let mut output = vec![];
+        let report = self.execute_to_writer(&mut output)?;
+        Ok(Report {
+            output,
+            skipped: report.skipped,
+        })
+    }
+
+    /// Writes the Parquet data to `wtr`, returning the records skipped with `ErrorPolicy::Collect`.
+    pub fn execute_to_writer<W: Write + Send>(self, wtr: W) -> Result<Report<()>> {
+        let builder = self.__build();
+        write_parquet(
+            builder.file_path.as_deref(),
+            builder.file_data.as_deref(),
+            &builder.column_types,
+            builder.compression,
+            builder.row_group_size,
+            builder.error_policy,
+            builder.encoding,
+            wtr,
+        )
+    }
+}
+
+/// Returns CSV data converted from Parquet. Recommended alternative: [`czv::parquet::FromParquet`](crate::parquet::FromParquet).
+///
+/// See [`czv::parquet::FromParquet`](crate::parquet::FromParquet) for a builder version (recommended) of this function.
+///
+/// ## Notes
+///
+/// - Values are written as Arrow displays them, e.g. dates as `2024-01-31`, and nulls are empty.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+///
+/// ## Arguments
+///
+/// * `file_path` - Parquet file path (alternative to `file_data`).
+/// * `file_data` - Parquet file data (alternative to `file_path`).
+pub fn from_parquet(file_path: Option<PathBuf>, file_data: Option<Vec<u8>>) -> Result<String> {
+    let reader = match (file_path, file_data) {
+        (Some(_), Some(_)) => {
+            return Err(CzvError::InvalidOptions(
+                "Cannot have both file_path and file_data, specify one only.".to_string(),
+            ))
+        }
+        (None, None) => {
+            return Err(CzvError::InvalidOptions(
+                "Must provide either file_path or file_data.".to_string(),
+            ))
+        }
+        (Some(file_path), None) => {
+            ParquetRecordBatchReaderBuilder::try_new(File::open(file_path)?)?.build()?
+        }
+        (None, Some(file_data)) => {
+            ParquetRecordBatchReaderBuilder::try_new(bytes::Bytes::from(file_data))?.build()?
+        }
+    };
+
+    let schema = reader.schema();
+    write_csv(&schema, reader.map(|batch| Ok(batch?)))
+}
+
+#[derive(TypedBuilder)]
+#[builder(doc, builder_method(name=new, doc="Returns a [`FromParquetBuilder`](crate::parquet::FromParquetBuilder) to customize Parquet conversion options by running other methods before getting the CSV with the [`execute`](crate::parquet::FromParquetBuilder::execute) method."), build_method(vis="", name=__build))]
+/// Returns CSV data converted from Parquet.
+///
+/// The entry point for setting up a [`czv::parquet::FromParquetBuilder`](crate::parquet::FromParquetBuilder) by running [`FromParquet::new()`](crate::parquet::FromParquet::new).
+///
+/// See [`ToParquet`](crate::parquet::ToParquet) for an example.
+///
+/// ## Notes
+///
+/// - See [`from_parquet`](crate::parquet::from_parquet) for how values are written.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+pub struct FromParquet {
+    #[builder(
+        default,
+        setter(
+            doc = "Parquet file path (alternative to `file_data`).",
+            strip_option(fallback = file_path_opt),
+            into
+        )
+    )]
+    file_path: Option<PathBuf>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "Parquet file data (alternative to `file_path`).",
+            strip_option(fallback = file_data_opt),
+            into
+        )
+    )]
+    file_data: Option<Vec<u8>>,
+}
+
+#[allow(non_camel_case_types)]
+impl<
+        __file_data: typed_builder::Optional<Option<Vec<u8>>>,
+        __file_path: typed_builder::Optional<Option<PathBuf>>,
+    > FromParquetBuilder<(__file_path, __file_data)>
+{
+    /// Returns the CSV output.
+    pub fn execute(self) -> Result<String> {
+        let builder = self.__build();
+        from_parquet(builder.file_path, builder.file_data)
+    }
+}
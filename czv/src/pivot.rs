@@ -0,0 +1,590 @@
+use crate::aggregate::{Accumulator, AggregateFunction, Aggregation};
+use crate::encoding::Encoding;
+use crate::policy::{ErrorPolicy, Report, Skipper};
+use crate::util::{column_index, column_indexes, into_string, reader_from, writer};
+use crate::{CzvError, Result};
+use csv::{ReaderBuilder, StringRecord};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use typed_builder::TypedBuilder;
+
+/// Returns the position of `key` in `keys`, adding it to `keys` and `positions` if it is new.
+fn position(
+    keys: &mut Vec<Vec<String>>,
+    positions: &mut HashMap<Vec<String>, usize>,
+    key: Vec<String>,
+) -> usize {
+    match positions.get(&key) {
+        Some(position) => *position,
+        None => {
+            positions.insert(key.clone(), keys.len());
+            keys.push(key);
+            keys.len() - 1
+        }
+    }
+}
+
+/// Returns the CSV data reshaped from long to wide. Recommended alternative: [`czv::pivot::Pivot`](crate::pivot::Pivot).
+///
+/// See [`czv::pivot::Pivot`](crate::pivot::Pivot) for a builder version (recommended) of this function.
+///
+/// ## Notes
+///
+/// - The output has the `index` columns followed by a column for each distinct value of
+///   `pivot_column`, and a row for each distinct combination of `index` values. Both are in the
+///   order they first appear.
+/// - Each cell is `aggregate` of the `value_column` values of the records with its row's `index`
+///   values and its column's `pivot_column` value. If there are no such records, counts are `0`
+///   and other cells are empty. See [`AggregateFunction`] for how each function handles empty
+///   values.
+/// - Only the state of each cell is kept in memory.
+/// - An empty `pivot_column` value, or one equal to an `index` header name, would be an empty or
+///   duplicate output header name, so it is an invalid option.
+/// - Records with a non-numeric value when `aggregate` is a sum or mean are malformed. They are
+///   left out when skipped with `error_policy`.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+///
+/// ## Arguments
+///
+/// * `file_path` - CSV file path (alternative to `file_data`).
+/// * `file_data` - CSV file data (alternative to `file_path`).
+/// * `index` - The header names of the columns identifying each output row.
+/// * `pivot_column` - The header name of the column whose values become the new columns.
+/// * `value_column` - The header name of the column whose values fill the new columns.
+/// * `aggregate` - How to combine the values of records with the same row and column.
+/// * `separator` - The separator of values joined with [`AggregateFunction::Concat`].
+/// * `error_policy` - How to handle malformed records.
+/// * `encoding` - The character encoding of `file_path`.
+#[allow(clippy::too_many_arguments)]
+pub fn pivot(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    index: Vec<String>,
+    pivot_column: Option<String>,
+    value_column: Option<String>,
+    aggregate: AggregateFunction,
+    separator: String,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<String> {
+    Ok(pivot_with_report(
+        file_path,
+        file_data,
+        index,
+        pivot_column,
+        value_column,
+        aggregate,
+        separator,
+        error_policy,
+        encoding,
+    )?
+    .output)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn pivot_with_report(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    index: Vec<String>,
+    pivot_column: Option<String>,
+    value_column: Option<String>,
+    aggregate: AggregateFunction,
+    separator: String,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<Report<String>> {
+    let (Some(pivot_column), Some(value_column)) = (pivot_column, value_column) else {
+        return Err(CzvError::InvalidOptions(
+            "Must provide pivot_column and value_column.".to_string(),
+        ));
+    };
+    let rdr = ReaderBuilder::new();
+    let mut rdr = reader_from(&rdr, file_path.as_deref(), file_data.as_deref(), encoding)?;
+    let headers = rdr.headers()?.clone();
+    let keys = column_indexes(&headers, &index)?;
+    let pivot = column_index(&headers, &pivot_column)?;
+    let value = column_index(&headers, &value_column)?;
+    let aggregation = Aggregation::new(aggregate, value_column);
+
+    let mut rows = vec![];
+    let mut row_positions = HashMap::new();
+    let mut columns = vec![];
+    let mut column_positions = HashMap::new();
+    let mut cells: HashMap<(usize, usize), Accumulator> = HashMap::new();
+    let mut skipper = Skipper::new(error_policy);
+    let mut record = StringRecord::new();
+    while skipper.read_record(&mut rdr, &mut record)? {
+        let value = record.get(value).unwrap_or_default();
+        if let Err(err) = aggregation.check(value, &record) {
+            skipper.skip(err)?;
+            continue;
+        }
+        let row_key = keys
+            .iter()
+            .map(|i| record.get(*i).unwrap_or_default().to_string())
+            .collect();
+        let row = position(&mut rows, &mut row_positions, row_key);
+        let column_key = vec![record.get(pivot).unwrap_or_default().to_string()];
+        let column = position(&mut columns, &mut column_positions, column_key);
+        cells
+            .entry((row, column))
+            .or_insert_with(|| Accumulator::new(&aggregation))
+            .update(value);
+    }
+
+    let mut names: HashSet<&str> = index.iter().map(String::as_str).collect();
+    if names.len() < index.len() {
+        return Err(CzvError::InvalidOptions(
+            "Index columns may only be given once.".to_string(),
+        ));
+    }
+    if let Some(name) = columns
+        .iter()
+        .flatten()
+        .find(|name| name.is_empty() || !names.insert(name.as_str()))
+    {
+        return Err(CzvError::InvalidOptions(format!(
+            "Value {name:?} of column {pivot_column:?} cannot be an output column as it is empty or an index column."
+        )));
+    }
+    // A cell without records is finished like a group without values, e.g. `0` for counts.
+    let empty = Accumulator::new(&aggregation);
+    let mut wtr = writer();
+    wtr.write_record(index.iter().chain(columns.iter().flatten()))?;
+    for (row, key) in rows.iter().enumerate() {
+        wtr.write_record(key.iter().cloned().chain((0..columns.len()).map(|column| {
+            cells
+                .get(&(row, column))
+                .unwrap_or(&empty)
+                .finish(&separator)
+        })))?;
+    }
+    Ok(skipper.finish(into_string(wtr)?))
+}
+
+#[derive(TypedBuilder)]
+#[builder(doc, builder_method(name=new, doc="Returns a [`PivotBuilder`](crate::pivot::PivotBuilder) to customize pivot options by running other methods before getting the output with the [`execute`](crate::pivot::PivotBuilder::execute) method."), build_method(vis="", name=__build))]
+/// Returns the CSV data reshaped from long to wide, with a new column for each value of a column.
+///
+/// The entry point for setting up a [`czv::pivot::PivotBuilder`](crate::pivot::PivotBuilder) by running [`Pivot::new()`](crate::pivot::Pivot::new).
+///
+/// # Example
+///
+/// ```rust
+/// use czv::aggregate::AggregateFunction;
+/// use czv::{Pivot, Result};
+///
+/// fn main() -> Result<()> {
+///     let data = "\
+/// store,fruit,count
+/// north,apple,3
+/// north,banana,4
+/// south,apple,2
+/// north,apple,1
+/// ";
+///     let output = Pivot::new()
+///         .file_data(data)
+///         .index(["store".to_string()])
+///         .pivot_column("fruit")
+///         .value_column("count")
+///         .aggregate(AggregateFunction::Sum)
+///         .execute()?;
+///     assert_eq!(
+///         output,
+///         "\
+/// store,apple,banana
+/// north,4,4
+/// south,2,
+/// "
+///     );
+///     Ok(())
+/// }
+/// ```
+///
+/// ## Notes
+///
+/// - See [`pivot`](crate::pivot::pivot) for how rows, columns and cells are formed.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+pub struct Pivot {
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file path (alternative to `file_data`).",
+            strip_option(fallback = file_path_opt),
+            into
+        )
+    )]
+    file_path: Option<PathBuf>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file data (alternative to `file_path`).",
+            strip_option(fallback = file_data_opt),
+            into
+        )
+    )]
+    file_data: Option<String>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "The header names of the columns identifying each output row.",
+            transform = |index: impl IntoIterator<Item = String>| index.into_iter().collect()
+        )
+    )]
+    index: Vec<String>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "The header name of the column whose values become the new columns.",
+            strip_option(fallback = pivot_column_opt),
+            into
+        )
+    )]
+    pivot_column: Option<String>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "The header name of the column whose values fill the new columns.",
+            strip_option(fallback = value_column_opt),
+            into
+        )
+    )]
+    value_column: Option<String>,
+
+    #[builder(
+        default = AggregateFunction::First,
+        setter(doc = "How to combine the values of records with the same row and column. Defaults to `AggregateFunction::First`.")
+    )]
+    aggregate: AggregateFunction,
+
+    #[builder(
+        default = ",".to_string(),
+        setter(
+            doc = "The separator of values joined with `AggregateFunction::Concat`. Defaults to `,`.",
+            into
+        )
+    )]
+    separator: String,
+
+    #[builder(
+        default,
+        setter(doc = "How to handle malformed records. Defaults to `ErrorPolicy::Fail`.")
+    )]
+    error_policy: ErrorPolicy,
+
+    #[builder(
+        default,
+        setter(doc = "The character encoding of `file_path`. Defaults to `Encoding::Utf8`.")
+    )]
+    encoding: Encoding,
+}
+
+#[allow(non_camel_case_types)]
+impl<
+        __encoding: typed_builder::Optional<Encoding>,
+        __error_policy: typed_builder::Optional<ErrorPolicy>,
+        __separator: typed_builder::Optional<String>,
+        __aggregate: typed_builder::Optional<AggregateFunction>,
+        __value_column: typed_builder::Optional<Option<String>>,
+        __pivot_column: typed_builder::Optional<Option<String>>,
+        __index: typed_builder::Optional<Vec<String>>,
+        __file_data: typed_builder::Optional<Option<String>>,
+        __file_path: typed_builder::Optional<Option<PathBuf>>,
+    >
+    PivotBuilder<(
+        __file_path,
+        __file_data,
+        __index,
+        __pivot_column,
+        __value_column,
+        __aggregate,
+        __separator,
+        __error_policy,
+        __encoding,
+    )>
+{
+    /// Returns the pivoted CSV data.
+    pub fn execute(self) -> Result<String> {
+        Ok(self.execute_with_report()?.output)
+    }
+
+    /// Returns the pivoted CSV data along with the records skipped with `ErrorPolicy::Collect`.
+    pub fn execute_with_report(self) -> Result<Report<String>> {
+        let builder = self.__build();
+        pivot_with_report(
+            builder.file_path,
+            builder.file_data,
+            builder.index,
+            builder.pivot_column,
+            builder.value_column,
+            builder.aggregate,
+            builder.separator,
+            builder.error_policy,
+            builder.encoding,
+        )
+    }
+}
+
+/// Returns the CSV data reshaped from wide to long. Recommended alternative: [`czv::pivot::Unpivot`](crate::pivot::Unpivot).
+///
+/// See [`czv::pivot::Unpivot`](crate::pivot::Unpivot) for a builder version (recommended) of this function.
+///
+/// ## Notes
+///
+/// - The output has the `id_columns` followed by a `variable_name` column holding the header name
+///   of a value column, and a `value_name` column holding its value.
+/// - Each record becomes a row for each of `value_columns`, which default to every column that is
+///   not in `id_columns`.
+/// - Rows with an empty value are left out if `skip_empty` is set.
+/// - Malformed records are left out when skipped with `error_policy`.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+///
+/// ## Arguments
+///
+/// * `file_path` - CSV file path (alternative to `file_data`).
+/// * `file_data` - CSV file data (alternative to `file_path`).
+/// * `id_columns` - The header names of the columns kept in every row.
+/// * `value_columns` - The header names of the columns turned into rows.
+/// * `variable_name` - The header name of the output column holding the value column names.
+/// * `value_name` - The header name of the output column holding the values.
+/// * `skip_empty` - Whether to leave out rows with an empty value.
+/// * `error_policy` - How to handle malformed records.
+/// * `encoding` - The character encoding of `file_path`.
+#[allow(clippy::too_many_arguments)]
+pub fn unpivot(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    id_columns: Vec<String>,
+    value_columns: Vec<String>,
+    variable_name: String,
+    value_name: String,
+    skip_empty: bool,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<String> {
+    Ok(unpivot_with_report(
+        file_path,
+        file_data,
+        id_columns,
+        value_columns,
+        variable_name,
+        value_name,
+        skip_empty,
+        error_policy,
+        encoding,
+    )?
+    .output)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn unpivot_with_report(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    id_columns: Vec<String>,
+    value_columns: Vec<String>,
+    variable_name: String,
+    value_name: String,
+    skip_empty: bool,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<Report<String>> {
+    let rdr = ReaderBuilder::new();
+    let mut rdr = reader_from(&rdr, file_path.as_deref(), file_data.as_deref(), encoding)?;
+    let headers = rdr.headers()?.clone();
+    let ids = column_indexes(&headers, &id_columns)?;
+    let values = match value_columns.is_empty() {
+        true => (0..headers.len()).filter(|i| !ids.contains(i)).collect(),
+        false => column_indexes(&headers, &value_columns)?,
+    };
+
+    let mut wtr = writer();
+    wtr.write_record(
+        id_columns
+            .iter()
+            .map(String::as_str)
+            .chain([variable_name.as_str(), value_name.as_str()]),
+    )?;
+    let mut skipper = Skipper::new(error_policy);
+    let mut record = StringRecord::new();
+    while skipper.read_record(&mut rdr, &mut record)? {
+        for column in &values {
+            let value = record.get(*column).unwrap_or_default();
+            if skip_empty && value.is_empty() {
+                continue;
+            }
+            wtr.write_record(
+                ids.iter()
+                    .map(|i| record.get(*i).unwrap_or_default())
+                    .chain([&headers[*column], value]),
+            )?;
+        }
+    }
+    Ok(skipper.finish(into_string(wtr)?))
+}
+
+#[derive(TypedBuilder)]
+#[builder(doc, builder_method(name=new, doc="Returns an [`UnpivotBuilder`](crate::pivot::UnpivotBuilder) to customize unpivot options by running other methods before getting the output with the [`execute`](crate::pivot::UnpivotBuilder::execute) method."), build_method(vis="", name=__build))]
+/// Returns the CSV data reshaped from wide to long, with a row for each value of the value columns.
+///
+/// The entry point for setting up a [`czv::pivot::UnpivotBuilder`](crate::pivot::UnpivotBuilder) by running [`Unpivot::new()`](crate::pivot::Unpivot::new).
+///
+/// # Example
+///
+/// ```rust
+/// use czv::{Result, Unpivot};
+///
+/// fn main() -> Result<()> {
+///     let data = "\
+/// store,apple,banana
+/// north,4,4
+/// south,2,
+/// ";
+///     let output = Unpivot::new()
+///         .file_data(data)
+///         .id_columns(["store".to_string()])
+///         .variable_name("fruit")
+///         .value_name("count")
+///         .skip_empty(true)
+///         .execute()?;
+///     assert_eq!(
+///         output,
+///         "\
+/// store,fruit,count
+/// north,apple,4
+/// north,banana,4
+/// south,apple,2
+/// "
+///     );
+///     Ok(())
+/// }
+/// ```
+///
+/// ## Notes
+///
+/// - See [`unpivot`](crate::pivot::unpivot) for how rows are formed.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+pub struct Unpivot {
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file path (alternative to `file_data`).",
+            strip_option(fallback = file_path_opt),
+            into
+        )
+    )]
+    file_path: Option<PathBuf>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file data (alternative to `file_path`).",
+            strip_option(fallback = file_data_opt),
+            into
+        )
+    )]
+    file_data: Option<String>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "The header names of the columns kept in every row.",
+            transform = |id_columns: impl IntoIterator<Item = String>| id_columns.into_iter().collect()
+        )
+    )]
+    id_columns: Vec<String>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "The header names of the columns turned into rows. Defaults to every column that is not an id column.",
+            transform = |value_columns: impl IntoIterator<Item = String>| value_columns.into_iter().collect()
+        )
+    )]
+    value_columns: Vec<String>,
+
+    #[builder(
+        default = "variable".to_string(),
+        setter(
+            doc = "The header name of the output column holding the value column names. Defaults to `variable`.",
+            into
+        )
+    )]
+    variable_name: String,
+
+    #[builder(
+        default = "value".to_string(),
+        setter(
+            doc = "The header name of the output column holding the values. Defaults to `value`.",
+            into
+        )
+    )]
+    value_name: String,
+
+    #[builder(
+        default,
+        setter(doc = "Whether to leave out rows with an empty value. Defaults to `false`.")
+    )]
+    skip_empty: bool,
+
+    #[builder(
+        default,
+        setter(doc = "How to handle malformed records. Defaults to `ErrorPolicy::Fail`.")
+    )]
+    error_policy: ErrorPolicy,
+
+    #[builder(
+        default,
+        setter(doc = "The character encoding of `file_path`. Defaults to `Encoding::Utf8`.")
+    )]
+    encoding: Encoding,
+}
+
+#[allow(non_camel_case_types)]
+impl<
+        __encoding: typed_builder::Optional<Encoding>,
+        __error_policy: typed_builder::Optional<ErrorPolicy>,
+        __skip_empty: typed_builder::Optional<bool>,
+        __value_name: typed_builder::Optional<String>,
+        __variable_name: typed_builder::Optional<String>,
+        __value_columns: typed_builder::Optional<Vec<String>>,
+        __id_columns: typed_builder::Optional<Vec<String>>,
+        __file_data: typed_builder::Optional<Option<String>>,
+        __file_path: typed_builder::Optional<Option<PathBuf>>,
+    >
+    UnpivotBuilder<(
+        __file_path,
+        __file_data,
+        __id_columns,
+        __value_columns,
+        __variable_name,
+        __value_name,
+        __skip_empty,
+        __error_policy,
+        __encoding,
+    )>
+{
+    /// Returns the unpivoted CSV data.
+    pub fn execute(self) -> Result<String> {
+        Ok(self.execute_with_report()?.output)
+    }
+
+    /// Returns the unpivoted CSV data along with the records skipped with `ErrorPolicy::Collect`.
+    pub fn execute_with_report(self) -> Result<Report<String>> {
+        let builder = self.__build();
+        unpivot_with_report(
+            builder.file_path,
+            builder.file_data,
+            builder.id_columns,
+            builder.value_columns,
+            builder.variable_name,
+            builder.value_name,
+            builder.skip_empty,
+            builder.error_policy,
+            builder.encoding,
+        )
+    }
+}
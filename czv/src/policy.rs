@@ -0,0 +1,111 @@
+use crate::{CzvError, Result};
+use csv::{Reader, StringRecord};
+use std::io::Read;
+
+/// How an operation handles malformed records, e.g. a record with an unexpected number of fields.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ErrorPolicy {
+    /// Return an error at the first malformed record. This is the default.
+    #[default]
+    Fail,
+    /// Skip malformed records.
+    Skip,
+    /// Skip malformed records and collect a [`SkippedRecord`] for each of them, which is returned
+    /// by the builder's `execute_with_report` method.
+    Collect,
+}
+
+/// A malformed record skipped with [`ErrorPolicy::Collect`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedRecord {
+    /// The record index (0-indexed, for CSV data the header row is record 0).
+    pub record: Option<u64>,
+    /// The line the record starts on (1-indexed).
+    pub line: Option<u64>,
+    /// The byte offset from the start of the data where the record starts.
+    pub byte: Option<u64>,
+    /// Why the record was skipped.
+    pub reason: String,
+}
+
+/// The output of an operation along with the records it skipped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report<T> {
+    /// The output of the operation, as returned by its `execute` method.
+    pub output: T,
+    /// The records skipped with [`ErrorPolicy::Collect`], which is empty for any other policy.
+    pub skipped: Vec<SkippedRecord>,
+}
+
+/// Applies an [`ErrorPolicy`] to the records read by an operation.
+pub(crate) struct Skipper {
+    policy: ErrorPolicy,
+    skipped: Vec<SkippedRecord>,
+}
+
+impl Skipper {
+    pub(crate) fn new(policy: ErrorPolicy) -> Self {
+        Self {
+            policy,
+            skipped: vec![],
+        }
+    }
+
+    /// Returns the record, `None` if it should be skipped, or an error if the operation should stop.
+    ///
+    /// I/O errors always stop the operation since they are not caused by a single record.
+    pub(crate) fn check<T>(&mut self, result: csv::Result<T>) -> Result<Option<T>> {
+        match result {
+            Ok(record) => Ok(Some(record)),
+            Err(err) => self.skip(err.into()).map(|()| None),
+        }
+    }
+
+    /// Returns `Ok` if the record that caused `err` should be skipped, or the error if the operation should stop.
+    ///
+    /// Only [`CzvError::Parse`] errors are skipped.
+    pub(crate) fn skip(&mut self, err: CzvError) -> Result<()> {
+        match (self.policy, err) {
+            (ErrorPolicy::Skip, CzvError::Parse { .. }) => Ok(()),
+            (
+                ErrorPolicy::Collect,
+                CzvError::Parse {
+                    message,
+                    line,
+                    byte,
+                    record,
+                },
+            ) => {
+                self.skipped.push(SkippedRecord {
+                    record,
+                    line,
+                    byte,
+                    reason: message,
+                });
+                Ok(())
+            }
+            (_, err) => Err(err),
+        }
+    }
+
+    /// Reads the next record that is not skipped into `record`, returning `false` at the end of the data.
+    pub(crate) fn read_record<R: Read>(
+        &mut self,
+        rdr: &mut Reader<R>,
+        record: &mut StringRecord,
+    ) -> Result<bool> {
+        loop {
+            if let Some(has_record) = self.check(rdr.read_record(record))? {
+                return Ok(has_record);
+            }
+        }
+    }
+
+    /// Returns the output along with the records skipped so far.
+    pub(crate) fn finish<T>(self, output: T) -> Report<T> {
+        Report {
+            output,
+            skipped: self.skipped,
+        }
+    }
+}
@@ -0,0 +1,303 @@
+use crate::encoding::Encoding;
+use crate::policy::{ErrorPolicy, Report, Skipper};
+use crate::sql::{create_table_statement, sql_columns, SqlDialect};
+use crate::sqlite::insert_records;
+use crate::util::{into_string, writer};
+use crate::{CzvError, Result};
+use rusqlite::fallible_iterator::FallibleIterator;
+use rusqlite::hooks::{AuthAction, AuthContext, Authorization};
+use rusqlite::types::ValueRef;
+use rusqlite::{Batch, Connection};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use typed_builder::TypedBuilder;
+
+/// The columns and rows returned by a query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryRecords {
+    /// The names of the result columns.
+    pub headers: Vec<String>,
+    /// The result rows, with `None` for `NULL` values.
+    pub rows: Vec<Vec<Option<String>>>,
+}
+
+impl QueryRecords {
+    /// Returns the records as CSV data, writing `NULL` values as empty values.
+    fn to_csv(&self) -> Result<String> {
+        let mut wtr = writer();
+        wtr.write_record(&self.headers)?;
+        for row in &self.rows {
+            wtr.write_record(row.iter().map(|value| value.as_deref().unwrap_or_default()))?;
+        }
+        into_string(wtr)
+    }
+}
+
+/// Returns the text of a SQLite value, or `None` for `NULL`.
+fn value_string(value: ValueRef) -> Option<String> {
+    match value {
+        ValueRef::Null => None,
+        ValueRef::Integer(n) => Some(n.to_string()),
+        ValueRef::Real(n) => Some(format!("{n:?}")),
+        ValueRef::Text(text) | ValueRef::Blob(text) => {
+            Some(String::from_utf8_lossy(text).into_owned())
+        }
+    }
+}
+
+/// Returns the result of a SQL query over CSV data as CSV. Recommended alternative: [`czv::query::Query`](crate::query::Query).
+///
+/// See [`czv::query::Query`](crate::query::Query) for a builder version (recommended) of this function.
+///
+/// ## Notes
+///
+/// - Each source is loaded into an in-memory SQLite table with the given name, so the query may
+///   use any SQLite `SELECT` syntax such as joins, `GROUP BY` and window functions.
+/// - Column types are inferred as in [`to_sqlite`](crate::sqlite::to_sqlite), and each source is
+///   read twice. Booleans are stored as `1` and `0`, and empty values as `NULL`.
+/// - `NULL` values in the result are written as empty values.
+/// - Only one read-only statement is accepted, and other databases may not be attached.
+/// - Malformed records are not loaded when skipped with `error_policy`. The reasons of collected
+///   records start with the name of their table.
+///
+/// ## Arguments
+///
+/// * `file_paths` - CSV file paths by table name.
+/// * `file_data` - CSV file data by table name.
+/// * `sql` - The SQL query.
+/// * `error_policy` - How to handle malformed records.
+/// * `encoding` - The character encoding of `file_paths`.
+pub fn query(
+    file_paths: Vec<(String, PathBuf)>,
+    file_data: Vec<(String, String)>,
+    sql: Option<String>,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<String> {
+    query_with_report(file_paths, file_data, sql, error_policy, encoding)?
+        .output
+        .to_csv()
+}
+
+fn query_with_report(
+    file_paths: Vec<(String, PathBuf)>,
+    file_data: Vec<(String, String)>,
+    sql: Option<String>,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<Report<QueryRecords>> {
+    let sql = sql.ok_or_else(|| CzvError::InvalidOptions("Must provide sql.".to_string()))?;
+    let sources: Vec<(&str, Option<&Path>, Option<&str>)> = file_paths
+        .iter()
+        .map(|(name, path)| (name.as_str(), Some(path.as_path()), None))
+        .chain(
+            file_data
+                .iter()
+                .map(|(name, data)| (name.as_str(), None, Some(data.as_str()))),
+        )
+        .collect();
+    if sources.is_empty() {
+        return Err(CzvError::InvalidOptions(
+            "Must provide at least one table in file_paths or file_data.".to_string(),
+        ));
+    }
+    // SQLite table names are case-insensitive.
+    let mut names = HashSet::new();
+    if let Some((name, ..)) = sources
+        .iter()
+        .find(|(name, ..)| !names.insert(name.to_lowercase()))
+    {
+        return Err(CzvError::InvalidOptions(format!(
+            "Table {name:?} is provided more than once."
+        )));
+    }
+
+    let mut conn = Connection::open_in_memory()?;
+    let mut skipped = vec![];
+    for (name, file_path, file_data) in sources {
+        let columns = sql_columns(
+            file_path,
+            file_data,
+            &HashMap::new(),
+            error_policy,
+            encoding,
+        )?;
+        conn.execute(
+            &create_table_statement(SqlDialect::Sqlite, name, &columns, false),
+            [],
+        )?;
+        let mut skipper = Skipper::new(error_policy);
+        insert_records(
+            &mut conn,
+            name,
+            &columns,
+            file_path,
+            file_data,
+            usize::MAX,
+            &mut skipper,
+            encoding,
+        )?;
+        skipped.extend(skipper.finish(()).skipped.into_iter().map(|mut record| {
+            record.reason = format!("Table {name:?}: {}", record.reason);
+            record
+        }));
+    }
+
+    // SQLite reports `ATTACH` as read-only although it may create a database file.
+    conn.authorizer(Some(|context: AuthContext| match context.action {
+        AuthAction::Attach { .. } => Authorization::Deny,
+        _ => Authorization::Allow,
+    }));
+    let invalid = |err: rusqlite::Error| CzvError::InvalidOptions(format!("Invalid query: {err}"));
+    let mut statements = Batch::new(&conn, &sql);
+    let Some(mut statement) = statements.next().map_err(invalid)? else {
+        return Err(CzvError::InvalidOptions(
+            "Must provide a query.".to_string(),
+        ));
+    };
+    if statements.next().map_err(invalid)?.is_some() {
+        return Err(CzvError::InvalidOptions(
+            "Only one statement is supported.".to_string(),
+        ));
+    }
+    if !statement.readonly() {
+        return Err(CzvError::InvalidOptions(
+            "Only read-only queries such as SELECT are supported.".to_string(),
+        ));
+    }
+    let headers: Vec<String> = statement
+        .column_names()
+        .into_iter()
+        .map(String::from)
+        .collect();
+    let mut rows = vec![];
+    let mut result = statement.query([])?;
+    while let Some(row) = result.next()? {
+        rows.push(
+            (0..headers.len())
+                .map(|i| Ok(value_string(row.get_ref(i)?)))
+                .collect::<Result<_>>()?,
+        );
+    }
+
+    Ok(Report {
+        output: QueryRecords { headers, rows },
+        skipped,
+    })
+}
+
+#[derive(TypedBuilder)]
+#[builder(doc, builder_method(name=new, doc="Returns a [`QueryBuilder`](crate::query::QueryBuilder) to customize query options by running other methods before getting the result with the [`execute`](crate::query::QueryBuilder::execute) method."), build_method(vis="", name=__build))]
+/// Returns the result of a SQL query over CSV data registered as named tables.
+///
+/// The entry point for setting up a [`czv::query::QueryBuilder`](crate::query::QueryBuilder) by running [`Query::new()`](crate::query::Query::new).
+///
+/// # Example
+///
+/// ```rust
+/// use czv::{Query, Result};
+///
+/// fn main() -> Result<()> {
+///     let prices = "\
+/// fruit,price
+/// apple,2.50
+/// banana,3.00
+/// ";
+///     let stock = "\
+/// fruit,count
+/// apple,4
+/// banana,0
+/// ";
+///     let output = Query::new()
+///         .file_data([
+///             ("prices".to_string(), prices.to_string()),
+///             ("stock".to_string(), stock.to_string()),
+///         ])
+///         .sql("SELECT fruit, price * count AS value FROM prices JOIN stock USING (fruit) ORDER BY fruit")
+///         .execute()?;
+///     assert_eq!(output, "fruit,value\napple,10.0\nbanana,0.0\n");
+///     Ok(())
+/// }
+/// ```
+///
+/// ## Notes
+///
+/// - See [`query`](crate::query::query) for how the tables are loaded and the result is written.
+pub struct Query {
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file paths by table name.",
+            transform = |file_paths: impl IntoIterator<Item = (String, PathBuf)>| file_paths.into_iter().collect()
+        )
+    )]
+    file_paths: Vec<(String, PathBuf)>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file data by table name.",
+            transform = |file_data: impl IntoIterator<Item = (String, String)>| file_data.into_iter().collect()
+        )
+    )]
+    file_data: Vec<(String, String)>,
+
+    #[builder(
+        default,
+        setter(doc = "The SQL query.", strip_option(fallback = sql_opt), into)
+    )]
+    sql: Option<String>,
+
+    #[builder(
+        default,
+        setter(doc = "How to handle malformed records. Defaults to `ErrorPolicy::Fail`.")
+    )]
+    error_policy: ErrorPolicy,
+
+    #[builder(
+        default,
+        setter(doc = "The character encoding of `file_paths`. Defaults to `Encoding::Utf8`.")
+    )]
+    encoding: Encoding,
+}
+
+#[allow(non_camel_case_types)]
+impl<
+        __encoding: typed_builder::Optional<Encoding>,
+        __error_policy: typed_builder::Optional<ErrorPolicy>,
+        __sql: typed_builder::Optional<Option<String>>,
+        __file_data: typed_builder::Optional<Vec<(String, String)>>,
+        __file_paths: typed_builder::Optional<Vec<(String, PathBuf)>>,
+    > QueryBuilder<(__file_paths, __file_data, __sql, __error_policy, __encoding)>
+{
+    /// Returns the query result as CSV data.
+    pub fn execute(self) -> Result<String> {
+        Ok(self.execute_with_report()?.output)
+    }
+
+    /// Returns the query result as CSV data along with the records skipped with `ErrorPolicy::Collect`.
+    pub fn execute_with_report(self) -> Result<Report<String>> {
+        let report = self.execute_to_records_with_report()?;
+        Ok(Report {
+            output: report.output.to_csv()?,
+            skipped: report.skipped,
+        })
+    }
+
+    /// Returns the query result as records.
+    pub fn execute_to_records(self) -> Result<QueryRecords> {
+        Ok(self.execute_to_records_with_report()?.output)
+    }
+
+    /// Returns the query result as records along with the records skipped with `ErrorPolicy::Collect`.
+    pub fn execute_to_records_with_report(self) -> Result<Report<QueryRecords>> {
+        let builder = self.__build();
+        query_with_report(
+            builder.file_paths,
+            builder.file_data,
+            builder.sql,
+            builder.error_policy,
+            builder.encoding,
+        )
+    }
+}
@@ -0,0 +1,479 @@
+use crate::encoding::Encoding;
+use crate::infer::{infer_columns, ColumnType};
+use crate::policy::{ErrorPolicy, Report, Skipper};
+use crate::util::{into_string, reader_from, writer};
+use crate::{CzvError, Result};
+use csv::{ReaderBuilder, StringRecord};
+use jsonschema::paths::LocationSegment;
+use jsonschema::ValidationError;
+use serde_json::{json, Map, Number, Value};
+use std::path::PathBuf;
+use typed_builder::TypedBuilder;
+
+/// A single schema violation found by [`schema_validate`](crate::schema::schema_validate).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaRowError {
+    /// The record index (0-indexed, the header row is record 0).
+    pub record: u64,
+    /// The header name of the column the violation was found in, if it applies to a single column.
+    pub column: Option<String>,
+    /// A description of the violation.
+    pub message: String,
+}
+
+/// The output of [`schema_validate`](crate::schema::schema_validate).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaValidateOutput {
+    /// CSV data of the rows that match the schema, including the header row.
+    pub valid: String,
+    /// CSV data of the rows that do not match the schema, including the header row.
+    pub invalid: String,
+    /// Every violation found, in the order of the rows.
+    pub errors: Vec<SchemaRowError>,
+}
+
+/// Returns the JSON types a column's schema allows, or an empty list if it does not specify any.
+fn allowed_types(schema: &Value, name: &str) -> Vec<String> {
+    match schema.pointer(&format!("/properties/{}/type", escape_pointer(name))) {
+        Some(Value::String(t)) => vec![t.clone()],
+        Some(Value::Array(types)) => types
+            .iter()
+            .filter_map(|t| t.as_str().map(str::to_string))
+            .collect(),
+        _ => vec![],
+    }
+}
+
+fn escape_pointer(name: &str) -> String {
+    name.replace('~', "~0").replace('/', "~1")
+}
+
+/// Converts a CSV value into the first JSON type allowed by the column's schema that it can be read as.
+///
+/// Empty values become `null` if the schema allows it and are otherwise left out of the row,
+/// so that `required` rejects them.
+fn to_json(value: &str, types: &[String]) -> Option<Value> {
+    if value.is_empty() {
+        return types.iter().any(|t| t == "null").then_some(Value::Null);
+    }
+    for t in types {
+        let converted = match t.as_str() {
+            "integer" => value.parse::<i64>().ok().map(Value::from),
+            "number" => value
+                .parse::<f64>()
+                .ok()
+                .and_then(Number::from_f64)
+                .map(Value::Number),
+            "boolean" if value.eq_ignore_ascii_case("true") => Some(Value::Bool(true)),
+            "boolean" if value.eq_ignore_ascii_case("false") => Some(Value::Bool(false)),
+            _ => None,
+        };
+        if converted.is_some() {
+            return converted;
+        }
+    }
+    Some(Value::String(value.to_string()))
+}
+
+fn row_error(record: u64, err: ValidationError) -> SchemaRowError {
+    let column = match err.kind() {
+        jsonschema::error::ValidationErrorKind::Required { property } => {
+            property.as_str().map(str::to_string)
+        }
+        _ => match err.instance_path().iter().next() {
+            Some(LocationSegment::Property(name)) => Some(name.to_string()),
+            _ => None,
+        },
+    };
+    SchemaRowError {
+        record,
+        column,
+        message: err.to_string(),
+    }
+}
+
+/// Validates every row against a JSON Schema. Recommended alternative: [`czv::schema::SchemaValidate`](crate::schema::SchemaValidate).
+///
+/// See [`czv::schema::SchemaValidate`](crate::schema::SchemaValidate) for a builder version (recommended) of this function.
+///
+/// ## Notes
+///
+/// - Each row is converted to a JSON object keyed by header name. A value is converted to the
+///   first of its column's schema `type`s it can be read as (`integer`, `number`, `boolean`),
+///   otherwise it is kept as a string.
+/// - Empty values become `null` if the column's schema allows `null`, and are otherwise left
+///   out of the object so that `required` rejects them.
+/// - Malformed records are left out of both outputs when skipped with `error_policy`.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+/// - You may not use `schema_path` and `schema_data` together, only one may be specified.
+///
+/// ## Arguments
+///
+/// * `file_path` - CSV file path (alternative to `file_data`).
+/// * `file_data` - CSV file data (alternative to `file_path`).
+/// * `schema_path` - JSON Schema file path (alternative to `schema_data`).
+/// * `schema_data` - JSON Schema data (alternative to `schema_path`).
+/// * `error_policy` - How to handle malformed records.
+/// * `encoding` - The character encoding of `file_path`.
+pub fn schema_validate(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    schema_path: Option<PathBuf>,
+    schema_data: Option<String>,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<SchemaValidateOutput> {
+    Ok(schema_validate_with_report(
+        file_path,
+        file_data,
+        schema_path,
+        schema_data,
+        error_policy,
+        encoding,
+    )?
+    .output)
+}
+
+fn schema_validate_with_report(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    schema_path: Option<PathBuf>,
+    schema_data: Option<String>,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<Report<SchemaValidateOutput>> {
+    let schema: Value = match (schema_path, schema_data) {
+        (Some(_), Some(_)) => {
+            return Err(CzvError::InvalidOptions(
+                "Cannot have both schema_path and schema_data, specify one only.".to_string(),
+            ))
+        }
+        (None, None) => {
+            return Err(CzvError::InvalidOptions(
+                "Must provide either schema_path or schema_data.".to_string(),
+            ))
+        }
+        (Some(schema_path), None) => serde_json::from_reader(std::fs::File::open(schema_path)?)?,
+        (None, Some(schema_data)) => serde_json::from_str(&schema_data)?,
+    };
+    let validator = match jsonschema::validator_for(&schema) {
+        Ok(validator) => validator,
+        Err(err) => {
+            return Err(CzvError::InvalidOptions(format!(
+                "Invalid JSON Schema: {err}"
+            )))
+        }
+    };
+
+    let mut rdr = reader_from(
+        &ReaderBuilder::new(),
+        file_path.as_deref(),
+        file_data.as_deref(),
+        encoding,
+    )?;
+    let headers = rdr.headers()?.clone();
+    let types = headers
+        .iter()
+        .map(|name| allowed_types(&schema, name))
+        .collect::<Vec<_>>();
+
+    let mut valid = writer();
+    let mut invalid = writer();
+    valid.write_record(&headers)?;
+    invalid.write_record(&headers)?;
+    let mut errors = vec![];
+
+    let mut skipper = Skipper::new(error_policy);
+    let mut record = StringRecord::new();
+    while skipper.read_record(&mut rdr, &mut record)? {
+        let record_index = record.position().map_or(0, |pos| pos.record());
+        let mut row = Map::new();
+        for ((name, value), types) in headers.iter().zip(record.iter()).zip(&types) {
+            if let Some(value) = to_json(value, types) {
+                row.insert(name.to_string(), value);
+            }
+        }
+        let row = Value::Object(row);
+
+        let errors_before = errors.len();
+        errors.extend(
+            validator
+                .iter_errors(&row)
+                .map(|err| row_error(record_index, err)),
+        );
+        if errors.len() == errors_before {
+            valid.write_record(&record)?;
+        } else {
+            invalid.write_record(&record)?;
+        }
+    }
+
+    Ok(skipper.finish(SchemaValidateOutput {
+        valid: into_string(valid)?,
+        invalid: into_string(invalid)?,
+        errors,
+    }))
+}
+
+#[derive(TypedBuilder)]
+#[builder(doc, builder_method(name=new, doc="Returns a [`SchemaValidateBuilder`](crate::schema::SchemaValidateBuilder) to customize schema validation options by running other methods before getting the output with the [`execute`](crate::schema::SchemaValidateBuilder::execute) method."), build_method(vis="", name=__build))]
+/// Validates every row against a JSON Schema, splitting the rows into valid and invalid CSV data with a per-row error report.
+///
+/// The entry point for setting up a [`czv::schema::SchemaValidateBuilder`](crate::schema::SchemaValidateBuilder) by running [`SchemaValidate::new()`](crate::schema::SchemaValidate::new).
+///
+/// # Example
+///
+/// ```rust
+/// use czv::{Result, SchemaValidate};
+///
+/// fn main() -> Result<()> {
+///     let data = "\
+/// fruits,price
+/// apple,2.50
+/// banana,free
+/// ";
+///     let schema = r#"{
+///         "type": "object",
+///         "properties": {
+///             "fruits": { "type": "string" },
+///             "price": { "type": "number", "minimum": 0 }
+///         },
+///         "required": ["fruits", "price"]
+///     }"#;
+///     let output = SchemaValidate::new()
+///         .file_data(data)
+///         .schema_data(schema)
+///         .execute()?;
+///     assert_eq!(output.valid, "fruits,price\napple,2.50\n");
+///     assert_eq!(output.invalid, "fruits,price\nbanana,free\n");
+///     assert_eq!(output.errors[0].record, 2);
+///     assert_eq!(output.errors[0].column.as_deref(), Some("price"));
+///     Ok(())
+/// }
+/// ```
+///
+/// ## Notes
+///
+/// - See [`schema_validate`](crate::schema::schema_validate) for how values are converted to JSON.
+/// - See [`SchemaGenerate`](crate::schema::SchemaGenerate) to generate a starter schema from the data.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+/// - You may not use `schema_path` and `schema_data` together, only one may be specified.
+pub struct SchemaValidate {
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file path (alternative to `file_data`).",
+            strip_option(fallback = file_path_opt),
+            into
+        )
+    )]
+    file_path: Option<PathBuf>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file data (alternative to `file_path`).",
+            strip_option(fallback = file_data_opt),
+            into
+        )
+    )]
+    file_data: Option<String>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "JSON Schema file path (alternative to `schema_data`).",
+            strip_option(fallback = schema_path_opt),
+            into
+        )
+    )]
+    schema_path: Option<PathBuf>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "JSON Schema data (alternative to `schema_path`).",
+            strip_option(fallback = schema_data_opt),
+            into
+        )
+    )]
+    schema_data: Option<String>,
+
+    #[builder(
+        default,
+        setter(doc = "How to handle malformed records. Defaults to `ErrorPolicy::Fail`.")
+    )]
+    error_policy: ErrorPolicy,
+
+    #[builder(
+        default,
+        setter(doc = "The character encoding of `file_path`. Defaults to `Encoding::Utf8`.")
+    )]
+    encoding: Encoding,
+}
+
+#[allow(non_camel_case_types)]
+impl<
+        __encoding: typed_builder::Optional<Encoding>,
+        __error_policy: typed_builder::Optional<ErrorPolicy>,
+        __schema_data: typed_builder::Optional<Option<String>>,
+        __schema_path: typed_builder::Optional<Option<PathBuf>>,
+        __file_data: typed_builder::Optional<Option<String>>,
+        __file_path: typed_builder::Optional<Option<PathBuf>>,
+    >
+    SchemaValidateBuilder<(
+        __file_path,
+        __file_data,
+        __schema_path,
+        __schema_data,
+        __error_policy,
+        __encoding,
+    )>
+{
+    /// Returns the valid rows, invalid rows and error report.
+    pub fn execute(self) -> Result<SchemaValidateOutput> {
+        Ok(self.execute_with_report()?.output)
+    }
+
+    /// Returns the valid rows, invalid rows and error report along with the records skipped with `ErrorPolicy::Collect`.
+    pub fn execute_with_report(self) -> Result<Report<SchemaValidateOutput>> {
+        let builder = self.__build();
+        schema_validate_with_report(
+            builder.file_path,
+            builder.file_data,
+            builder.schema_path,
+            builder.schema_data,
+            builder.error_policy,
+            builder.encoding,
+        )
+    }
+}
+
+/// Returns a starter JSON Schema for the CSV data using inferred column types. Recommended alternative: [`czv::schema::SchemaGenerate`](crate::schema::SchemaGenerate).
+///
+/// See [`czv::schema::SchemaGenerate`](crate::schema::SchemaGenerate) for a builder version (recommended) of this function.
+///
+/// ## Notes
+///
+/// - Columns with an empty value allow `null`, every other column is `required`.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+///
+/// ## Arguments
+///
+/// * `file_path` - CSV file path (alternative to `file_data`).
+/// * `file_data` - CSV file data (alternative to `file_path`).
+/// * `encoding` - The character encoding of `file_path`.
+pub fn schema_generate(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    encoding: Encoding,
+) -> Result<String> {
+    let mut rdr = reader_from(
+        &ReaderBuilder::new(),
+        file_path.as_deref(),
+        file_data.as_deref(),
+        encoding,
+    )?;
+    let columns = infer_columns(&mut rdr, ErrorPolicy::Fail)?;
+
+    let mut properties = Map::new();
+    let mut required = vec![];
+    for column in columns {
+        let json_type = match column.column_type {
+            ColumnType::Boolean => "boolean",
+            ColumnType::Integer => "integer",
+            ColumnType::Float => "number",
+            ColumnType::String => "string",
+        };
+        let json_type = if column.nullable {
+            json!([json_type, "null"])
+        } else {
+            required.push(column.name.clone());
+            json!(json_type)
+        };
+        properties.insert(column.name, json!({ "type": json_type }));
+    }
+
+    let schema = json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    });
+    Ok(serde_json::to_string_pretty(&schema)?)
+}
+
+#[derive(TypedBuilder)]
+#[builder(doc, builder_method(name=new, doc="Returns a [`SchemaGenerateBuilder`](crate::schema::SchemaGenerateBuilder) to customize schema generation options by running other methods before getting the schema with the [`execute`](crate::schema::SchemaGenerateBuilder::execute) method."), build_method(vis="", name=__build))]
+/// Returns a starter JSON Schema for the CSV data using inferred column types.
+///
+/// The entry point for setting up a [`czv::schema::SchemaGenerateBuilder`](crate::schema::SchemaGenerateBuilder) by running [`SchemaGenerate::new()`](crate::schema::SchemaGenerate::new).
+///
+/// # Example
+///
+/// ```rust
+/// use czv::{Result, SchemaGenerate, SchemaValidate};
+///
+/// fn main() -> Result<()> {
+///     let data = "\
+/// fruits,price
+/// apple,2.50
+/// banana,3.00
+/// ";
+///     let schema = SchemaGenerate::new().file_data(data).execute()?;
+///     println!("{schema}");
+///
+///     // The data always matches its own generated schema.
+///     let output = SchemaValidate::new()
+///         .file_data(data)
+///         .schema_data(schema)
+///         .execute()?;
+///     assert!(output.errors.is_empty());
+///     Ok(())
+/// }
+/// ```
+///
+/// ## Notes
+///
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+pub struct SchemaGenerate {
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file path (alternative to `file_data`).",
+            strip_option(fallback = file_path_opt),
+            into
+        )
+    )]
+    file_path: Option<PathBuf>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file data (alternative to `file_path`).",
+            strip_option(fallback = file_data_opt),
+            into
+        )
+    )]
+    file_data: Option<String>,
+
+    #[builder(
+        default,
+        setter(doc = "The character encoding of `file_path`. Defaults to `Encoding::Utf8`.")
+    )]
+    encoding: Encoding,
+}
+
+#[allow(non_camel_case_types)]
+impl<
+        __encoding: typed_builder::Optional<Encoding>,
+        __file_data: typed_builder::Optional<Option<String>>,
+        __file_path: typed_builder::Optional<Option<PathBuf>>,
+    > SchemaGenerateBuilder<(__file_path, __file_data, __encoding)>
+{
+    /// Returns the generated JSON Schema.
+    pub fn execute(self) -> Result<String> {
+        let builder = self.__build();
+        schema_generate(builder.file_path, builder.file_data, builder.encoding)
+    }
+}
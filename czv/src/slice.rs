@@ -1,9 +1,23 @@
-use crate::{bail, Result};
+use crate::encoding::Encoding;
+use crate::policy::{ErrorPolicy, Report, Skipper};
+use crate::util::{into_string, reader_from, writer};
+use crate::{CzvError, Result};
 use csv::ReaderBuilder;
 use std::path::PathBuf;
 use typed_builder::TypedBuilder;
 
-/// Returns a slice of rows from the CSV data.
+/// Resolves a possibly negative record index, clamping it to the start of the records.
+fn resolve(value: i32, records_count: Option<usize>) -> usize {
+    if value >= 0 {
+        value as usize
+    } else {
+        records_count
+            .unwrap_or(0)
+            .saturating_sub(value.unsigned_abs() as usize)
+    }
+}
+
+/// Returns a slice of rows from the CSV data. Recommended alternative: [`czv::slice::Slice`](crate::slice::Slice).
 ///
 /// See [`czv::slice::Slice`](crate::slice::Slice) for a builder version (recommended) of this function.
 ///
@@ -21,7 +35,7 @@ use typed_builder::TypedBuilder;
 /// Let's print the first two non-header rows in the CSV file:
 ///
 /// ```rust
-/// use czv::Result;
+/// use czv::{Encoding, ErrorPolicy, Result};
 ///
 /// fn main() -> Result<()> {
 ///     let file_path = "tests/resources/fruits.csv";
@@ -33,7 +47,9 @@ use typed_builder::TypedBuilder;
 ///         Some(2), // end
 ///         None, // length
 ///         None, // index
-///         false // include_header_row
+///         false, // include_header_row
+///         ErrorPolicy::Fail, // error_policy
+///         Encoding::Utf8, // encoding
 ///     )?;
 ///
 ///     println!("{output}");
@@ -54,17 +70,22 @@ use typed_builder::TypedBuilder;
 /// - You may not use `file_path` and `file_data` together, only one may be specified.
 /// - You may not use `end` and `length` together, only one may be specified.
 /// - You may not use `index` with any of `start`, `end`, or `length`.
+/// - `start` and `end` are clamped to the records available, while an out of range `index` is an error.
+/// - Malformed records are left out of the output when skipped with `error_policy`.
 ///
 /// ## Arguments
 ///
 /// * `file_path` - CSV file path (alternative to `file_data`).
 /// * `file_data` - CSV file data (alternative to `file_path`).
 /// * `start` - The index of the record to slice from (0-indexed).
-/// If negative, starts from the last record.
-/// * `end` - The index of the record to slice to.
+///   If negative, starts from the last record.
+/// * `end` - The index of the record to slice to (exclusive). If negative, starts from the last record.
 /// * `length` - The length of the slice (alternative to `end`).
 /// * `index` - Slice a single record. If negative, starts from the last record.
-/// * `include_header_row` - Specify whether to include the header row (first row) in the records.
+/// * `include_header_row` - Specify whether to include the header row (first row) in the output.
+/// * `error_policy` - How to handle malformed records.
+/// * `encoding` - The character encoding of `file_path`.
+#[allow(clippy::too_many_arguments)]
 pub fn slice(
     file_path: Option<PathBuf>,
     file_data: Option<String>,
@@ -73,165 +94,120 @@ pub fn slice(
     length: Option<i32>,
     index: Option<i32>,
     include_header_row: bool,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
 ) -> Result<String> {
-    match (file_path, file_data) {
-        (Some(_), Some(_)) => bail!("Cannot have both file_path and file_data, specify one only."),
-        (None, None) => bail!("Must provide either file_path or file_data."),
-        (Some(file_path), None) => {
-            let mut rdr = ReaderBuilder::new()
-                .has_headers(include_header_row)
-                .from_path(file_path)?;
-            if !include_header_row {
-                rdr.byte_headers()?;
-            }
-            let original_pos = rdr.position().to_owned();
-            let records_count = rdr.records().count();
-
-            if index.is_some() && (start.is_some() || end.is_some() || length.is_some()) {
-                bail!("Cannot use index with start, end, or length.")
-            }
-            if end.is_some() && length.is_some() {
-                bail!("Cannot use end with length.")
-            }
-
-            let start_line = match start {
-                Some(x) => {
-                    if x > records_count as i32 {
-                        bail!("start value {x} cannot be greater than the number of records.")
-                    } else if x >= 0 {
-                        Some(x as usize)
-                    } else {
-                        Some((records_count as i32 + x) as usize)
-                    }
-                }
-                None => None,
-            };
-            let end_line = match (end, length) {
-                (Some(e), None) => {
-                    if x >= records_count as i32 {
-                        bail!("end value {e} cannot be greater than or equal to the number of records.")
-                    } else if e >= 0 {
-                        Some(e as usize)
-                    } else {
-                        Some((records_count as i32 + e) as usize)
-                    }
-                }
-                (None, Some(l)) => {
-                    if l >= records_count as i32 {
-                        bail!("end value {l} cannot be greater than or equal to the number of records.")
-                    } else if l >= 0 {
-                        Some(l as usize)
-                    } else {
-                        Some((records_count as i32 + l) as usize)
-                    }
-                }
-                None => None,
-            };
+    Ok(slice_with_report(
+        file_path,
+        file_data,
+        start,
+        end,
+        length,
+        index,
+        include_header_row,
+        error_policy,
+        encoding,
+    )?
+    .output)
+}
 
-            rdr.seek(original_pos)?;
-            let mut res_vec: Vec<String> = vec![];
+#[allow(clippy::too_many_arguments)]
+fn slice_with_report(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    start: Option<i32>,
+    end: Option<i32>,
+    length: Option<i32>,
+    index: Option<i32>,
+    include_header_row: bool,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<Report<String>> {
+    if index.is_some() && (start.is_some() || end.is_some() || length.is_some()) {
+        return Err(CzvError::InvalidOptions(
+            "Cannot use index with start, end, or length.".to_string(),
+        ));
+    }
+    if end.is_some() && length.is_some() {
+        return Err(CzvError::InvalidOptions(
+            "Cannot use end with length.".to_string(),
+        ));
+    }
+    if length.is_some_and(|length| length < 0) {
+        return Err(CzvError::InvalidOptions(
+            "length cannot be negative.".to_string(),
+        ));
+    }
 
-            if let Some(idx) = index {
-                return Ok(rdr
-                    .records()
-                    .skip(idx as usize)
-                    .next()
-                    .unwrap()?
-                    .iter()
-                    .map(|x| x.to_string())
-                    .collect::<Vec<_>>()
-                    .join(","));
-            }
+    let rdr = ReaderBuilder::new();
+    let file_path = file_path.as_deref();
+    let file_data = file_data.as_deref();
 
-            for r in rdr
-                .records()
-                .skip(start_line.unwrap())
-                .take(end_line.unwrap() - start_line.unwrap())
-            {
-                let record = r?;
-                res_vec.push(
-                    record
-                        .iter()
-                        .map(|x| x.to_string())
-                        .collect::<Vec<_>>()
-                        .join(","),
-                );
+    // Only count the records when an index is relative to the last record.
+    let records_count = if [start, end, index].iter().flatten().any(|x| *x < 0) {
+        let mut skipper = Skipper::new(error_policy);
+        let mut count = 0;
+        for record in reader_from(&rdr, file_path, file_data, encoding)?.byte_records() {
+            if skipper.check(record)?.is_some() {
+                count += 1;
             }
-            Ok(res_vec
-                .iter()
-                .map(|x| x.to_string())
-                .collect::<Vec<_>>()
-                .join("\n"))
         }
-        (None, Some(file_data)) => {
-            let mut rdr = ReaderBuilder::new()
-                .has_headers(include_header_row)
-                .from_reader(std::io::Cursor::new(file_data.as_str()));
-            if !include_header_row {
-                rdr.byte_headers()?;
-            }
-            let original_pos = rdr.position().to_owned();
-            let records_count = rdr.records().count();
+        Some(count)
+    } else {
+        None
+    };
 
-            if index.is_some() && (start.is_some() || end.is_some() || length.is_some()) {
-                bail!("Cannot use index with start, end, or length.")
+    let (start_line, end_line) = match index {
+        Some(idx) => {
+            if idx < 0 && idx.unsigned_abs() as usize > records_count.unwrap_or(0) {
+                return Err(CzvError::InvalidOptions(format!(
+                    "index value {idx} is out of range."
+                )));
             }
-            if end.is_some() && length.is_some() {
-                bail!("Cannot use end with length.")
-            }
-
-            let start_line = match start {
-                Some(x) => {
-                    if x > records_count as i32 {
-                        bail!("start value {x} cannot be greater than the number of records.")
-                    } else if x >= 0 {
-                        Some(x as usize)
-                    } else {
-                        Some((records_count as i32 + x) as usize)
-                    }
-                }
-                None => None,
-            };
-            let end_line = match end {
-                Some(x) => {
-                    if x >= records_count as i32 {
-                        bail!("end value {x} cannot be greater than or equal to the number of records.")
-                    } else if x >= 0 {
-                        Some(x as usize)
-                    } else {
-                        Some((records_count as i32 + x) as usize)
-                    }
-                }
-                None => None,
+            let idx = resolve(idx, records_count);
+            (idx, idx + 1)
+        }
+        None => {
+            let start_line = resolve(start.unwrap_or(0), records_count);
+            let end_line = match (end, length) {
+                (Some(e), _) => resolve(e, records_count),
+                (None, Some(l)) => start_line.saturating_add(l as usize),
+                (None, None) => usize::MAX,
             };
+            (start_line, end_line.max(start_line))
+        }
+    };
 
-            rdr.seek(original_pos)?;
-            let mut res_vec: Vec<String> = vec![];
-            for r in rdr
-                .records()
-                .skip(start_line.unwrap())
-                .take(end_line.unwrap() - start_line.unwrap())
-            {
-                let record = r?;
-                res_vec.push(
-                    record
-                        .iter()
-                        .map(|x| x.to_string())
-                        .collect::<Vec<_>>()
-                        .join(","),
-                );
+    let mut rdr = reader_from(&rdr, file_path, file_data, encoding)?;
+    let mut wtr = writer();
+    if include_header_row {
+        wtr.write_byte_record(rdr.byte_headers()?)?;
+    }
+
+    let mut skipper = Skipper::new(error_policy);
+    let mut i = 0;
+    for record in rdr.byte_records() {
+        if i >= end_line {
+            break;
+        }
+        if let Some(record) = skipper.check(record)? {
+            if i >= start_line {
+                wtr.write_byte_record(&record)?;
             }
-            Ok(res_vec
-                .iter()
-                .map(|x| x.to_string())
-                .collect::<Vec<_>>()
-                .join("\n"))
+            i += 1;
         }
     }
+    if let Some(idx) = index.filter(|_| i <= start_line) {
+        return Err(CzvError::InvalidOptions(format!(
+            "index value {idx} is out of range."
+        )));
+    }
+
+    Ok(skipper.finish(into_string(wtr)?))
 }
 
 #[derive(TypedBuilder)]
-#[builder(doc, builder_method(name=new, doc="Returns a [`SliceBuilder`](crate::slice::SliceBuilder) to customize row count options by running other methods before getting the sliced rows with the [`execute`](crate::slice::SliceBuilder::execute) method."), build_method(vis="", name=__build))]
+#[builder(doc, builder_method(name=new, doc="Returns a [`SliceBuilder`](crate::slice::SliceBuilder) to customize slice options by running other methods before getting the sliced rows with the [`execute`](crate::slice::SliceBuilder::execute) method."), build_method(vis="", name=__build))]
 /// Returns a slice of rows from the CSV data.
 ///
 /// ## Example
@@ -283,7 +259,7 @@ pub struct Slice {
         default,
         setter(
             doc = "CSV file path (alternative to `file_data`).",
-            strip_option,
+            strip_option(fallback = file_path_opt),
             into
         )
     )]
@@ -293,7 +269,7 @@ pub struct Slice {
         default,
         setter(
             doc = "CSV file data (alternative to `file_path`).",
-            strip_option,
+            strip_option(fallback = file_data_opt),
             into
         )
     )]
@@ -303,20 +279,26 @@ pub struct Slice {
         default,
         setter(
             doc = "The index of the record to slice from (0-indexed). If negative, starts from the last record.",
-            strip_option
+            strip_option(fallback = start_opt)
         )
     )]
     start: Option<i32>,
 
     #[builder(
         default,
-        setter(doc = "The index of the record to slice to.", strip_option)
+        setter(
+            doc = "The index of the record to slice to (exclusive). If negative, starts from the last record.",
+            strip_option(fallback = end_opt)
+        )
     )]
     end: Option<i32>,
 
     #[builder(
         default,
-        setter(doc = "The length of the slice (alternative to `end`).", strip_option)
+        setter(
+            doc = "The length of the slice (alternative to `end`).",
+            strip_option(fallback = length_opt)
+        )
     )]
     length: Option<i32>,
 
@@ -324,20 +306,34 @@ pub struct Slice {
         default,
         setter(
             doc = "Slice a single record. If negative, starts from the last record.",
-            strip_option
+            strip_option(fallback = index_opt)
         )
     )]
     index: Option<i32>,
 
     #[builder(
         default = false,
-        setter(doc = "Specify whether to include the header row (first row) in the records.")
+        setter(doc = "Specify whether to include the header row (first row) in the output.")
     )]
     include_header_row: bool,
+
+    #[builder(
+        default,
+        setter(doc = "How to handle malformed records. Defaults to `ErrorPolicy::Fail`.")
+    )]
+    error_policy: ErrorPolicy,
+
+    #[builder(
+        default,
+        setter(doc = "The character encoding of `file_path`. Defaults to `Encoding::Utf8`.")
+    )]
+    encoding: Encoding,
 }
 
 #[allow(non_camel_case_types)]
 impl<
+        __encoding: typed_builder::Optional<Encoding>,
+        __error_policy: typed_builder::Optional<ErrorPolicy>,
         __include_header_row: typed_builder::Optional<bool>,
         __index: typed_builder::Optional<Option<i32>>,
         __length: typed_builder::Optional<Option<i32>>,
@@ -354,12 +350,19 @@ impl<
         __length,
         __index,
         __include_header_row,
+        __error_policy,
+        __encoding,
     )>
 {
     /// Returns the sliced rows.
     pub fn execute(self) -> Result<String> {
+        Ok(self.execute_with_report()?.output)
+    }
+
+    /// Returns the sliced rows along with the records skipped with `ErrorPolicy::Collect`.
+    pub fn execute_with_report(self) -> Result<Report<String>> {
         let builder = self.__build();
-        slice(
+        slice_with_report(
             builder.file_path,
             builder.file_data,
             builder.start,
@@ -367,6 +370,8 @@ impl<
             builder.length,
             builder.index,
             builder.include_header_row,
+            builder.error_policy,
+            builder.encoding,
         )
     }
 }
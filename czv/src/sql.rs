@@ -0,0 +1,505 @@
+use crate::encoding::Encoding;
+use crate::infer::{infer_columns, ColumnType, InferredColumn};
+use crate::policy::{ErrorPolicy, Report, Skipper};
+use crate::util::reader_from;
+use crate::{CzvError, Result};
+use csv::{ReaderBuilder, StringRecord};
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use typed_builder::TypedBuilder;
+
+/// The table name used when it is not set and cannot be taken from the file name.
+const DEFAULT_TABLE_NAME: &str = "data";
+
+/// The SQL dialect of a generated script.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SqlDialect {
+    /// SQLite. This is the default.
+    #[default]
+    Sqlite,
+    /// PostgreSQL.
+    Postgres,
+    /// MySQL and MariaDB.
+    Mysql,
+}
+
+impl SqlDialect {
+    /// Returns the column type for values of `column_type`.
+    pub(crate) fn column_type(self, column_type: ColumnType) -> &'static str {
+        match (self, column_type) {
+            (Self::Sqlite, ColumnType::Boolean | ColumnType::Integer) => "INTEGER",
+            (Self::Sqlite, ColumnType::Float) => "REAL",
+            (Self::Postgres, ColumnType::Float) => "DOUBLE PRECISION",
+            (Self::Mysql, ColumnType::Float) => "DOUBLE",
+            (_, ColumnType::Boolean) => "BOOLEAN",
+            (_, ColumnType::Integer) => "BIGINT",
+            (_, ColumnType::String) => "TEXT",
+        }
+    }
+
+    /// Returns `name` quoted as an identifier, e.g. a table or column name.
+    pub(crate) fn quote_identifier(self, name: &str) -> String {
+        match self {
+            Self::Mysql => format!("`{}`", name.replace('`', "``")),
+            _ => format!("\"{}\"", name.replace('"', "\"\"")),
+        }
+    }
+
+    /// Returns `value` as a literal of `column_type`, `NULL` if it is empty, or `None` if it
+    /// cannot be read as `column_type`.
+    pub(crate) fn literal(self, value: &str, column_type: ColumnType) -> Option<String> {
+        if value.is_empty() {
+            return Some("NULL".to_string());
+        }
+        match column_type {
+            ColumnType::Boolean => {
+                let value = match value.to_ascii_lowercase().as_str() {
+                    "true" => true,
+                    "false" => false,
+                    _ => return None,
+                };
+                Some(match (self, value) {
+                    (Self::Sqlite, true) => "1".to_string(),
+                    (Self::Sqlite, false) => "0".to_string(),
+                    (_, true) => "TRUE".to_string(),
+                    (_, false) => "FALSE".to_string(),
+                })
+            }
+            ColumnType::Integer => value.parse::<i64>().ok().map(|n| n.to_string()),
+            ColumnType::Float => value
+                .parse::<f64>()
+                .ok()
+                .filter(|n| n.is_finite())
+                .map(|_| value.to_string()),
+            ColumnType::String => {
+                let value = value.replace('\'', "''");
+                Some(match self {
+                    // MySQL reads backslashes as escapes by default.
+                    Self::Mysql => format!("'{}'", value.replace('\\', "\\\\")),
+                    _ => format!("'{value}'"),
+                })
+            }
+        }
+    }
+}
+
+/// Returns the name, type and nullability of each column, with `column_types` overriding the
+/// inferred types.
+pub(crate) fn sql_columns(
+    file_path: Option<&Path>,
+    file_data: Option<&str>,
+    column_types: &HashMap<String, ColumnType>,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<Vec<InferredColumn>> {
+    let rdr = ReaderBuilder::new();
+    let mut columns = infer_columns(
+        &mut reader_from(&rdr, file_path, file_data, encoding)?,
+        error_policy,
+    )?;
+    // Column names are case-insensitive in SQLite and MySQL.
+    let mut names = HashSet::new();
+    if let Some(column) = columns
+        .iter()
+        .find(|column| column.name.is_empty() || !names.insert(column.name.to_lowercase()))
+    {
+        return Err(CzvError::InvalidOptions(match column.name.is_empty() {
+            true => "Column names may not be empty.".to_string(),
+            false => format!(
+                "Column {:?} is in the header row more than once.",
+                column.name
+            ),
+        }));
+    }
+    if let Some(name) = column_types
+        .keys()
+        .find(|name| !columns.iter().any(|column| &column.name == *name))
+    {
+        return Err(CzvError::ColumnNotFound(name.clone()));
+    }
+    for column in &mut columns {
+        if let Some(column_type) = column_types.get(&column.name) {
+            column.column_type = *column_type;
+        }
+    }
+    Ok(columns)
+}
+
+/// Returns `table_name`, or the file name without its extension if it is not set.
+pub(crate) fn table_name_or_default(
+    table_name: Option<String>,
+    file_path: Option<&Path>,
+) -> String {
+    table_name
+        .or_else(|| {
+            file_path
+                .and_then(Path::file_stem)
+                .map(|stem| stem.to_string_lossy().into_owned())
+        })
+        .unwrap_or_else(|| DEFAULT_TABLE_NAME.to_string())
+}
+
+/// Returns the parse error for a `value` of `record` that cannot be read as its column's type.
+pub(crate) fn type_mismatch(
+    value: &str,
+    column: &InferredColumn,
+    record: &StringRecord,
+) -> CzvError {
+    CzvError::parse_at(
+        format!(
+            "Value {value:?} in column {:?} is not a valid {}.",
+            column.name, column.column_type
+        ),
+        record,
+    )
+}
+
+/// Returns a `CREATE TABLE` statement for `columns`, which are `NOT NULL` unless they are nullable.
+pub(crate) fn create_table_statement(
+    dialect: SqlDialect,
+    table_name: &str,
+    columns: &[InferredColumn],
+    if_not_exists: bool,
+) -> String {
+    let if_not_exists = if if_not_exists { "IF NOT EXISTS " } else { "" };
+    let columns: Vec<String> = columns
+        .iter()
+        .map(|column| {
+            let not_null = if column.nullable { "" } else { " NOT NULL" };
+            format!(
+                "  {} {}{not_null}",
+                dialect.quote_identifier(&column.name),
+                dialect.column_type(column.column_type),
+            )
+        })
+        .collect();
+    format!(
+        "CREATE TABLE {if_not_exists}{} (\n{}\n);",
+        dialect.quote_identifier(table_name),
+        columns.join(",\n")
+    )
+}
+
+/// Returns the literals of `record`, or a parse error if a value does not match its column type.
+fn record_literals(
+    dialect: SqlDialect,
+    columns: &[InferredColumn],
+    record: &StringRecord,
+) -> Result<Vec<String>> {
+    record
+        .iter()
+        .zip(columns)
+        .map(|(value, column)| {
+            dialect
+                .literal(value, column.column_type)
+                .ok_or_else(|| type_mismatch(value, column, record))
+        })
+        .collect()
+}
+
+#[allow(clippy::too_many_arguments)]
+fn write_sql<W: Write>(
+    file_path: Option<&Path>,
+    file_data: Option<&str>,
+    table_name: Option<String>,
+    dialect: SqlDialect,
+    column_types: &HashMap<String, ColumnType>,
+    create_table: bool,
+    batch_size: usize,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+    mut wtr: W,
+) -> Result<Report<()>> {
+    if batch_size == 0 {
+        return Err(CzvError::InvalidOptions(
+            "batch_size must be greater than 0.".to_string(),
+        ));
+    }
+    let table_name = table_name_or_default(table_name, file_path);
+    let columns = sql_columns(file_path, file_data, column_types, error_policy, encoding)?;
+
+    if create_table {
+        writeln!(
+            wtr,
+            "{}",
+            create_table_statement(dialect, &table_name, &columns, false)
+        )?;
+    }
+
+    let insert = format!(
+        "INSERT INTO {} ({}) VALUES",
+        dialect.quote_identifier(&table_name),
+        columns
+            .iter()
+            .map(|column| dialect.quote_identifier(&column.name))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    let rdr = ReaderBuilder::new();
+    let mut rdr = reader_from(&rdr, file_path, file_data, encoding)?;
+    let mut skipper = Skipper::new(error_policy);
+    let mut record = StringRecord::new();
+    let mut batch_rows = 0;
+    while skipper.read_record(&mut rdr, &mut record)? {
+        let literals = match record_literals(dialect, &columns, &record) {
+            Ok(literals) => literals,
+            Err(err) => {
+                skipper.skip(err)?;
+                continue;
+            }
+        };
+        if batch_rows == 0 {
+            writeln!(wtr, "{insert}")?;
+        } else {
+            writeln!(wtr, ",")?;
+        }
+        write!(wtr, "  ({})", literals.join(", "))?;
+        batch_rows += 1;
+        if batch_rows == batch_size {
+            writeln!(wtr, ";")?;
+            batch_rows = 0;
+        }
+    }
+    if batch_rows > 0 {
+        writeln!(wtr, ";")?;
+    }
+    wtr.flush()?;
+
+    Ok(skipper.finish(()))
+}
+
+/// Returns a SQL script creating a table for the CSV data and inserting its rows. Recommended alternative: [`czv::sql::ToSql`](crate::sql::ToSql).
+///
+/// See [`czv::sql::ToSql`](crate::sql::ToSql) for a builder version (recommended) of this function.
+///
+/// ## Notes
+///
+/// - The table is named `table_name`, otherwise after the file name without its extension, or
+///   `data` for `file_data`. Table and column names are quoted for `dialect`.
+/// - Each column's type comes from `column_types` if the column is named there, otherwise it is
+///   inferred from the whole column, which reads the input twice. Columns without empty values are
+///   `NOT NULL`.
+/// - Header names must be non-empty and unique, ignoring case.
+/// - Empty values are inserted as `NULL`. Text values are quoted with their quotes escaped, and for
+///   MySQL their backslashes too.
+/// - The rows are inserted with one `INSERT` statement per `batch_size` rows.
+/// - Records with a value that does not match its column type are malformed. They are left out of
+///   the output when skipped with `error_policy`.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+///
+/// ## Arguments
+///
+/// * `file_path` - CSV file path (alternative to `file_data`).
+/// * `file_data` - CSV file data (alternative to `file_path`).
+/// * `table_name` - The name of the table.
+/// * `dialect` - The SQL dialect of the script.
+/// * `column_types` - Column types by header name, overriding the inferred types.
+/// * `create_table` - Specify whether to start the script with a `CREATE TABLE` statement.
+/// * `batch_size` - The maximum number of rows in an `INSERT` statement.
+/// * `error_policy` - How to handle malformed records.
+/// * `encoding` - The character encoding of `file_path`.
+#[allow(clippy::too_many_arguments)]
+pub fn to_sql(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    table_name: Option<String>,
+    dialect: SqlDialect,
+    column_types: HashMap<String, ColumnType>,
+    create_table: bool,
+    batch_size: usize,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<String> {
+    let mut output = vec![];
+    write_sql(
+        file_path.as_deref(),
+        file_data.as_deref(),
+        table_name,
+        dialect,
+        &column_types,
+        create_table,
+        batch_size,
+        error_policy,
+        encoding,
+        &mut output,
+    )?;
+    String::from_utf8(output).map_err(|err| CzvError::parse(err.to_string()))
+}
+
+#[derive(TypedBuilder)]
+#[builder(doc, builder_method(name=new, doc="Returns a [`ToSqlBuilder`](crate::sql::ToSqlBuilder) to customize SQL script options by running other methods before getting the script with the [`execute`](crate::sql::ToSqlBuilder::execute) method."), build_method(vis="", name=__build))]
+/// Returns a SQL script creating a table for the CSV data and inserting its rows.
+///
+/// The entry point for setting up a [`czv::sql::ToSqlBuilder`](crate::sql::ToSqlBuilder) by running [`ToSql::new()`](crate::sql::ToSql::new).
+///
+/// # Example
+///
+/// ```rust
+/// use czv::sql::SqlDialect;
+/// use czv::{Result, ToSql};
+///
+/// fn main() -> Result<()> {
+///     let data = "\
+/// fruit,price,note
+/// apple,2.50,
+/// banana,3,O'Neil's
+/// ";
+///     let output = ToSql::new()
+///         .file_data(data)
+///         .table_name("fruits")
+///         .dialect(SqlDialect::Postgres)
+///         .execute()?;
+///     assert_eq!(
+///         output,
+///         r#"CREATE TABLE "fruits" (
+///   "fruit" TEXT NOT NULL,
+///   "price" DOUBLE PRECISION NOT NULL,
+///   "note" TEXT
+/// );
+/// INSERT INTO "fruits" ("fruit", "price", "note") VALUES
+///   ('apple', 2.50, NULL),
+///   ('banana', 3, 'O''Neil''s');
+/// "#
+///     );
+///     Ok(())
+/// }
+/// ```
+///
+/// For large files use [`execute_to_writer`](crate::sql::ToSqlBuilder::execute_to_writer) to
+/// stream the script instead of holding it in memory.
+///
+/// ## Notes
+///
+/// - See [`to_sql`](crate::sql::to_sql) for how the table and values are written.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+pub struct ToSql {
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file path (alternative to `file_data`).",
+            strip_option(fallback = file_path_opt),
+            into
+        )
+    )]
+    file_path: Option<PathBuf>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file data (alternative to `file_path`).",
+            strip_option(fallback = file_data_opt),
+            into
+        )
+    )]
+    file_data: Option<String>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "The name of the table. Defaults to the file name without its extension, or `data` for `file_data`.",
+            strip_option(fallback = table_name_opt),
+            into
+        )
+    )]
+    table_name: Option<String>,
+
+    #[builder(
+        default,
+        setter(doc = "The SQL dialect of the script. Defaults to `SqlDialect::Sqlite`.")
+    )]
+    dialect: SqlDialect,
+
+    #[builder(
+        default,
+        setter(
+            doc = "Column types by header name, overriding the inferred types.",
+            transform = |column_types: impl IntoIterator<Item = (String, ColumnType)>| column_types.into_iter().collect()
+        )
+    )]
+    column_types: HashMap<String, ColumnType>,
+
+    #[builder(
+        default = true,
+        setter(
+            doc = "Specify whether to start the script with a `CREATE TABLE` statement. Defaults to `true`."
+        )
+    )]
+    create_table: bool,
+
+    #[builder(
+        default = 500,
+        setter(doc = "The maximum number of rows in an `INSERT` statement. Defaults to 500.")
+    )]
+    batch_size: usize,
+
+    #[builder(
+        default,
+        setter(doc = "How to handle malformed records. Defaults to `ErrorPolicy::Fail`.")
+    )]
+    error_policy: ErrorPolicy,
+
+    #[builder(
+        default,
+        setter(doc = "The character encoding of `file_path`. Defaults to `Encoding::Utf8`.")
+    )]
+    encoding: Encoding,
+}
+
+#[allow(non_camel_case_types)]
+impl<
+        __encoding: typed_builder::Optional<Encoding>,
+        __error_policy: typed_builder::Optional<ErrorPolicy>,
+        __batch_size: typed_builder::Optional<usize>,
+        __create_table: typed_builder::Optional<bool>,
+        __column_types: typed_builder::Optional<HashMap<String, ColumnType>>,
+        __dialect: typed_builder::Optional<SqlDialect>,
+        __table_name: typed_builder::Optional<Option<String>>,
+        __file_data: typed_builder::Optional<Option<String>>,
+        __file_path: typed_builder::Optional<Option<PathBuf>>,
+    >
+    ToSqlBuilder<(
+        __file_path,
+        __file_data,
+        __table_name,
+        __dialect,
+        __column_types,
+        __create_table,
+        __batch_size,
+        __error_policy,
+        __encoding,
+    )>
+{
+    /// Returns the SQL script.
+    pub fn execute(self) -> Result<String> {
+        Ok(self.execute_with_report()?.output)
+    }
+
+    /// Returns the SQL script along with the records skipped with `ErrorPolicy::Collect`.
+    pub fn execute_with_report(self) -> Result<Report<String>> {
+        let mut output = vec![];
+        let report = self.execute_to_writer(&mut output)?;
+        let output = String::from_utf8(output).map_err(|err| CzvError::parse(err.to_string()))?;
+        Ok(Report {
+            output,
+            skipped: report.skipped,
+        })
+    }
+
+    /// Writes the SQL script to `wtr` as each row is converted, returning the records skipped with `ErrorPolicy::Collect`.
+    pub fn execute_to_writer<W: Write>(self, wtr: W) -> Result<Report<()>> {
+        let builder = self.__build();
+        write_sql(
+            builder.file_path.as_deref(),
+            builder.file_data.as_deref(),
+            builder.table_name,
+            builder.dialect,
+            &builder.column_types,
+            builder.create_table,
+            builder.batch_size,
+            builder.error_policy,
+            builder.encoding,
+            wtr,
+        )
+    }
+}
@@ -0,0 +1,449 @@
+use crate::encoding::Encoding;
+use crate::infer::{ColumnType, InferredColumn};
+use crate::policy::{ErrorPolicy, Report, Skipper};
+use crate::sql::{
+    create_table_statement, sql_columns, table_name_or_default, type_mismatch, SqlDialect,
+};
+use crate::util::reader_from;
+use crate::{CzvError, Result};
+use csv::{ReaderBuilder, StringRecord};
+use rusqlite::types::Value;
+use rusqlite::Connection;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use typed_builder::TypedBuilder;
+
+/// What to do when the table already exists in the database.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IfExists {
+    /// Return an error without changing the database. This is the default.
+    #[default]
+    Fail,
+    /// Drop the table and create it again.
+    Replace,
+    /// Insert the rows into the existing table.
+    Append,
+}
+
+impl FromStr for IfExists {
+    type Err = CzvError;
+
+    /// Parses an option name, e.g. `replace`.
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "fail" => Ok(Self::Fail),
+            "replace" => Ok(Self::Replace),
+            "append" => Ok(Self::Append),
+            _ => Err(CzvError::InvalidOptions(format!(
+                "Unknown if_exists option {s:?}, expected fail, replace or append."
+            ))),
+        }
+    }
+}
+
+/// Returns the SQLite values of `record`, or a parse error if a value does not match its column type.
+fn record_values(columns: &[InferredColumn], record: &StringRecord) -> Result<Vec<Value>> {
+    record
+        .iter()
+        .zip(columns)
+        .map(|(value, column)| {
+            if value.is_empty() {
+                return Ok(Value::Null);
+            }
+            let parsed = match column.column_type {
+                ColumnType::Boolean => match value.to_ascii_lowercase().as_str() {
+                    "true" => Some(Value::Integer(1)),
+                    "false" => Some(Value::Integer(0)),
+                    _ => None,
+                },
+                ColumnType::Integer => value.parse().ok().map(Value::Integer),
+                ColumnType::Float => value
+                    .parse()
+                    .ok()
+                    .filter(|n: &f64| n.is_finite())
+                    .map(Value::Real),
+                ColumnType::String => Some(Value::Text(value.to_string())),
+            };
+            parsed.ok_or_else(|| type_mismatch(value, column, record))
+        })
+        .collect()
+}
+
+/// Inserts the CSV records into the table with `columns`, committing a transaction every
+/// `batch_size` rows, and returns the number of rows inserted.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn insert_records(
+    conn: &mut Connection,
+    table_name: &str,
+    columns: &[InferredColumn],
+    file_path: Option<&Path>,
+    file_data: Option<&str>,
+    batch_size: usize,
+    skipper: &mut Skipper,
+    encoding: Encoding,
+) -> Result<usize> {
+    let dialect = SqlDialect::Sqlite;
+    let insert = format!(
+        "INSERT INTO {} ({}) VALUES ({})",
+        dialect.quote_identifier(table_name),
+        columns
+            .iter()
+            .map(|column| dialect.quote_identifier(&column.name))
+            .collect::<Vec<_>>()
+            .join(", "),
+        vec!["?"; columns.len()].join(", ")
+    );
+    let rdr = ReaderBuilder::new();
+    let mut rdr = reader_from(&rdr, file_path, file_data, encoding)?;
+    let mut record = StringRecord::new();
+    let mut inserted = 0;
+    let mut done = false;
+    while !done {
+        let tx = conn.transaction()?;
+        {
+            let mut statement = tx.prepare_cached(&insert)?;
+            let mut rows = 0;
+            while rows < batch_size {
+                if !skipper.read_record(&mut rdr, &mut record)? {
+                    done = true;
+                    break;
+                }
+                match record_values(columns, &record) {
+                    Ok(values) => {
+                        statement.execute(rusqlite::params_from_iter(values))?;
+                        rows += 1;
+                    }
+                    Err(err) => skipper.skip(err)?,
+                }
+            }
+            inserted += rows;
+        }
+        tx.commit()?;
+    }
+    Ok(inserted)
+}
+
+/// Returns the number of rows inserted into a SQLite database table from the CSV data. Recommended alternative: [`czv::sqlite::ToSqlite`](crate::sqlite::ToSqlite).
+///
+/// See [`czv::sqlite::ToSqlite`](crate::sqlite::ToSqlite) for a builder version (recommended) of this function.
+///
+/// ## Notes
+///
+/// - The database file at `database_path` is created if it does not exist.
+/// - The table is named `table_name`, otherwise after the file name without its extension, or
+///   `data` for `file_data`. `if_exists` sets what happens when the table already exists.
+///   Rows are only appended to a table with the same column names as the header row, in any order.
+/// - Each column's type comes from `column_types` if the column is named there, otherwise it is
+///   inferred from the whole column, which reads the input twice. Booleans are stored as `1` and
+///   `0`, and empty values as `NULL`.
+/// - Rows are inserted in transactions of `batch_size` rows. If an error stops the operation, the
+///   rows of the transactions committed before it stay in the table.
+/// - An index is created on each column in `indexes` after the rows are inserted.
+/// - Records with a value that does not match its column type are malformed. They are not inserted
+///   when skipped with `error_policy`.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+///
+/// ## Arguments
+///
+/// * `file_path` - CSV file path (alternative to `file_data`).
+/// * `file_data` - CSV file data (alternative to `file_path`).
+/// * `database_path` - SQLite database file path.
+/// * `table_name` - The name of the table.
+/// * `column_types` - Column types by header name, overriding the inferred types.
+/// * `indexes` - The header names of the columns to index.
+/// * `if_exists` - What to do when the table already exists.
+/// * `batch_size` - The maximum number of rows inserted in a transaction.
+/// * `error_policy` - How to handle malformed records.
+/// * `encoding` - The character encoding of `file_path`.
+#[allow(clippy::too_many_arguments)]
+pub fn to_sqlite(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    database_path: Option<PathBuf>,
+    table_name: Option<String>,
+    column_types: HashMap<String, ColumnType>,
+    indexes: Vec<String>,
+    if_exists: IfExists,
+    batch_size: usize,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<usize> {
+    Ok(to_sqlite_with_report(
+        file_path,
+        file_data,
+        database_path,
+        table_name,
+        column_types,
+        indexes,
+        if_exists,
+        batch_size,
+        error_policy,
+        encoding,
+    )?
+    .output)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn to_sqlite_with_report(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    database_path: Option<PathBuf>,
+    table_name: Option<String>,
+    column_types: HashMap<String, ColumnType>,
+    indexes: Vec<String>,
+    if_exists: IfExists,
+    batch_size: usize,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<Report<usize>> {
+    let database_path = database_path
+        .ok_or_else(|| CzvError::InvalidOptions("Must provide database_path.".to_string()))?;
+    if batch_size == 0 {
+        return Err(CzvError::InvalidOptions(
+            "batch_size must be greater than 0.".to_string(),
+        ));
+    }
+    let file_path = file_path.as_deref();
+    let file_data = file_data.as_deref();
+    let dialect = SqlDialect::Sqlite;
+    let table_name = table_name_or_default(table_name, file_path);
+    let columns = sql_columns(file_path, file_data, &column_types, error_policy, encoding)?;
+    if let Some(name) = indexes
+        .iter()
+        .find(|name| !columns.iter().any(|column| &column.name == *name))
+    {
+        return Err(CzvError::ColumnNotFound(name.clone()));
+    }
+
+    let mut conn = Connection::open(database_path)?;
+    let quoted_table_name = dialect.quote_identifier(&table_name);
+    let tx = conn.transaction()?;
+    let exists: bool = tx.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1)",
+        [&table_name],
+        |row| row.get(0),
+    )?;
+    match (if_exists, exists) {
+        (IfExists::Fail, true) => {
+            return Err(CzvError::InvalidOptions(format!(
+                "Table {table_name:?} already exists."
+            )))
+        }
+        (IfExists::Replace, true) => {
+            tx.execute(&format!("DROP TABLE {quoted_table_name}"), [])?;
+        }
+        (IfExists::Append, true) => {
+            let existing = tx
+                .prepare("SELECT name FROM pragma_table_info(?1)")?
+                .query_map([&table_name], |row| row.get::<_, String>(0))?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            let matches = existing.len() == columns.len()
+                && columns.iter().all(|column| {
+                    existing
+                        .iter()
+                        .any(|name| name.eq_ignore_ascii_case(&column.name))
+                });
+            if !matches {
+                return Err(CzvError::InvalidOptions(format!(
+                    "The columns of table {table_name:?} are {existing:?}, which do not match the header row."
+                )));
+            }
+        }
+        _ => {}
+    }
+    tx.execute(
+        &create_table_statement(dialect, &table_name, &columns, true),
+        [],
+    )?;
+    tx.commit()?;
+
+    let mut skipper = Skipper::new(error_policy);
+    let inserted = insert_records(
+        &mut conn,
+        &table_name,
+        &columns,
+        file_path,
+        file_data,
+        batch_size,
+        &mut skipper,
+        encoding,
+    )?;
+
+    for name in &indexes {
+        conn.execute(
+            &format!(
+                "CREATE INDEX IF NOT EXISTS {} ON {quoted_table_name} ({})",
+                dialect.quote_identifier(&format!("{table_name}_{name}_idx")),
+                dialect.quote_identifier(name)
+            ),
+            [],
+        )?;
+    }
+
+    Ok(skipper.finish(inserted))
+}
+
+#[derive(TypedBuilder)]
+#[builder(doc, builder_method(name=new, doc="Returns a [`ToSqliteBuilder`](crate::sqlite::ToSqliteBuilder) to customize SQLite loading options by running other methods before inserting the rows with the [`execute`](crate::sqlite::ToSqliteBuilder::execute) method."), build_method(vis="", name=__build))]
+/// Returns the number of rows inserted into a SQLite database table from the CSV data.
+///
+/// The entry point for setting up a [`czv::sqlite::ToSqliteBuilder`](crate::sqlite::ToSqliteBuilder) by running [`ToSqlite::new()`](crate::sqlite::ToSqlite::new).
+///
+/// # Example
+///
+/// ```rust
+/// use czv::sqlite::IfExists;
+/// use czv::{Result, ToSqlite};
+///
+/// fn main() -> Result<()> {
+///     let database_path = std::env::temp_dir()
+///         .join(format!("czv-doctest-fruits-{}.db", std::process::id()));
+///     let inserted = ToSqlite::new()
+///         .file_path("tests/resources/fruits.csv")
+///         .database_path(&database_path)
+///         .indexes(["fruit".to_string()])
+///         .if_exists(IfExists::Replace)
+///         .execute()?;
+///     assert_eq!(inserted, 3);
+///     std::fs::remove_file(database_path)?;
+///     Ok(())
+/// }
+/// ```
+///
+/// ## Notes
+///
+/// - See [`to_sqlite`](crate::sqlite::to_sqlite) for how the table is created and loaded.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+pub struct ToSqlite {
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file path (alternative to `file_data`).",
+            strip_option(fallback = file_path_opt),
+            into
+        )
+    )]
+    file_path: Option<PathBuf>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file data (alternative to `file_path`).",
+            strip_option(fallback = file_data_opt),
+            into
+        )
+    )]
+    file_data: Option<String>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "SQLite database file path, created if it does not exist.",
+            strip_option(fallback = database_path_opt),
+            into
+        )
+    )]
+    database_path: Option<PathBuf>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "The name of the table. Defaults to the file name without its extension, or `data` for `file_data`.",
+            strip_option(fallback = table_name_opt),
+            into
+        )
+    )]
+    table_name: Option<String>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "Column types by header name, overriding the inferred types.",
+            transform = |column_types: impl IntoIterator<Item = (String, ColumnType)>| column_types.into_iter().collect()
+        )
+    )]
+    column_types: HashMap<String, ColumnType>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "The header names of the columns to index.",
+            transform = |indexes: impl IntoIterator<Item = String>| indexes.into_iter().collect()
+        )
+    )]
+    indexes: Vec<String>,
+
+    #[builder(
+        default,
+        setter(doc = "What to do when the table already exists. Defaults to `IfExists::Fail`.")
+    )]
+    if_exists: IfExists,
+
+    #[builder(
+        default = 10_000,
+        setter(doc = "The maximum number of rows inserted in a transaction. Defaults to 10000.")
+    )]
+    batch_size: usize,
+
+    #[builder(
+        default,
+        setter(doc = "How to handle malformed records. Defaults to `ErrorPolicy::Fail`.")
+    )]
+    error_policy: ErrorPolicy,
+
+    #[builder(
+        default,
+        setter(doc = "The character encoding of `file_path`. Defaults to `Encoding::Utf8`.")
+    )]
+    encoding: Encoding,
+}
+
+#[allow(non_camel_case_types)]
+impl<
+        __encoding: typed_builder::Optional<Encoding>,
+        __error_policy: typed_builder::Optional<ErrorPolicy>,
+        __batch_size: typed_builder::Optional<usize>,
+        __if_exists: typed_builder::Optional<IfExists>,
+        __indexes: typed_builder::Optional<Vec<String>>,
+        __column_types: typed_builder::Optional<HashMap<String, ColumnType>>,
+        __table_name: typed_builder::Optional<Option<String>>,
+        __database_path: typed_builder::Optional<Option<PathBuf>>,
+        __file_data: typed_builder::Optional<Option<String>>,
+        __file_path: typed_builder::Optional<Option<PathBuf>>,
+    >
+    ToSqliteBuilder<(
+        __file_path,
+        __file_data,
+        __database_path,
+        __table_name,
+        __column_types,
+        __indexes,
+        __if_exists,
+        __batch_size,
+        __error_policy,
+        __encoding,
+    )>
+{
+    /// Returns the number of rows inserted.
+    pub fn execute(self) -> Result<usize> {
+        Ok(self.execute_with_report()?.output)
+    }
+
+    /// Returns the number of rows inserted along with the records skipped with `ErrorPolicy::Collect`.
+    pub fn execute_with_report(self) -> Result<Report<usize>> {
+        let builder = self.__build();
+        to_sqlite_with_report(
+            builder.file_path,
+            builder.file_data,
+            builder.database_path,
+            builder.table_name,
+            builder.column_types,
+            builder.indexes,
+            builder.if_exists,
+            builder.batch_size,
+            builder.error_policy,
+            builder.encoding,
+        )
+    }
+}
@@ -0,0 +1,419 @@
+use crate::encoding::Encoding;
+use crate::infer::ColumnType;
+use crate::policy::{ErrorPolicy, Report, Skipper};
+use crate::util::reader_from;
+use crate::{CzvError, Result};
+use csv::{ReaderBuilder, StringRecord};
+use std::path::PathBuf;
+use std::str::FromStr;
+use typed_builder::TypedBuilder;
+
+/// The marker ending values cut to the maximum column width.
+const ELLIPSIS: char = '…';
+
+/// The format of a rendered table.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TableFormat {
+    /// Plain text with columns aligned by spaces and a dashed line under the header row. This is
+    /// the default.
+    #[default]
+    Text,
+    /// A GitHub-flavored Markdown table.
+    Markdown,
+    /// An HTML `<table>` element.
+    Html,
+}
+
+impl FromStr for TableFormat {
+    type Err = CzvError;
+
+    /// Parses a format name, e.g. `markdown`.
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "markdown" => Ok(Self::Markdown),
+            "html" => Ok(Self::Html),
+            _ => Err(CzvError::InvalidOptions(format!(
+                "Unknown table format {s:?}, expected text, markdown or html."
+            ))),
+        }
+    }
+}
+
+/// The cells of a table, with numeric columns marked for right alignment.
+struct Cells {
+    rows: Vec<Vec<String>>,
+    numeric: Vec<bool>,
+    widths: Vec<usize>,
+}
+
+impl Cells {
+    /// Cuts the values to `max_width` and escapes them with `escape` before measuring the columns.
+    fn new(
+        headers: &StringRecord,
+        records: &[StringRecord],
+        max_width: Option<usize>,
+        escape: fn(&str) -> String,
+    ) -> Self {
+        let mut types: Vec<Option<ColumnType>> = vec![None; headers.len()];
+        for record in records {
+            for (column_type, value) in types.iter_mut().zip(record) {
+                if let Some(value_type) = ColumnType::of(value) {
+                    *column_type = Some(column_type.map_or(value_type, |t| t.merge(value_type)));
+                }
+            }
+        }
+        let numeric = types
+            .into_iter()
+            .map(|t| matches!(t, Some(ColumnType::Integer | ColumnType::Float)))
+            .collect();
+
+        let rows: Vec<Vec<String>> = std::iter::once(headers)
+            .chain(records)
+            .map(|record| {
+                record
+                    .iter()
+                    .map(|value| escape(&truncate(value, max_width)))
+                    .collect()
+            })
+            .collect();
+        let mut widths = vec![0; headers.len()];
+        for row in &rows {
+            for (width, value) in widths.iter_mut().zip(row) {
+                *width = (*width).max(value.chars().count());
+            }
+        }
+        Self {
+            rows,
+            numeric,
+            widths,
+        }
+    }
+
+    /// Pads `value` to the width of `column`, at least `min_width` characters.
+    fn pad(&self, column: usize, value: &str, min_width: usize) -> String {
+        let width = self.widths[column].max(min_width);
+        if self.numeric[column] {
+            format!("{value:>width$}")
+        } else {
+            format!("{value:<width$}")
+        }
+    }
+}
+
+/// Cuts `value` to `max_width` characters, ending with an ellipsis, and puts it on one line.
+fn truncate(value: &str, max_width: Option<usize>) -> String {
+    let value = value.replace("\r\n", " ").replace(['\n', '\r'], " ");
+    match max_width {
+        Some(max_width) if value.chars().count() > max_width => value
+            .chars()
+            .take(max_width - 1)
+            .chain(std::iter::once(ELLIPSIS))
+            .collect(),
+        _ => value,
+    }
+}
+
+fn render_text(cells: &Cells) -> String {
+    let mut output = String::new();
+    let mut push_line = |values: Vec<String>| {
+        output.push_str(values.join("  ").trim_end());
+        output.push('\n');
+    };
+    for (i, row) in cells.rows.iter().enumerate() {
+        push_line(
+            row.iter()
+                .enumerate()
+                .map(|(column, value)| cells.pad(column, value, 0))
+                .collect(),
+        );
+        if i == 0 {
+            push_line(
+                cells
+                    .widths
+                    .iter()
+                    .map(|width| "-".repeat(*width))
+                    .collect(),
+            );
+        }
+    }
+    output
+}
+
+fn render_markdown(cells: &Cells) -> String {
+    let mut output = String::new();
+    let mut push_line = |values: Vec<String>| {
+        output.push_str(&format!("| {} |\n", values.join(" | ")));
+    };
+    for (i, row) in cells.rows.iter().enumerate() {
+        push_line(
+            row.iter()
+                .enumerate()
+                .map(|(column, value)| cells.pad(column, value, 3))
+                .collect(),
+        );
+        if i == 0 {
+            push_line(
+                cells
+                    .widths
+                    .iter()
+                    .zip(&cells.numeric)
+                    .map(|(width, numeric)| match numeric {
+                        true => format!("{}:", "-".repeat(width.max(&3) - 1)),
+                        false => "-".repeat(*width.max(&3)),
+                    })
+                    .collect(),
+            );
+        }
+    }
+    output
+}
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn render_html(cells: &Cells) -> String {
+    let row = |tag: &str, values: &[String]| {
+        let values: String = values
+            .iter()
+            .enumerate()
+            .map(|(column, value)| {
+                let style = match cells.numeric[column] {
+                    true => " style=\"text-align: right\"",
+                    false => "",
+                };
+                format!("<{tag}{style}>{value}</{tag}>")
+            })
+            .collect();
+        format!("    <tr>{values}</tr>\n")
+    };
+    let mut output = String::from("<table>\n  <thead>\n");
+    output.push_str(&row("th", &cells.rows[0]));
+    output.push_str("  </thead>\n  <tbody>\n");
+    for record in &cells.rows[1..] {
+        output.push_str(&row("td", record));
+    }
+    output.push_str("  </tbody>\n</table>\n");
+    output
+}
+
+/// Returns the CSV data rendered as a table. Recommended alternative: [`czv::table::Table`](crate::table::Table).
+///
+/// See [`czv::table::Table`](crate::table::Table) for a builder version (recommended) of this function.
+///
+/// ## Notes
+///
+/// - Columns whose values are all integers or floats are right-aligned.
+/// - Values longer than `max_width` characters are cut to `max_width` characters ending with `…`.
+///   Line breaks in values are replaced with spaces.
+/// - Markdown tables escape `|` in values, and HTML tables escape `&`, `<`, `>` and `"`.
+/// - The whole table is read into memory to align its columns.
+/// - Malformed records are left out of the table when skipped with `error_policy`.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+///
+/// ## Arguments
+///
+/// * `file_path` - CSV file path (alternative to `file_data`).
+/// * `file_data` - CSV file data (alternative to `file_path`).
+/// * `format` - The format of the table.
+/// * `max_width` - The maximum width of a column in characters.
+/// * `error_policy` - How to handle malformed records.
+/// * `encoding` - The character encoding of `file_path`.
+pub fn table(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    format: TableFormat,
+    max_width: Option<usize>,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<String> {
+    Ok(table_with_report(
+        file_path,
+        file_data,
+        format,
+        max_width,
+        error_policy,
+        encoding,
+    )?
+    .output)
+}
+
+fn table_with_report(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    format: TableFormat,
+    max_width: Option<usize>,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<Report<String>> {
+    if max_width == Some(0) {
+        return Err(CzvError::InvalidOptions(
+            "max_width must be greater than 0.".to_string(),
+        ));
+    }
+    let rdr = ReaderBuilder::new();
+    let mut rdr = reader_from(&rdr, file_path.as_deref(), file_data.as_deref(), encoding)?;
+    let headers = rdr.headers()?.clone();
+
+    let mut skipper = Skipper::new(error_policy);
+    let mut records = vec![];
+    let mut record = StringRecord::new();
+    while skipper.read_record(&mut rdr, &mut record)? {
+        records.push(record.clone());
+    }
+
+    let output = match format {
+        TableFormat::Text => {
+            render_text(&Cells::new(&headers, &records, max_width, str::to_string))
+        }
+        TableFormat::Markdown => {
+            render_markdown(&Cells::new(&headers, &records, max_width, |value| {
+                value.replace('|', "\\|")
+            }))
+        }
+        TableFormat::Html => render_html(&Cells::new(&headers, &records, max_width, escape_html)),
+    };
+    Ok(skipper.finish(output))
+}
+
+#[derive(TypedBuilder)]
+#[builder(doc, builder_method(name=new, doc="Returns a [`TableBuilder`](crate::table::TableBuilder) to customize table rendering options by running other methods before getting the table with the [`execute`](crate::table::TableBuilder::execute) method."), build_method(vis="", name=__build))]
+/// Returns the CSV data rendered as a table.
+///
+/// The entry point for setting up a [`czv::table::TableBuilder`](crate::table::TableBuilder) by running [`Table::new()`](crate::table::Table::new).
+///
+/// # Example
+///
+/// ```rust
+/// use czv::table::TableFormat;
+/// use czv::{Result, Table};
+///
+/// fn main() -> Result<()> {
+///     let data = "\
+/// fruit,price
+/// apple,2.50
+/// strawberry,1.50
+/// ";
+///     let output = Table::new().file_data(data).execute()?;
+///     assert_eq!(
+///         output,
+///         "\
+/// fruit       price
+/// ----------  -----
+/// apple        2.50
+/// strawberry   1.50
+/// "
+///     );
+///
+///     let output = Table::new()
+///         .file_data(data)
+///         .format(TableFormat::Markdown)
+///         .max_width(6)
+///         .execute()?;
+///     assert_eq!(
+///         output,
+///         "\
+/// | fruit  | price |
+/// | ------ | ----: |
+/// | apple  |  2.50 |
+/// | straw… |  1.50 |
+/// "
+///     );
+///     Ok(())
+/// }
+/// ```
+///
+/// ## Notes
+///
+/// - See [`table`](crate::table::table) for how values are aligned, cut and escaped.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+pub struct Table {
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file path (alternative to `file_data`).",
+            strip_option(fallback = file_path_opt),
+            into
+        )
+    )]
+    file_path: Option<PathBuf>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file data (alternative to `file_path`).",
+            strip_option(fallback = file_data_opt),
+            into
+        )
+    )]
+    file_data: Option<String>,
+
+    #[builder(
+        default,
+        setter(doc = "The format of the table. Defaults to `TableFormat::Text`.")
+    )]
+    format: TableFormat,
+
+    #[builder(
+        default,
+        setter(
+            doc = "The maximum width of a column in characters. Defaults to no maximum.",
+            strip_option(fallback = max_width_opt)
+        )
+    )]
+    max_width: Option<usize>,
+
+    #[builder(
+        default,
+        setter(doc = "How to handle malformed records. Defaults to `ErrorPolicy::Fail`.")
+    )]
+    error_policy: ErrorPolicy,
+
+    #[builder(
+        default,
+        setter(doc = "The character encoding of `file_path`. Defaults to `Encoding::Utf8`.")
+    )]
+    encoding: Encoding,
+}
+
+#[allow(non_camel_case_types)]
+impl<
+        __encoding: typed_builder::Optional<Encoding>,
+        __error_policy: typed_builder::Optional<ErrorPolicy>,
+        __max_width: typed_builder::Optional<Option<usize>>,
+        __format: typed_builder::Optional<TableFormat>,
+        __file_data: typed_builder::Optional<Option<String>>,
+        __file_path: typed_builder::Optional<Option<PathBuf>>,
+    >
+    TableBuilder<(
+        __file_path,
+        __file_data,
+        __format,
+        __max_width,
+        __error_policy,
+        __encoding,
+    )>
+{
+    /// Returns the rendered table.
+    pub fn execute(self) -> Result<String> {
+        Ok(self.execute_with_report()?.output)
+    }
+
+    /// Returns the rendered table along with the records skipped with `ErrorPolicy::Collect`.
+    pub fn execute_with_report(self) -> Result<Report<String>> {
+        let builder = self.__build();
+        table_with_report(
+            builder.file_path,
+            builder.file_data,
+            builder.format,
+            builder.max_width,
+            builder.error_policy,
+            builder.encoding,
+        )
+    }
+}
@@ -0,0 +1,225 @@
+use crate::encoding::Encoding;
+use crate::policy::{ErrorPolicy, Report, Skipper};
+use crate::util::reader_from;
+use crate::{CzvError, Result};
+use csv::{ByteRecord, ReaderBuilder, Writer};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use typed_builder::TypedBuilder;
+
+/// Returns the CSV data with its rows and columns swapped. Recommended alternative: [`czv::transpose::Transpose`](crate::transpose::Transpose).
+///
+/// See [`czv::transpose::Transpose`](crate::transpose::Transpose) for a builder version (recommended) of this function.
+///
+/// ## Notes
+///
+/// - The header row is treated as a regular row, so it becomes the first column of the output.
+/// - With `multipass` the input is read once per output row instead of holding every record in
+///   memory. The output is still returned whole, see
+///   [`execute_to_writer`](crate::transpose::TransposeBuilder::execute_to_writer) to stream it.
+/// - Malformed records are left out of the output when skipped with `error_policy`.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+///
+/// ## Arguments
+///
+/// * `file_path` - CSV file path (alternative to `file_data`).
+/// * `file_data` - CSV file data (alternative to `file_path`).
+/// * `multipass` - Specify whether to read the input once per output row instead of loading every record into memory.
+/// * `error_policy` - How to handle malformed records.
+/// * `encoding` - The character encoding of `file_path`.
+pub fn transpose(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    multipass: bool,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+) -> Result<String> {
+    let mut output = vec![];
+    write_transpose(
+        file_path.as_deref(),
+        file_data.as_deref(),
+        multipass,
+        error_policy,
+        encoding,
+        &mut output,
+    )?;
+    String::from_utf8(output).map_err(|err| CzvError::parse(err.to_string()))
+}
+
+fn write_transpose<W: Write>(
+    file_path: Option<&Path>,
+    file_data: Option<&str>,
+    multipass: bool,
+    error_policy: ErrorPolicy,
+    encoding: Encoding,
+    wtr: W,
+) -> Result<Report<()>> {
+    let mut rdr = ReaderBuilder::new();
+    rdr.has_headers(false);
+    let mut wtr = Writer::from_writer(wtr);
+    let mut skipper = Skipper::new(error_policy);
+
+    if multipass {
+        // Malformed records are collected on the first pass only.
+        let mut column_count = None;
+        for record in reader_from(&rdr, file_path, file_data, encoding)?.byte_records() {
+            if let Some(record) = skipper.check(record)? {
+                column_count.get_or_insert(record.len());
+            }
+        }
+        for i in 0..column_count.unwrap_or(0) {
+            let mut output_row = ByteRecord::new();
+            for record in reader_from(&rdr, file_path, file_data, encoding)?.byte_records() {
+                match record {
+                    Ok(record) => output_row.push_field(&record[i]),
+                    Err(err) if err.is_io_error() => return Err(err.into()),
+                    Err(_) => {}
+                }
+            }
+            wtr.write_byte_record(&output_row)?;
+        }
+    } else {
+        let mut records = vec![];
+        for record in reader_from(&rdr, file_path, file_data, encoding)?.byte_records() {
+            if let Some(record) = skipper.check(record)? {
+                records.push(record);
+            }
+        }
+        let column_count = records.first().map_or(0, |record| record.len());
+        for i in 0..column_count {
+            wtr.write_record(records.iter().map(|record| &record[i]))?;
+        }
+    }
+
+    wtr.flush()?;
+    Ok(skipper.finish(()))
+}
+
+#[derive(TypedBuilder)]
+#[builder(doc, builder_method(name=new, doc="Returns a [`TransposeBuilder`](crate::transpose::TransposeBuilder) to customize transpose options by running other methods before getting the transposed data with the [`execute`](crate::transpose::TransposeBuilder::execute) method."), build_method(vis="", name=__build))]
+/// Returns the CSV data with its rows and columns swapped.
+///
+/// The entry point for setting up a [`czv::transpose::TransposeBuilder`](crate::transpose::TransposeBuilder) by running [`Transpose::new()`](crate::transpose::Transpose::new).
+///
+/// # Example
+///
+/// ```rust
+/// use czv::{Result, Transpose};
+///
+/// fn main() -> Result<()> {
+///     let data = "\
+/// fruits,price
+/// apple,2.50
+/// banana,3.00
+/// ";
+///     let output = Transpose::new().file_data(data).execute()?;
+///     assert_eq!(output, "fruits,apple,banana\nprice,2.50,3.00\n");
+///     Ok(())
+/// }
+/// ```
+///
+/// For large files use `multipass` with [`execute_to_writer`](crate::transpose::TransposeBuilder::execute_to_writer)
+/// so that only one output row is held in memory at a time:
+///
+/// ```rust
+/// use czv::{Result, Transpose};
+///
+/// fn main() -> Result<()> {
+///     Transpose::new()
+///         .file_path("tests/resources/fruits.csv")
+///         .multipass(true)
+///         .execute_to_writer(std::io::stdout().lock())?;
+///     Ok(())
+/// }
+/// ```
+///
+/// ## Notes
+///
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+pub struct Transpose {
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file path (alternative to `file_data`).",
+            strip_option(fallback = file_path_opt),
+            into
+        )
+    )]
+    file_path: Option<PathBuf>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file data (alternative to `file_path`).",
+            strip_option(fallback = file_data_opt),
+            into
+        )
+    )]
+    file_data: Option<String>,
+
+    #[builder(
+        default = false,
+        setter(
+            doc = "Specify whether to read the input once per output row instead of loading every record into memory. Defaults to false."
+        )
+    )]
+    multipass: bool,
+
+    #[builder(
+        default,
+        setter(doc = "How to handle malformed records. Defaults to `ErrorPolicy::Fail`.")
+    )]
+    error_policy: ErrorPolicy,
+
+    #[builder(
+        default,
+        setter(doc = "The character encoding of `file_path`. Defaults to `Encoding::Utf8`.")
+    )]
+    encoding: Encoding,
+}
+
+#[allow(non_camel_case_types)]
+impl<
+        __encoding: typed_builder::Optional<Encoding>,
+        __error_policy: typed_builder::Optional<ErrorPolicy>,
+        __multipass: typed_builder::Optional<bool>,
+        __file_data: typed_builder::Optional<Option<String>>,
+        __file_path: typed_builder::Optional<Option<PathBuf>>,
+    >
+    TransposeBuilder<(
+        __file_path,
+        __file_data,
+        __multipass,
+        __error_policy,
+        __encoding,
+    )>
+{
+    /// Returns the transposed data.
+    pub fn execute(self) -> Result<String> {
+        Ok(self.execute_with_report()?.output)
+    }
+
+    /// Returns the transposed data along with the records skipped with `ErrorPolicy::Collect`.
+    pub fn execute_with_report(self) -> Result<Report<String>> {
+        let mut output = vec![];
+        let report = self.execute_to_writer(&mut output)?;
+        let output = String::from_utf8(output).map_err(|err| CzvError::parse(err.to_string()))?;
+        Ok(Report {
+            output,
+            skipped: report.skipped,
+        })
+    }
+
+    /// Writes the transposed data to `wtr` one output row at a time, returning the records skipped with `ErrorPolicy::Collect`.
+    pub fn execute_to_writer<W: Write>(self, wtr: W) -> Result<Report<()>> {
+        let builder = self.__build();
+        write_transpose(
+            builder.file_path.as_deref(),
+            builder.file_data.as_deref(),
+            builder.multipass,
+            builder.error_policy,
+            builder.encoding,
+            wtr,
+        )
+    }
+}
@@ -0,0 +1,68 @@
+use crate::encoding::{decode, Encoding};
+use crate::{CzvError, Result};
+use csv::{Reader, ReaderBuilder, StringRecord, Writer};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// Returns a UTF-8 reader over either `file_path` or `file_data`, only one may be specified.
+///
+/// The file is transcoded from `encoding`, while `file_data` is already text and only has its BOM
+/// stripped.
+pub(crate) fn source_from<'a>(
+    file_path: Option<&Path>,
+    file_data: Option<&'a str>,
+    encoding: Encoding,
+) -> Result<Box<dyn Read + 'a>> {
+    Ok(match (file_path, file_data) {
+        (Some(_), Some(_)) => {
+            return Err(CzvError::InvalidOptions(
+                "Cannot have both file_path and file_data, specify one only.".to_string(),
+            ))
+        }
+        (None, None) => {
+            return Err(CzvError::InvalidOptions(
+                "Must provide either file_path or file_data.".to_string(),
+            ))
+        }
+        (Some(file_path), None) => decode(Box::new(File::open(file_path)?), encoding)?,
+        (None, Some(file_data)) => Box::new(file_data.trim_start_matches('\u{feff}').as_bytes()),
+    })
+}
+
+/// Returns a CSV reader over either `file_path` or `file_data`, only one may be specified.
+pub(crate) fn reader_from<'a>(
+    rdr: &ReaderBuilder,
+    file_path: Option<&Path>,
+    file_data: Option<&'a str>,
+    encoding: Encoding,
+) -> Result<Reader<Box<dyn Read + 'a>>> {
+    Ok(rdr.from_reader(source_from(file_path, file_data, encoding)?))
+}
+
+/// Returns a CSV writer that writes into memory, see [`into_string`].
+pub(crate) fn writer() -> Writer<Vec<u8>> {
+    Writer::from_writer(vec![])
+}
+
+/// Flushes an in-memory CSV writer and returns its contents.
+pub(crate) fn into_string(wtr: Writer<Vec<u8>>) -> Result<String> {
+    let bytes = wtr.into_inner().map_err(|err| err.into_error())?;
+    String::from_utf8(bytes).map_err(|err| CzvError::parse(err.to_string()))
+}
+
+/// Returns the index of the column named `name`.
+pub(crate) fn column_index(headers: &StringRecord, name: &str) -> Result<usize> {
+    headers
+        .iter()
+        .position(|header| header == name)
+        .ok_or_else(|| CzvError::ColumnNotFound(name.to_string()))
+}
+
+/// Returns the index of each column named in `names`.
+pub(crate) fn column_indexes(headers: &StringRecord, names: &[String]) -> Result<Vec<usize>> {
+    names
+        .iter()
+        .map(|name| column_index(headers, name))
+        .collect()
+}
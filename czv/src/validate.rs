@@ -0,0 +1,341 @@
+use crate::encoding::Encoding;
+use crate::util::source_from;
+use crate::Result;
+use std::collections::HashSet;
+use std::fmt;
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
+use typed_builder::TypedBuilder;
+
+/// The kind of problem found by [`validate`](crate::validate::validate).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationProblemKind {
+    /// A record has a different number of fields than the header row.
+    UnequalLengths { expected: usize, found: usize },
+    /// A field contains bytes that are not valid UTF-8.
+    InvalidUtf8,
+    /// A header name is used by more than one column.
+    DuplicateHeader { name: String },
+    /// A quote appears inside a field that does not start with a quote.
+    StrayQuote,
+    /// A closing quote is followed by something other than a delimiter or line terminator.
+    UnexpectedAfterQuote,
+    /// A quoted field is not closed before the end of the data.
+    UnterminatedQuote,
+}
+
+/// A single problem found by [`validate`](crate::validate::validate).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationProblem {
+    /// What is wrong.
+    pub kind: ValidationProblemKind,
+    /// The record index (0-indexed, the header row is record 0).
+    pub record: u64,
+    /// The byte offset from the start of the data where the problem was found.
+    pub byte: u64,
+    /// The column index (0-indexed) the problem was found in, if it applies to a single field.
+    pub column: Option<usize>,
+}
+
+impl fmt::Display for ValidationProblemKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnequalLengths { expected, found } => {
+                write!(f, "expected {expected} fields but found {found}")
+            }
+            Self::InvalidUtf8 => write!(f, "invalid UTF-8"),
+            Self::DuplicateHeader { name } => write!(f, "duplicate header name {name:?}"),
+            Self::StrayQuote => write!(f, "quote inside an unquoted field"),
+            Self::UnexpectedAfterQuote => write!(f, "unexpected data after a closing quote"),
+            Self::UnterminatedQuote => write!(f, "quoted field is never closed"),
+        }
+    }
+}
+
+impl fmt::Display for ValidationProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "record {} (byte {})", self.record, self.byte)?;
+        if let Some(column) = self.column {
+            write!(f, ", column {column}")?;
+        }
+        write!(f, ": {}", self.kind)
+    }
+}
+
+/// A field as read by [`Scanner`] along with the byte offset it starts at.
+struct Field {
+    start: u64,
+    value: Vec<u8>,
+}
+
+/// A strict RFC 4180 reader that records problems instead of recovering from them silently.
+struct Scanner<R> {
+    rdr: R,
+    pos: u64,
+    record: u64,
+    problems: Vec<ValidationProblem>,
+}
+
+impl<R: BufRead> Scanner<R> {
+    fn peek(&mut self) -> Result<Option<u8>> {
+        let buf = self.rdr.fill_buf()?;
+        Ok(buf.first().copied())
+    }
+
+    fn bump(&mut self) {
+        self.rdr.consume(1);
+        self.pos += 1;
+    }
+
+    fn problem(&mut self, kind: ValidationProblemKind, byte: u64, column: Option<usize>) {
+        self.problems.push(ValidationProblem {
+            kind,
+            record: self.record,
+            byte,
+            column,
+        });
+    }
+
+    /// Reads the next record, skipping empty lines. Returns `None` at the end of the data.
+    fn next_record(&mut self) -> Result<Option<(u64, Vec<Field>)>> {
+        while let Some(b'\r' | b'\n') = self.peek()? {
+            self.bump();
+        }
+        if self.peek()?.is_none() {
+            return Ok(None);
+        }
+
+        let record_start = self.pos;
+        let mut fields = vec![];
+        loop {
+            let column = fields.len();
+            let mut field = Field {
+                start: self.pos,
+                value: vec![],
+            };
+
+            let quoted = self.peek()? == Some(b'"');
+            if quoted {
+                self.bump();
+                let mut closed = false;
+                while let Some(byte) = self.peek()? {
+                    self.bump();
+                    if byte != b'"' {
+                        field.value.push(byte);
+                    } else if self.peek()? == Some(b'"') {
+                        self.bump();
+                        field.value.push(b'"');
+                    } else {
+                        closed = true;
+                        break;
+                    }
+                }
+                if !closed {
+                    self.problem(
+                        ValidationProblemKind::UnterminatedQuote,
+                        field.start,
+                        Some(column),
+                    );
+                } else if !matches!(self.peek()?, None | Some(b',' | b'\r' | b'\n')) {
+                    let pos = self.pos;
+                    self.problem(
+                        ValidationProblemKind::UnexpectedAfterQuote,
+                        pos,
+                        Some(column),
+                    );
+                }
+            }
+
+            let mut stray_quote = false;
+            while let Some(byte) = self.peek()? {
+                if matches!(byte, b',' | b'\r' | b'\n') {
+                    break;
+                }
+                if byte == b'"' && !quoted && !stray_quote {
+                    stray_quote = true;
+                    let pos = self.pos;
+                    self.problem(ValidationProblemKind::StrayQuote, pos, Some(column));
+                }
+                field.value.push(byte);
+                self.bump();
+            }
+            fields.push(field);
+
+            match self.peek()? {
+                Some(b',') => self.bump(),
+                Some(b'\r') => {
+                    self.bump();
+                    if self.peek()? == Some(b'\n') {
+                        self.bump();
+                    }
+                    break;
+                }
+                Some(_) => {
+                    // b'\n'
+                    self.bump();
+                    break;
+                }
+                None => break,
+            }
+        }
+        Ok(Some((record_start, fields)))
+    }
+}
+
+/// Returns a list of structural problems found in the CSV data. Recommended alternative: [`czv::validate::Validate`](crate::validate::Validate).
+///
+/// See [`czv::validate::Validate`](crate::validate::Validate) for a builder version (recommended) of this function.
+///
+/// ## Notes
+///
+/// - The data is checked for RFC 4180 quoting, a consistent number of fields per record,
+///   valid UTF-8 and unique header names. An empty list means the data is valid.
+/// - Empty lines are skipped, as they are by the other operations.
+/// - Byte offsets are positions in the data after any BOM is stripped. When `encoding` is not
+///   UTF-8, they are positions in the data transcoded to UTF-8 rather than in the file.
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+///
+/// ## Arguments
+///
+/// * `file_path` - CSV file path (alternative to `file_data`).
+/// * `file_data` - CSV file data (alternative to `file_path`).
+/// * `encoding` - The character encoding of `file_path`.
+pub fn validate(
+    file_path: Option<PathBuf>,
+    file_data: Option<String>,
+    encoding: Encoding,
+) -> Result<Vec<ValidationProblem>> {
+    validate_reader(BufReader::new(source_from(
+        file_path.as_deref(),
+        file_data.as_deref(),
+        encoding,
+    )?))
+}
+
+fn validate_reader<R: BufRead>(rdr: R) -> Result<Vec<ValidationProblem>> {
+    let mut scanner = Scanner {
+        rdr,
+        pos: 0,
+        record: 0,
+        problems: vec![],
+    };
+    let mut expected_len = None;
+
+    while let Some((record_start, fields)) = scanner.next_record()? {
+        for (column, field) in fields.iter().enumerate() {
+            if let Err(err) = std::str::from_utf8(&field.value) {
+                let byte = field.start + err.valid_up_to() as u64;
+                scanner.problem(ValidationProblemKind::InvalidUtf8, byte, Some(column));
+            }
+        }
+
+        match expected_len {
+            None => {
+                expected_len = Some(fields.len());
+                let mut seen = HashSet::new();
+                for (column, field) in fields.iter().enumerate() {
+                    if !seen.insert(&field.value) {
+                        let name = String::from_utf8_lossy(&field.value).into_owned();
+                        scanner.problem(
+                            ValidationProblemKind::DuplicateHeader { name },
+                            field.start,
+                            Some(column),
+                        );
+                    }
+                }
+            }
+            Some(expected) if expected != fields.len() => {
+                let (byte, column) = match fields.get(expected) {
+                    Some(extra) => (extra.start, Some(expected)),
+                    None => (record_start, None),
+                };
+                scanner.problem(
+                    ValidationProblemKind::UnequalLengths {
+                        expected,
+                        found: fields.len(),
+                    },
+                    byte,
+                    column,
+                );
+            }
+            Some(_) => {}
+        }
+        scanner.record += 1;
+    }
+
+    Ok(scanner.problems)
+}
+
+#[derive(TypedBuilder)]
+#[builder(doc, builder_method(name=new, doc="Returns a [`ValidateBuilder`](crate::validate::ValidateBuilder) to customize validation options by running other methods before getting the problems with the [`execute`](crate::validate::ValidateBuilder::execute) method."), build_method(vis="", name=__build))]
+/// Returns a list of structural problems found in the CSV data.
+///
+/// The entry point for setting up a [`czv::validate::ValidateBuilder`](crate::validate::ValidateBuilder) by running [`Validate::new()`](crate::validate::Validate::new).
+///
+/// # Example
+///
+/// ```rust
+/// use czv::{Result, Validate};
+/// use czv::validate::ValidationProblemKind;
+///
+/// fn main() -> Result<()> {
+///     let data = "\
+/// fruits,price
+/// apple,2.50
+/// banana
+/// ";
+///     let problems = Validate::new().file_data(data).execute()?;
+///     assert_eq!(problems.len(), 1);
+///     assert_eq!(
+///         problems[0].kind,
+///         ValidationProblemKind::UnequalLengths { expected: 2, found: 1 }
+///     );
+///     println!("{}", problems[0]); // record 2 (byte 24): expected 2 fields but found 1
+///     Ok(())
+/// }
+/// ```
+///
+/// ## Notes
+///
+/// - You may not use `file_path` and `file_data` together, only one may be specified.
+pub struct Validate {
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file path (alternative to `file_data`).",
+            strip_option(fallback = file_path_opt),
+            into
+        )
+    )]
+    file_path: Option<PathBuf>,
+
+    #[builder(
+        default,
+        setter(
+            doc = "CSV file data (alternative to `file_path`).",
+            strip_option(fallback = file_data_opt),
+            into
+        )
+    )]
+    file_data: Option<String>,
+
+    #[builder(
+        default,
+        setter(doc = "The character encoding of `file_path`. Defaults to `Encoding::Utf8`.")
+    )]
+    encoding: Encoding,
+}
+
+#[allow(non_camel_case_types)]
+impl<
+        __encoding: typed_builder::Optional<Encoding>,
+        __file_data: typed_builder::Optional<Option<String>>,
+        __file_path: typed_builder::Optional<Option<PathBuf>>,
+    > ValidateBuilder<(__file_path, __file_data, __encoding)>
+{
+    /// Returns the problems found, which is empty if the data is valid.
+    pub fn execute(self) -> Result<Vec<ValidationProblem>> {
+        let builder = self.__build();
+        validate(builder.file_path, builder.file_data, builder.encoding)
+    }
+}
@@ -0,0 +1,122 @@
+use czv::aggregate::{AggregateFunction, Aggregation};
+use czv::{Aggregate, CzvError, Encoding, ErrorPolicy, Result};
+
+const SALES: &str = "\
+store,fruit,count,price
+north,apple,3,2.5
+south,apple,2,
+north,banana,4,3
+south,kiwi,10,0.5
+";
+
+#[test]
+fn test_aggregate() -> Result<()> {
+    let got = czv::aggregate::aggregate(
+        None,
+        Some(SALES.to_string()),
+        vec!["store".to_string()],
+        vec![
+            Aggregation::count(),
+            Aggregation::new(AggregateFunction::Sum, "count"),
+            Aggregation::new(AggregateFunction::Sum, "price"),
+            Aggregation::new(AggregateFunction::Mean, "price"),
+            Aggregation::new(AggregateFunction::Count, "price"),
+        ],
+        ",".to_string(),
+        ErrorPolicy::Fail,
+        Encoding::Utf8,
+    )?;
+    assert_eq!(
+        "\
+store,count,sum_count,sum_price,mean_price,count_price
+north,2,7,5.5,2.75,2
+south,2,12,0.5,0.5,1
+",
+        got
+    );
+    Ok(())
+}
+
+#[test]
+fn test_aggregate_functions() -> Result<()> {
+    let aggregations = [
+        "min(count)",
+        "max(count)",
+        "min(fruit)",
+        "first(price)",
+        "last(price)",
+        "distinct_count(fruit)",
+        "concat(fruit)",
+    ]
+    .iter()
+    .map(|s| s.parse())
+    .collect::<Result<Vec<Aggregation>>>()?;
+    let got = Aggregate::new()
+        .file_data(SALES)
+        .aggregations(aggregations)
+        .separator("|")
+        .execute()?;
+    assert_eq!(
+        "\
+min_count,max_count,min_fruit,first_price,last_price,distinct_count_fruit,concat_fruit
+2,10,apple,2.5,0.5,3,apple|apple|banana|kiwi
+",
+        got
+    );
+    Ok(())
+}
+
+#[test]
+fn test_aggregate_groups() -> Result<()> {
+    let got = Aggregate::new()
+        .file_data(SALES)
+        .group_by(["fruit".to_string(), "store".to_string()])
+        .execute()?;
+    assert_eq!(
+        "fruit,store\napple,north\napple,south\nbanana,north\nkiwi,south\n",
+        got
+    );
+
+    let got = Aggregate::new()
+        .file_data("a,b\n")
+        .aggregations([Aggregation::count(), "sum(b)".parse()?])
+        .execute()?;
+    assert_eq!("count,sum_b\n0,\n", got);
+    Ok(())
+}
+
+#[test]
+fn test_aggregate_errors() -> Result<()> {
+    let report = Aggregate::new()
+        .file_data("a,b\nx,1\ny,two\nx,3\n")
+        .group_by(["a".to_string()])
+        .aggregations([Aggregation::new(AggregateFunction::Mean, "b")])
+        .error_policy(ErrorPolicy::Collect)
+        .execute_with_report()?;
+    assert_eq!("a,mean_b\nx,2.0\n", report.output);
+    assert_eq!(1, report.skipped.len());
+    assert_eq!(Some(2), report.skipped[0].record);
+
+    let got = Aggregate::new()
+        .file_data(SALES)
+        .aggregations([Aggregation::new(AggregateFunction::Sum, "missing")])
+        .execute();
+    assert!(matches!(got, Err(CzvError::ColumnNotFound(name)) if name == "missing"));
+
+    let got = Aggregate::new()
+        .file_data("count,b\nx,1\n")
+        .group_by(["count".to_string()])
+        .aggregations([Aggregation::count()])
+        .execute();
+    assert!(matches!(got, Err(CzvError::InvalidOptions(_))));
+
+    assert!(matches!(
+        "median(price)".parse::<Aggregation>(),
+        Err(CzvError::InvalidOptions(_))
+    ));
+    assert!(matches!(
+        Aggregate::new().file_data(SALES).execute(),
+        Err(CzvError::InvalidOptions(_))
+    ));
+    Ok(())
+}
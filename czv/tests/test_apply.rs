@@ -0,0 +1,141 @@
+use czv::apply::{HashAlgorithm, Transform};
+use czv::{Apply, CzvError, Encoding, ErrorPolicy, Result};
+
+/// Returns the single value of `data` transformed by `transform`.
+fn apply_one(value: &str, transform: &str) -> Result<String> {
+    let output = Apply::new()
+        .file_data(format!("a\n\"{value}\"\n"))
+        .columns(["a".to_string()])
+        .transforms([transform.parse()?])
+        .execute()?;
+    Ok(output
+        .trim_start_matches("a\n")
+        .trim_end()
+        .trim_matches('"')
+        .to_string())
+}
+
+#[test]
+fn test_apply() -> Result<()> {
+    let got = czv::apply::apply(
+        None,
+        Some("name,city\n  ada   lovelace , london \n".to_string()),
+        vec!["name".to_string(), "city".to_string()],
+        vec![Transform::Trim, Transform::Upper],
+        vec![],
+        ErrorPolicy::Fail,
+        Encoding::Utf8,
+    )?;
+    assert_eq!("name,city\nADA   LOVELACE,LONDON\n", got);
+    Ok(())
+}
+
+#[test]
+fn test_apply_transforms() -> Result<()> {
+    let cases = [
+        (" a  b\tc ", "squeeze", "a b c"),
+        ("hELLO wORLD", "title", "Hello World"),
+        ("HeLLo", "lower", "hello"),
+        (
+            "2024-01-05",
+            "replace/(\\d+)-(\\d+)-(\\d+)/$3.$2.$1",
+            "05.01.2024",
+        ),
+        ("abcdef", "substring/2/3", "cde"),
+        ("abcdef", "substring/4", "ef"),
+        ("2.345", "round/1", "2.3"),
+        ("7", "round/2", "7.00"),
+        ("-1234567.5", "thousands", "-1,234,567.5"),
+        ("1234", "thousands/ ", "1 234"),
+        ("05/01/2024", "date|%d/%m/%Y|%Y-%m-%d", "2024-01-05"),
+        ("2024-01-05T10:20:30", "date|%H:%M", "10:20"),
+        ("2024-01-05T10:20:30+02:00", "date/%Y", "2024"),
+        ("", "round/2", ""),
+        ("abc", "hash/md5", "900150983cd24fb0d6963f7d28e17f72"),
+        (
+            "abc",
+            "hash/sha1",
+            "a9993e364706816aba3e25717850c26c9cd0d89d",
+        ),
+        (
+            "abc",
+            "hash",
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+        ),
+    ];
+    for (value, transform, expected) in cases {
+        assert_eq!(expected, apply_one(value, transform)?, "{transform}");
+    }
+    assert_eq!(
+        Transform::Hash(HashAlgorithm::Sha256),
+        "hash/SHA-256".parse()?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_apply_new_columns() -> Result<()> {
+    let got = Apply::new()
+        .file_data("a,b\nx,y\n")
+        .columns(["b".to_string(), "a".to_string()])
+        .transforms([Transform::Upper])
+        .new_columns(["b2".to_string(), "a2".to_string()])
+        .execute()?;
+    assert_eq!("a,b,b2,a2\nx,y,Y,X\n", got);
+
+    let got = Apply::new()
+        .file_data("a,b\nx,y\n")
+        .columns(["a".to_string(), "b".to_string()])
+        .transforms([Transform::Upper])
+        .new_columns(["a2".to_string()])
+        .execute();
+    assert!(matches!(got, Err(CzvError::InvalidOptions(_))));
+    Ok(())
+}
+
+#[test]
+fn test_apply_errors() -> Result<()> {
+    let report = Apply::new()
+        .file_data("n\n1.5\nabc\n2\n")
+        .columns(["n".to_string()])
+        .transforms([Transform::Round { decimals: 0 }])
+        .error_policy(ErrorPolicy::Collect)
+        .execute_with_report()?;
+    assert_eq!("n\n2\n2\n", report.output);
+    assert_eq!(1, report.skipped.len());
+    assert_eq!(
+        "Value \"abc\" in column \"n\" is not a number.",
+        report.skipped[0].reason
+    );
+
+    for transform in ["reverse", "round/x", "round/18", "substring", "hash/crc"] {
+        assert!(
+            matches!(
+                transform.parse::<Transform>(),
+                Err(CzvError::InvalidOptions(_))
+            ),
+            "{transform}"
+        );
+    }
+    let got = Apply::new()
+        .file_data("n\n1.5\n")
+        .columns(["n".to_string()])
+        .transforms([Transform::Round { decimals: 100 }])
+        .execute();
+    assert!(matches!(got, Err(CzvError::InvalidOptions(_))));
+    for transform in ["replace/(/x", "date/%Q"] {
+        let got = apply_one("x", transform);
+        assert!(
+            matches!(got, Err(CzvError::InvalidOptions(_))),
+            "{transform}"
+        );
+    }
+
+    let got = Apply::new()
+        .file_data("a\n1\n")
+        .columns(["b".to_string()])
+        .transforms([Transform::Trim])
+        .execute();
+    assert!(matches!(got, Err(CzvError::ColumnNotFound(name)) if name == "b"));
+    Ok(())
+}
@@ -0,0 +1,124 @@
+#![cfg(feature = "arrow")]
+
+use arrow::array::{Array, Float64Array, Int64Array, StringArray};
+use arrow::datatypes::DataType;
+use czv::infer::ColumnType;
+use czv::{CzvError, Encoding, ErrorPolicy, FromArrow, Result, ToArrow};
+use std::collections::HashMap;
+
+#[test]
+fn test_to_arrow() -> Result<()> {
+    let data = "id,zip,score,active,note\n1,02134,1.5,true,\n2,10001,,FALSE,hi\n";
+    let batches = czv::arrow::to_arrow(
+        None,
+        Some(data.into()),
+        HashMap::new(),
+        8192,
+        ErrorPolicy::Fail,
+        Encoding::Utf8,
+    )?;
+    assert_eq!(1, batches.len());
+    let schema = batches[0].schema();
+    let types = schema
+        .fields()
+        .iter()
+        .map(|field| field.data_type().clone())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            DataType::Int64,
+            DataType::Utf8,
+            DataType::Float64,
+            DataType::Boolean,
+            DataType::Utf8
+        ],
+        types
+    );
+    let ids = batches[0]
+        .column(0)
+        .as_any()
+        .downcast_ref::<Int64Array>()
+        .unwrap();
+    assert_eq!(vec![Some(1), Some(2)], ids.iter().collect::<Vec<_>>());
+    let scores = batches[0]
+        .column(2)
+        .as_any()
+        .downcast_ref::<Float64Array>()
+        .unwrap();
+    assert!(scores.is_null(1));
+    let notes = batches[0]
+        .column(4)
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    assert_eq!("", notes.value(0));
+    Ok(())
+}
+
+#[test]
+fn test_to_arrow_batch_size() -> Result<()> {
+    let data = (0..25).fold("n\n".to_string(), |data, n| {
+        data + format!("{n}\n").as_str()
+    });
+    let batches = ToArrow::new()
+        .file_data(data.as_str())
+        .batch_size(10)
+        .execute()?;
+    let rows = batches
+        .iter()
+        .map(|batch| batch.num_rows())
+        .collect::<Vec<_>>();
+    assert_eq!(vec![10, 10, 5], rows);
+    assert_eq!(data, FromArrow::new().batches(batches).execute()?);
+
+    let got = ToArrow::new().file_data("a\n1\n").batch_size(0).execute();
+    assert!(matches!(got, Err(CzvError::InvalidOptions(_))));
+    Ok(())
+}
+
+#[test]
+fn test_to_arrow_empty() -> Result<()> {
+    let batches = ToArrow::new().file_data("a,b\n").execute()?;
+    assert_eq!(1, batches.len());
+    assert_eq!(0, batches[0].num_rows());
+    assert_eq!(2, batches[0].num_columns());
+    assert_eq!("a,b\n", FromArrow::new().batches(batches).execute()?);
+    Ok(())
+}
+
+#[test]
+fn test_to_arrow_column_types() -> Result<()> {
+    let got = ToArrow::new()
+        .file_data("code,amount\n7,10\nx,20\n")
+        .column_types([("code".to_string(), ColumnType::Integer)])
+        .error_policy(ErrorPolicy::Collect)
+        .execute_with_report()?;
+    assert_eq!(1, got.output[0].num_rows());
+    assert_eq!(1, got.skipped.len());
+    assert_eq!(Some(2), got.skipped[0].record);
+    Ok(())
+}
+
+#[test]
+fn test_arrow_ipc_round_trip() -> Result<()> {
+    let mut ipc_data = vec![];
+    ToArrow::new()
+        .file_path("tests/resources/fruits.csv")
+        .column_types([("price".to_string(), ColumnType::String)])
+        .execute_to_ipc(&mut ipc_data)?;
+    let got = FromArrow::new().ipc_data(ipc_data).execute()?;
+    assert_eq!(
+        "fruit,price\napple,2.50\nbanana,3.00\nstrawberry,1.50\n",
+        got
+    );
+    Ok(())
+}
+
+#[test]
+fn test_from_arrow_batches_and_ipc_data() {
+    let got = FromArrow::new().batches(vec![]).ipc_data(vec![]).execute();
+    assert!(matches!(got, Err(CzvError::InvalidOptions(_))));
+
+    let got = FromArrow::new().ipc_data(b"not arrow".to_vec()).execute();
+    assert!(got.is_err());
+}
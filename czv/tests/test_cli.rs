@@ -0,0 +1,417 @@
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn czv(args: &[&str], stdin: Option<&str>) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_czv"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    if let Some(stdin) = stdin {
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(stdin.as_bytes())
+            .unwrap();
+    }
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn test_cli_count() {
+    let output = czv(&["count", "tests/resources/fruits.csv"], None);
+    assert!(output.status.success());
+    assert_eq!("3\n", stdout(&output));
+
+    let output = czv(
+        &["count", "--include-header-row", "-"],
+        Some("fruit,price\napple,2.50\n"),
+    );
+    assert_eq!("2\n", stdout(&output));
+}
+
+#[test]
+fn test_cli_columns() {
+    let output = czv(&["columns"], Some("fruit,price\napple,2.50\n"));
+    assert_eq!("2\n", stdout(&output));
+}
+
+#[test]
+fn test_cli_slice() {
+    let output = czv(
+        &["slice", "--start", "-2", "tests/resources/fruits.csv"],
+        None,
+    );
+    assert_eq!(
+        "fruit,price\nbanana,3.00\nstrawberry,1.50\n",
+        stdout(&output)
+    );
+
+    let output = czv(
+        &[
+            "slice",
+            "--index",
+            "0",
+            "--no-header-row",
+            "tests/resources/fruits.csv",
+        ],
+        None,
+    );
+    assert_eq!("apple,2.50\n", stdout(&output));
+}
+
+#[test]
+fn test_cli_error_policy() {
+    let data = "a,b\n1\n2,3\n";
+    let output = czv(&["count"], Some(data));
+    assert!(!output.status.success());
+
+    let output = czv(&["count", "--error-policy", "collect"], Some(data));
+    assert!(output.status.success());
+    assert_eq!("1\n", stdout(&output));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("skipped record 1"));
+}
+
+#[test]
+fn test_cli_validate() {
+    let output = czv(&["validate", "tests/resources/fruits.csv"], None);
+    assert!(output.status.success());
+
+    let output = czv(&["validate"], Some("a,b\n1\n"));
+    assert!(!output.status.success());
+    assert_eq!(
+        "record 1 (byte 4): expected 2 fields but found 1\n",
+        stdout(&output)
+    );
+}
+
+#[test]
+fn test_cli_encoding() {
+    let path = std::env::temp_dir().join(format!("czv-cli-{}-latin1.csv", std::process::id()));
+    std::fs::write(&path, b"fruit,price\ncaf\xe9,2.50\n").unwrap();
+    let path = path.to_str().unwrap();
+    let normalized = czv(&["input", path], None);
+    let detected = czv(&["input", "--detect", path], None);
+    let table = czv(
+        &["table", "--encoding", "latin1", "-f", "markdown", path],
+        None,
+    );
+    let failed = czv(&["table", path], None);
+    std::fs::remove_file(path).unwrap();
+
+    assert!(normalized.status.success());
+    assert_eq!("fruit,price\ncafé,2.50\n", stdout(&normalized));
+    assert_eq!("windows-1252\n", stdout(&detected));
+    assert_eq!(
+        "| fruit | price |\n| ----- | ----: |\n| café  |  2.50 |\n",
+        stdout(&table)
+    );
+    assert!(!failed.status.success());
+
+    let output = czv(&["count", "--encoding", "bogus"], Some("a\n1\n"));
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_cli_to_json() {
+    let output = czv(
+        &["to-json", "--lines", "--infer-types"],
+        Some("fruit,price\napple,2.50\n"),
+    );
+    assert!(output.status.success());
+    assert_eq!("{\"fruit\":\"apple\",\"price\":2.5}\n", stdout(&output));
+}
+
+#[test]
+fn test_cli_from_json() {
+    let output = czv(&["from-json", "--explode"], Some("{\"a\":1,\"b\":[2,3]}\n"));
+    assert!(output.status.success());
+    assert_eq!("a,b\n1,2\n1,3\n", stdout(&output));
+}
+
+#[cfg(feature = "parquet")]
+#[test]
+fn test_cli_parquet_round_trip() {
+    let path = std::env::temp_dir().join(format!("czv-cli-{}.parquet", std::process::id()));
+    let output = czv(
+        &[
+            "to-parquet",
+            "--type",
+            "price=string",
+            "-o",
+            path.to_str().unwrap(),
+            "tests/resources/fruits.csv",
+        ],
+        None,
+    );
+    assert!(output.status.success());
+
+    let output = czv(&["from-parquet", path.to_str().unwrap()], None);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(
+        "fruit,price\napple,2.50\nbanana,3.00\nstrawberry,1.50\n",
+        stdout(&output)
+    );
+}
+
+#[cfg(feature = "arrow")]
+#[test]
+fn test_cli_arrow_round_trip() {
+    let path = std::env::temp_dir().join(format!("czv-cli-{}.arrows", std::process::id()));
+    let output = czv(
+        &["to-arrow", "-o", path.to_str().unwrap()],
+        Some("a,b\n1,x\n2,\n"),
+    );
+    assert!(output.status.success());
+
+    let output = czv(&["from-arrow", path.to_str().unwrap()], None);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!("a,b\n1,x\n2,\n", stdout(&output));
+}
+
+#[cfg(feature = "excel")]
+#[test]
+fn test_cli_from_excel() {
+    let output = czv(
+        &[
+            "from-excel",
+            "--sheet",
+            "fruits",
+            "--range",
+            "A1:A3",
+            "tests/resources/fruits.xlsx",
+        ],
+        None,
+    );
+    assert!(output.status.success());
+    assert_eq!("fruit\napple\nbanana\n", stdout(&output));
+
+    let output = czv(
+        &["from-excel", "--list-sheets", "tests/resources/fruits.xlsx"],
+        None,
+    );
+    assert_eq!(
+        "sheet,rows,columns\nfruits,4,4\nnotes,2,2\n",
+        stdout(&output)
+    );
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn test_cli_to_sqlite() {
+    let path = std::env::temp_dir().join(format!("czv-cli-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let args = [
+        "to-sqlite",
+        "-o",
+        path.to_str().unwrap(),
+        "--table",
+        "fruits",
+        "--index",
+        "fruit",
+        "tests/resources/fruits.csv",
+    ];
+    let output = czv(&args, None);
+    assert!(output.status.success());
+    assert_eq!("3\n", stdout(&output));
+
+    let output = czv(&args, None);
+    assert!(!output.status.success());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn test_cli_query() {
+    let output = czv(
+        &[
+            "query",
+            "--table",
+            "tests/resources/fruits.csv",
+            "-t",
+            "stock=-",
+            "SELECT fruit, count FROM fruits JOIN stock USING (fruit)",
+        ],
+        Some(
+            "fruit,count
+banana,4
+",
+        ),
+    );
+    assert!(output.status.success());
+    assert_eq!(
+        "fruit,count
+banana,4
+",
+        stdout(&output)
+    );
+}
+
+#[test]
+fn test_cli_aggregate() {
+    let output = czv(
+        &[
+            "aggregate",
+            "-g",
+            "a",
+            "-a",
+            "count,concat(b)",
+            "--separator",
+            ";",
+        ],
+        Some("a,b\nx,1\ny,2\nx,3\n"),
+    );
+    assert!(output.status.success());
+    assert_eq!("a,count,concat_b\nx,2,1;3\ny,1,2\n", stdout(&output));
+}
+
+#[test]
+fn test_cli_pivot() {
+    let output = czv(
+        &["pivot", "-i", "k", "-c", "c", "-v", "v", "--agg", "sum"],
+        Some("k,c,v\na,x,1\na,x,2\nb,y,3\n"),
+    );
+    assert!(output.status.success());
+    assert_eq!("k,x,y\na,3,\nb,,3\n", stdout(&output));
+
+    let output = czv(
+        &["unpivot", "--id", "k", "--skip-empty"],
+        Some(stdout(&output).as_str()),
+    );
+    assert!(output.status.success());
+    assert_eq!("k,variable,value\na,x,3\nb,y,3\n", stdout(&output));
+}
+
+#[test]
+fn test_cli_apply() {
+    let output = czv(
+        &["apply", "-c", "a", "-t", "trim", "-t", "round/1", "-n", "b"],
+        Some("a\n 2.25 \n"),
+    );
+    assert!(output.status.success());
+    assert_eq!("a,b\n 2.25 ,2.2\n", stdout(&output));
+}
+
+#[test]
+fn test_cli_enumerate() {
+    let output = czv(
+        &["enumerate", "--start", "1", "-n", "id"],
+        Some("a\nx\ny\n"),
+    );
+    assert!(output.status.success());
+    assert_eq!("a,id\nx,1\ny,2\n", stdout(&output));
+
+    let output = czv(
+        &["enumerate", "--hash", "md5", "--copy", "a"],
+        Some("a\nx\n"),
+    );
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_cli_explode() {
+    let output = czv(&["explode", "-c", "b", "-s", ";"], Some("a,b\n1,x;y\n"));
+    assert!(output.status.success());
+    assert_eq!("a,b\n1,x\n1,y\n", stdout(&output));
+
+    let output = czv(&["implode", "-c", "b", "-s", ";"], Some("a,b\n1,x\n1,y\n"));
+    assert_eq!("a,b\n1,x;y\n", stdout(&output));
+}
+
+#[test]
+fn test_cli_fix_lengths() {
+    let output = czv(&["fix-lengths", "--longest"], Some("a\n1,2\n"));
+    assert!(output.status.success());
+    assert_eq!("a,\n1,2\n", stdout(&output));
+}
+
+#[test]
+fn test_cli_fill() {
+    let output = czv(
+        &["fill", "-g", "k", "--first"],
+        Some("k,v\nx,1\ny,\nx,2\ny,3\ny,\n"),
+    );
+    assert!(output.status.success());
+    assert_eq!("k,v\nx,1\ny,\nx,2\ny,3\ny,3\n", stdout(&output));
+
+    let output = czv(&["fill", "--value", "0", "--backward"], Some("v\n\n"));
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_cli_fixed_width() {
+    let output = czv(
+        &["to-fixed-width", "--align", "right", "--widths", "3,2"],
+        Some("a,b\n1,2\n"),
+    );
+    assert!(output.status.success());
+    assert_eq!("  a b\n  1 2\n", stdout(&output));
+
+    let output = czv(
+        &["from-fixed-width", "--layout", "x:3,y:2", "--no-header-row"],
+        Some("  1 2\n"),
+    );
+    assert!(output.status.success());
+    assert_eq!("x,y\n1,2\n", stdout(&output));
+}
+
+#[test]
+fn test_cli_table() {
+    let output = czv(
+        &["table", "--format", "markdown", "--max-width", "4"],
+        Some("fruit,price\nbanana,3.00\n"),
+    );
+    assert!(output.status.success());
+    assert_eq!(
+        "| fru… | pri… |\n| ---- | ---: |\n| ban… | 3.00 |\n",
+        stdout(&output)
+    );
+}
+
+#[test]
+fn test_cli_to_sql() {
+    let output = czv(
+        &[
+            "to-sql",
+            "--dialect",
+            "postgres",
+            "--table",
+            "t",
+            "--no-create-table",
+        ],
+        Some("a,b\n1,x\n"),
+    );
+    assert!(output.status.success());
+    assert_eq!(
+        "INSERT INTO \"t\" (\"a\", \"b\") VALUES\n  (1, 'x');\n",
+        stdout(&output)
+    );
+}
+
+#[cfg(feature = "rhai")]
+#[test]
+fn test_cli_map() {
+    let output = czv(
+        &[
+            "map",
+            "print(row.a); row.b = row.a.parse_int() * 2; row.a != \"2\"",
+            "-n",
+            "b",
+        ],
+        Some("a\n1\n2\n3\n"),
+    );
+    assert!(output.status.success());
+    assert_eq!("a,b\n1,2\n3,6\n", stdout(&output));
+    assert_eq!("1\n2\n3\n", String::from_utf8_lossy(&output.stderr));
+
+    let output = czv(&["map", "print(row.a)", "--quiet"], Some("a\n1\n"));
+    assert_eq!("a\n1\n", stdout(&output));
+    assert!(output.stderr.is_empty());
+}
@@ -1,5 +1,4 @@
-use czv;
-use czv::Result;
+use czv::{Encoding, ErrorPolicy, Result};
 
 #[test]
 fn test_row_count() -> Result<()> {
@@ -8,7 +7,13 @@ fn test_row_count() -> Result<()> {
         ("tests/resources/constituents_altnames.csv", 33971),
     ];
     for (file_name, expected) in cases {
-        let got = czv::count::row_count(Some(file_name.into()), None, false)?;
+        let got = czv::count::row_count(
+            Some(file_name.into()),
+            None,
+            false,
+            ErrorPolicy::Fail,
+            Encoding::Utf8,
+        )?;
         assert_eq!(expected, got);
     }
     Ok(())
@@ -34,7 +39,7 @@ fn test_column_count() -> Result<()> {
         ("tests/resources/constituents_altnames.csv", 6),
     ];
     for (file_name, expected) in cases {
-        let got = czv::count::column_count(Some(file_name.into()), None)?;
+        let got = czv::count::column_count(Some(file_name.into()), None, Encoding::Utf8)?;
         assert_eq!(expected, got);
     }
     Ok(())
@@ -54,3 +59,28 @@ fn test_column_count_builder() -> Result<()> {
     }
     Ok(())
 }
+
+#[test]
+fn test_row_count_error_policy() -> Result<()> {
+    let data = "fruit,price\napple,2.50\nbanana\nstrawberry,1.50\n";
+
+    let got = czv::RowCount::new().file_data(data).execute();
+    assert!(got.is_err());
+
+    let got = czv::RowCount::new()
+        .file_data(data)
+        .error_policy(ErrorPolicy::Skip)
+        .execute_with_report()?;
+    assert_eq!(2, got.output);
+    assert!(got.skipped.is_empty());
+
+    let got = czv::RowCount::new()
+        .file_data(data)
+        .error_policy(ErrorPolicy::Collect)
+        .execute_with_report()?;
+    assert_eq!(2, got.output);
+    assert_eq!(1, got.skipped.len());
+    assert_eq!(Some(2), got.skipped[0].record);
+    assert_eq!(Some(3), got.skipped[0].line);
+    Ok(())
+}
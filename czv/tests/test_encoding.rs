@@ -0,0 +1,146 @@
+use czv::table::TableFormat;
+use czv::{CzvError, Encoding, Input, Result, RowCount, Table};
+use std::path::PathBuf;
+
+/// "café" in Latin-1, which is not valid UTF-8.
+const LATIN1: &[u8] = b"fruit,price\ncaf\xe9,2.50\n";
+
+const UTF8: &str = "fruit,price\ncafé,2.50\n";
+
+/// Returns the UTF-16 code units of `text` as little-endian bytes.
+fn utf16le(text: &str) -> Vec<u8> {
+    text.encode_utf16().flat_map(u16::to_le_bytes).collect()
+}
+
+/// Writes `data` to a file unique to the test and returns its path.
+fn temp_file(name: &str, data: &[u8]) -> Result<PathBuf> {
+    let path = std::env::temp_dir().join(format!("czv-test-{name}-{}.csv", std::process::id()));
+    std::fs::write(&path, data)?;
+    Ok(path)
+}
+
+#[test]
+fn test_input() -> Result<()> {
+    let got = czv::encoding::input(None, Some(LATIN1.to_vec()), Encoding::Auto)?;
+    assert_eq!(UTF8, got);
+
+    let got = Input::new()
+        .file_data(LATIN1.to_vec())
+        .encoding("latin1".parse()?)
+        .execute()?;
+    assert_eq!(UTF8, got);
+
+    let got = Input::new().file_data(UTF8.as_bytes().to_vec()).execute()?;
+    assert_eq!(UTF8, got);
+    Ok(())
+}
+
+#[test]
+fn test_input_utf16() -> Result<()> {
+    let mut data = vec![0xff, 0xfe];
+    data.extend(utf16le(UTF8));
+    let got = Input::new().file_data(data.clone()).execute()?;
+    assert_eq!(UTF8, got);
+    // The BOM takes precedence over the given encoding.
+    let got = Input::new()
+        .file_data(data.clone())
+        .encoding(Encoding::Utf8)
+        .execute()?;
+    assert_eq!(UTF8, got);
+    assert_eq!("utf-16le", Input::new().file_data(data).detect()?);
+
+    // Without a BOM, UTF-16 is detected from the zero bytes of ASCII characters.
+    let got = Input::new().file_data(utf16le(UTF8)).execute()?;
+    assert_eq!(UTF8, got);
+
+    // U+0100 has a zero low byte, as do U+0400 and U+4E00.
+    let got = Input::new().file_data(utf16le("name\nĀda\n")).execute()?;
+    assert_eq!("name\nĀda\n", got);
+    let data: Vec<u8> = "name,city\nЀlena,一\n"
+        .encode_utf16()
+        .flat_map(u16::to_be_bytes)
+        .collect();
+    assert_eq!("utf-16be", Input::new().file_data(data).detect()?);
+    Ok(())
+}
+
+#[test]
+fn test_input_bom() -> Result<()> {
+    let data = [b"\xef\xbb\xbf".as_slice(), UTF8.as_bytes()].concat();
+    let got = Input::new()
+        .file_data(data.clone())
+        .encoding(Encoding::Utf8)
+        .execute()?;
+    assert_eq!(UTF8, got);
+    assert_eq!("utf-8", Input::new().file_data(data).detect()?);
+
+    let got = RowCount::new()
+        .file_data("\u{feff}fruit\napple\n")
+        .include_header_row(true)
+        .execute()?;
+    assert_eq!(2, got);
+    let got = Table::new()
+        .file_data("\u{feff}fruit\napple\n")
+        .format(TableFormat::Markdown)
+        .execute()?;
+    assert_eq!("| fruit |\n| ----- |\n| apple |\n", got);
+    Ok(())
+}
+
+#[test]
+fn test_encoding_option() -> Result<()> {
+    let path = temp_file("encoding-option", LATIN1)?;
+    let got = Table::new()
+        .file_path(&path)
+        .format(TableFormat::Markdown)
+        .encoding(Encoding::Auto)
+        .execute();
+    let failed = Table::new().file_path(&path).execute();
+    std::fs::remove_file(&path)?;
+    assert_eq!(
+        "| fruit | price |\n| ----- | ----: |\n| café  |  2.50 |\n",
+        got?
+    );
+    assert!(matches!(failed, Err(CzvError::Parse { .. })));
+
+    let mut data = vec![0xfe, 0xff];
+    data.extend(UTF8.encode_utf16().flat_map(u16::to_be_bytes));
+    let utf16 = temp_file("encoding-option-utf16", &data)?;
+    let bom = temp_file("encoding-option-bom", b"\xef\xbb\xbffruit\napple\n")?;
+    let got = RowCount::new().file_path(&utf16).execute();
+    let got_bom = Table::new().file_path(&bom).execute();
+    std::fs::remove_file(&utf16)?;
+    std::fs::remove_file(&bom)?;
+    assert_eq!(1, got?);
+    assert_eq!("fruit\n-----\napple\n", got_bom?);
+    Ok(())
+}
+
+#[test]
+fn test_encoding_from_str() -> Result<()> {
+    assert_eq!(Encoding::Auto, "auto".parse()?);
+    assert_eq!(Encoding::Utf8, "UTF-8".parse()?);
+    assert_eq!(Encoding::Utf8, "utf8".parse()?);
+    assert!(matches!(
+        "latin1".parse()?,
+        Encoding::Named(encoding) if encoding.name() == "windows-1252"
+    ));
+    assert_eq!("utf-16le".parse::<Encoding>()?, "UTF-16LE".parse()?);
+    assert!(matches!(
+        "bogus".parse::<Encoding>(),
+        Err(CzvError::InvalidOptions(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_input_errors() {
+    let got = Input::new()
+        .file_data(LATIN1.to_vec())
+        .encoding(Encoding::Utf8)
+        .execute();
+    assert!(matches!(got, Err(CzvError::Parse { byte: Some(15), .. })));
+
+    let got = Input::new().execute();
+    assert!(matches!(got, Err(CzvError::InvalidOptions(_))));
+}
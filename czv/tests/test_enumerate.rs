@@ -0,0 +1,101 @@
+use czv::apply::HashAlgorithm;
+use czv::enumerate::EnumerateValue;
+use czv::{CzvError, Encoding, Enumerate, ErrorPolicy, Result};
+
+/// Returns the values of the last column of CSV data without its header row.
+fn last_column(output: &str) -> Vec<&str> {
+    output
+        .lines()
+        .skip(1)
+        .map(|line| line.rsplit(',').next().unwrap())
+        .collect()
+}
+
+#[test]
+fn test_enumerate() -> Result<()> {
+    let got = czv::enumerate::enumerate(
+        Some("tests/resources/fruits.csv".into()),
+        None,
+        EnumerateValue::Index { start: 5, step: -2 },
+        None,
+        ErrorPolicy::Fail,
+        Encoding::Utf8,
+    )?;
+    assert_eq!(
+        "fruit,price,index\napple,2.50,5\nbanana,3.00,3\nstrawberry,1.50,1\n",
+        got
+    );
+    Ok(())
+}
+
+#[test]
+fn test_enumerate_values() -> Result<()> {
+    let enumerate = |value: EnumerateValue| {
+        Enumerate::new()
+            .file_path("tests/resources/fruits.csv")
+            .value(value)
+            .execute()
+    };
+
+    let got = enumerate(EnumerateValue::Uuid)?;
+    assert!(got.starts_with("fruit,price,uuid\n"));
+    let uuids = last_column(&got);
+    assert_eq!(3, uuids.len());
+    assert!(uuids
+        .iter()
+        .all(|uuid| uuid.len() == 36 && uuid.as_bytes()[14] == b'4'));
+    assert_ne!(uuids[0], uuids[1]);
+
+    let got = enumerate(EnumerateValue::Constant("x".to_string()))?;
+    assert_eq!(vec!["x", "x", "x"], last_column(&got));
+
+    let got = enumerate(EnumerateValue::Copy("fruit".to_string()))?;
+    assert!(got.starts_with("fruit,price,fruit_copy\n"));
+    assert_eq!(vec!["apple", "banana", "strawberry"], last_column(&got));
+
+    let got = enumerate(EnumerateValue::FileName)?;
+    assert_eq!(vec!["fruits.csv"; 3], last_column(&got));
+    Ok(())
+}
+
+#[test]
+fn test_enumerate_hash() -> Result<()> {
+    let got = Enumerate::new()
+        .file_data("a,b\nx,y\nx,y\nxy,\n")
+        .value(EnumerateValue::Hash(HashAlgorithm::Md5))
+        .new_column("row_hash")
+        .execute()?;
+    let hashes = last_column(&got);
+    // md5 of "x\x1fy".
+    assert_eq!("64bf4095c35cddef482fe5b9f5dc35e5", hashes[0]);
+    assert_eq!(hashes[0], hashes[1]);
+    assert_ne!(hashes[0], hashes[2]);
+
+    let got = Enumerate::new()
+        .file_data("a\n1\n")
+        .value(EnumerateValue::FileName)
+        .execute()?;
+    assert_eq!("a,file_name\n1,\n", got);
+    Ok(())
+}
+
+#[test]
+fn test_enumerate_errors() {
+    let got = Enumerate::new().file_data("index\n1\n").execute();
+    assert!(matches!(got, Err(CzvError::InvalidOptions(_))));
+
+    let got = Enumerate::new()
+        .file_data("a\n1\n")
+        .value(EnumerateValue::Copy("b".to_string()))
+        .execute();
+    assert!(matches!(got, Err(CzvError::ColumnNotFound(name)) if name == "b"));
+
+    let got = Enumerate::new()
+        .file_data("a\n1\n2\n3\n")
+        .value(EnumerateValue::Index {
+            start: i64::MAX - 1,
+            step: 1,
+        })
+        .execute();
+    assert!(matches!(got, Err(CzvError::InvalidOptions(_))));
+}
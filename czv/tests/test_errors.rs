@@ -0,0 +1,36 @@
+use czv::{CzvError, Result};
+
+#[test]
+fn test_io_error() {
+    let got = czv::RowCount::new()
+        .file_path("tests/resources/does_not_exist.csv")
+        .execute();
+    assert!(matches!(got, Err(CzvError::Io(_))));
+}
+
+#[test]
+fn test_parse_error_position() -> Result<()> {
+    let got = czv::Transpose::new()
+        .file_data("fruit,price\napple,2.50\nbanana\n")
+        .execute();
+    match got {
+        Err(CzvError::Parse {
+            line, byte, record, ..
+        }) => {
+            assert_eq!(Some(3), line);
+            assert_eq!(Some(23), byte);
+            assert_eq!(Some(2), record);
+        }
+        _ => panic!("expected a parse error, got {:?}", got),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_invalid_options_error() {
+    let got = czv::Transpose::new()
+        .file_path("tests/resources/fruits.csv")
+        .file_data("fruit,price")
+        .execute();
+    assert!(matches!(got, Err(CzvError::InvalidOptions(_))));
+}
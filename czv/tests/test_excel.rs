@@ -0,0 +1,129 @@
+#![cfg(feature = "excel")]
+
+use czv::excel::SheetInfo;
+use czv::{CzvError, ExcelSheets, FromExcel, Result};
+
+const XLSX: &str = "tests/resources/fruits.xlsx";
+const ODS: &str = "tests/resources/fruits.ods";
+
+#[test]
+fn test_from_excel_xlsx() -> Result<()> {
+    let got = czv::excel::from_excel(Some(XLSX.into()), None, None, None, None, None)?;
+    assert_eq!(
+        "fruit,price,harvested,fresh\n\
+         apple,2.5,2024-05-01,true\n\
+         banana,3,2024-05-01 18:00:00,false\n\
+         strawberry,1.5,,true\n",
+        got
+    );
+    Ok(())
+}
+
+#[test]
+fn test_from_excel_ods() -> Result<()> {
+    let got = FromExcel::new()
+        .file_data(std::fs::read(ODS)?)
+        .date_format("%d/%m/%Y")
+        .execute()?;
+    assert_eq!(
+        "fruit,price,harvested\napple,2.5,01/05/2024\nbanana,3,01/05/2024\n",
+        got
+    );
+    Ok(())
+}
+
+#[test]
+fn test_from_excel_durations() -> Result<()> {
+    let got = FromExcel::new()
+        .file_path("tests/resources/durations.xlsx")
+        .execute()?;
+    assert_eq!("duration\n-0:30:00\n25:30:00\n", got);
+    Ok(())
+}
+
+#[test]
+fn test_from_excel_sheet_and_range() -> Result<()> {
+    let got = FromExcel::new().file_path(XLSX).sheet_index(1).execute()?;
+    assert_eq!("fruit,note\nbanana,ripe soon\n", got);
+
+    let got = FromExcel::new()
+        .file_path(XLSX)
+        .sheet_name("fruits")
+        .range("c2")
+        .execute()?;
+    assert_eq!("2024-05-01,true\n2024-05-01 18:00:00,false\n,true\n", got);
+
+    let got = FromExcel::new()
+        .file_path(XLSX)
+        .sheet_name("notes")
+        .range("A1:C2")
+        .execute()?;
+    assert_eq!(",,\n,fruit,note\n", got);
+
+    let got = FromExcel::new()
+        .file_path(XLSX)
+        .sheet_name("notes")
+        .range("B2:XFD1048576")
+        .execute()?;
+    assert_eq!("fruit,note\nbanana,ripe soon\n", got);
+    Ok(())
+}
+
+#[test]
+fn test_from_excel_range_outside_used_range() -> Result<()> {
+    for range in ["A10", "Z1", "A10:B20", "A1:A1"] {
+        let got = FromExcel::new()
+            .file_path(XLSX)
+            .sheet_name("notes")
+            .range(range)
+            .execute()?;
+        assert_eq!("", got, "range {range}");
+    }
+    Ok(())
+}
+
+#[test]
+fn test_from_excel_invalid_options() {
+    let sheet_name = FromExcel::new()
+        .file_path(XLSX)
+        .sheet_name("vegetables")
+        .execute();
+    assert!(matches!(sheet_name, Err(CzvError::InvalidOptions(_))));
+
+    let sheet_index = FromExcel::new().file_path(XLSX).sheet_index(2).execute();
+    assert!(matches!(sheet_index, Err(CzvError::InvalidOptions(_))));
+
+    let range = FromExcel::new().file_path(XLSX).range("C3:A1").execute();
+    assert!(matches!(range, Err(CzvError::InvalidOptions(_))));
+
+    let date_format = FromExcel::new().file_path(XLSX).date_format("%Q").execute();
+    assert!(matches!(date_format, Err(CzvError::InvalidOptions(_))));
+
+    let not_a_workbook = FromExcel::new().file_data(b"a,b\n1,2\n".to_vec()).execute();
+    assert!(matches!(not_a_workbook, Err(CzvError::Parse { .. })));
+}
+
+#[test]
+fn test_excel_sheets() -> Result<()> {
+    let got = ExcelSheets::new().file_path(XLSX).execute()?;
+    assert_eq!(
+        vec![
+            SheetInfo {
+                name: "fruits".to_string(),
+                rows: 4,
+                columns: 4,
+            },
+            SheetInfo {
+                name: "notes".to_string(),
+                rows: 2,
+                columns: 2,
+            },
+        ],
+        got
+    );
+
+    let got = czv::excel::excel_sheets(Some(ODS.into()), None)?;
+    assert_eq!(1, got.len());
+    assert_eq!((3, 3), (got[0].rows, got[0].columns));
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use czv::{CzvError, Encoding, ErrorPolicy, Explode, Implode, Result};
+
+#[test]
+fn test_explode() -> Result<()> {
+    let got = czv::explode::explode(
+        None,
+        Some("id,tags,n\n1,a|b|c,x\n2,,y\n3,d||e,z\n".to_string()),
+        Some("tags".to_string()),
+        "|".to_string(),
+        ErrorPolicy::Fail,
+        Encoding::Utf8,
+    )?;
+    assert_eq!(
+        "id,tags,n\n1,a,x\n1,b,x\n1,c,x\n2,,y\n3,d,z\n3,,z\n3,e,z\n",
+        got
+    );
+
+    let got = Explode::new()
+        .file_data("id,tags\n1,\"a, b\"\n")
+        .column("tags")
+        .separator(", ")
+        .execute()?;
+    assert_eq!("id,tags\n1,a\n1,b\n", got);
+    Ok(())
+}
+
+#[test]
+fn test_implode() -> Result<()> {
+    let data = "id,tag\n1,a\n1,b\n2,c\n1,d\n1,\n";
+    let got = czv::explode::implode(
+        None,
+        Some(data.to_string()),
+        Some("tag".to_string()),
+        vec![],
+        ";".to_string(),
+        ErrorPolicy::Fail,
+        Encoding::Utf8,
+    );
+    assert_eq!("id,tag\n1,a;b\n2,c\n1,d;\n", got?);
+
+    let got = Implode::new()
+        .file_data("id,tag,day\n1,a,mon\n1,b,tue\n2,c,wed\n")
+        .column("tag")
+        .keys(["id".to_string()])
+        .execute()?;
+    assert_eq!("id,tag,day\n1,\"a,b\",mon\n2,c,wed\n", got);
+    Ok(())
+}
+
+#[test]
+fn test_explode_implode_round_trip() -> Result<()> {
+    let data = "id,tags\n1,a;b;c\n2,d\n3,\n";
+    let exploded = Explode::new()
+        .file_data(data)
+        .column("tags")
+        .separator(";")
+        .execute()?;
+    let got = Implode::new()
+        .file_data(exploded)
+        .column("tags")
+        .separator(";")
+        .execute()?;
+    assert_eq!(data, got);
+    Ok(())
+}
+
+#[test]
+fn test_explode_errors() {
+    let got = Explode::new().file_data("a\n1\n").execute();
+    assert!(matches!(got, Err(CzvError::InvalidOptions(_))));
+
+    let got = Explode::new()
+        .file_data("a\n1\n")
+        .column("a")
+        .separator("")
+        .execute();
+    assert!(matches!(got, Err(CzvError::InvalidOptions(_))));
+
+    let got = Implode::new().file_data("a\n1\n").column("b").execute();
+    assert!(matches!(got, Err(CzvError::ColumnNotFound(name)) if name == "b"));
+
+    let got = Implode::new()
+        .file_data("a,b\n1,2\n")
+        .column("a")
+        .keys(["a".to_string()])
+        .execute();
+    assert!(matches!(got, Err(CzvError::InvalidOptions(_))));
+}
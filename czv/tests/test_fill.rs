@@ -0,0 +1,119 @@
+use czv::fill::FillMethod;
+use czv::{CzvError, Encoding, ErrorPolicy, Fill, Result};
+
+const DATA: &str = "\
+store,day,price,note
+north,mon,,a
+south,mon,1.00,
+north,tue,2.00,
+south,tue,,b
+north,wed,,
+south,wed,3.00,
+";
+
+#[test]
+fn test_fill() -> Result<()> {
+    let got = czv::fill::fill(
+        None,
+        Some(DATA.to_string()),
+        vec![],
+        FillMethod::Forward,
+        vec![],
+        ErrorPolicy::Fail,
+        Encoding::Utf8,
+    )?;
+    let expected = "\
+store,day,price,note
+north,mon,,a
+south,mon,1.00,a
+north,tue,2.00,a
+south,tue,2.00,b
+north,wed,2.00,b
+south,wed,3.00,b
+";
+    assert_eq!(expected, got);
+    Ok(())
+}
+
+#[test]
+fn test_fill_methods() -> Result<()> {
+    let fill = |method: FillMethod| {
+        Fill::new()
+            .file_data(DATA)
+            .columns(["price".to_string()])
+            .method(method)
+            .execute()
+    };
+    let prices = |output: String| -> Vec<String> {
+        output
+            .lines()
+            .skip(1)
+            .map(|line| line.split(',').nth(2).unwrap().to_string())
+            .collect()
+    };
+    assert_eq!(
+        vec!["1.00", "1.00", "2.00", "3.00", "3.00", "3.00"],
+        prices(fill(FillMethod::Backward)?)
+    );
+    assert_eq!(
+        vec!["", "1.00", "2.00", "1.00", "1.00", "3.00"],
+        prices(fill(FillMethod::First)?)
+    );
+    assert_eq!(
+        vec!["0", "1.00", "2.00", "0", "0", "3.00"],
+        prices(fill(FillMethod::Constant("0".to_string()))?)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_fill_group_by() -> Result<()> {
+    let got = Fill::new()
+        .file_data(DATA)
+        .group_by(["store".to_string()])
+        .execute()?;
+    let expected = "\
+store,day,price,note
+north,mon,,a
+south,mon,1.00,
+north,tue,2.00,a
+south,tue,1.00,b
+north,wed,2.00,a
+south,wed,3.00,b
+";
+    assert_eq!(expected, got);
+
+    let got = Fill::new()
+        .file_data(DATA)
+        .columns(["price".to_string()])
+        .method(FillMethod::Backward)
+        .group_by(["store".to_string()])
+        .execute()?;
+    let expected = "\
+store,day,price,note
+north,mon,2.00,a
+south,mon,1.00,
+north,tue,2.00,
+south,tue,3.00,b
+north,wed,,
+south,wed,3.00,
+";
+    assert_eq!(expected, got);
+    Ok(())
+}
+
+#[test]
+fn test_fill_errors() {
+    let got = Fill::new()
+        .file_data(DATA)
+        .columns(["cost".to_string()])
+        .execute();
+    assert!(matches!(got, Err(CzvError::ColumnNotFound(name)) if name == "cost"));
+
+    let got = Fill::new()
+        .file_data(DATA)
+        .columns(["store".to_string()])
+        .group_by(["store".to_string()])
+        .execute();
+    assert!(matches!(got, Err(CzvError::InvalidOptions(_))));
+}
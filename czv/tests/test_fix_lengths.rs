@@ -0,0 +1,43 @@
+use czv::{CzvError, Encoding, ErrorPolicy, FixLengths, Result};
+
+const DATA: &str = "a,b,c\n1\n1,2,3,4\n\"x,y\",2,3\n";
+
+#[test]
+fn test_fix_lengths() -> Result<()> {
+    let got = czv::fix_lengths::fix_lengths(
+        None,
+        Some(DATA.to_string()),
+        None,
+        false,
+        ErrorPolicy::Fail,
+        Encoding::Utf8,
+    )?;
+    assert_eq!("a,b,c\n1,,\n1,2,3\n\"x,y\",2,3\n", got);
+    Ok(())
+}
+
+#[test]
+fn test_fix_lengths_options() -> Result<()> {
+    let got = FixLengths::new().file_data(DATA).length(2).execute()?;
+    assert_eq!("a,b\n1,\n1,2\n\"x,y\",2\n", got);
+
+    let got = FixLengths::new().file_data(DATA).longest(true).execute()?;
+    assert_eq!("a,b,c,\n1,,,\n1,2,3,4\n\"x,y\",2,3,\n", got);
+
+    let got = FixLengths::new().file_data("").longest(true).execute()?;
+    assert_eq!("", got);
+    Ok(())
+}
+
+#[test]
+fn test_fix_lengths_errors() {
+    let got = FixLengths::new()
+        .file_data(DATA)
+        .length(2)
+        .longest(true)
+        .execute();
+    assert!(matches!(got, Err(CzvError::InvalidOptions(_))));
+
+    let got = FixLengths::new().file_data(DATA).length(0).execute();
+    assert!(matches!(got, Err(CzvError::InvalidOptions(_))));
+}
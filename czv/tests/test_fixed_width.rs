@@ -0,0 +1,165 @@
+use czv::fixed_width::Alignment;
+use czv::{CzvError, Encoding, ErrorPolicy, FromFixedWidth, Result, ToFixedWidth};
+
+#[test]
+fn test_from_fixed_width_widths() -> Result<()> {
+    let data = "fruit     price\napple     2.50\n\nbanana    3.00\r\nstrawberry1.5\n";
+    let got = czv::fixed_width::from_fixed_width(
+        None,
+        Some(data.into()),
+        vec![10, 5],
+        vec![],
+        true,
+        true,
+        ErrorPolicy::Fail,
+        Encoding::Utf8,
+    )?;
+    assert_eq!(
+        "fruit,price\napple,2.50\nbanana,3.00\nstrawberry,1.5\n",
+        got
+    );
+
+    let got = FromFixedWidth::new()
+        .file_data(" a b\n")
+        .widths([2, 2])
+        .has_header_row(false)
+        .trim(false)
+        .execute()?;
+    assert_eq!(" a, b\n", got);
+
+    let got = FromFixedWidth::new()
+        .file_data("12 34  \n")
+        .widths([3, 2])
+        .has_header_row(false)
+        .execute()?;
+    assert_eq!("12,34\n", got);
+    Ok(())
+}
+
+#[test]
+fn test_from_fixed_width_layout() -> Result<()> {
+    let data = "FRUIT PRICE\nkiwi  1.00\ncafé  2\n";
+    let got = FromFixedWidth::new()
+        .file_data(data)
+        .layout([("fruit".to_string(), 6), ("price".to_string(), 5)])
+        .execute()?;
+    assert_eq!("fruit,price\nkiwi,1.00\ncafé,2\n", got);
+    Ok(())
+}
+
+#[test]
+fn test_from_fixed_width_error_policy() -> Result<()> {
+    let data = "ab\n12\n123\n45\n";
+    let builder = || FromFixedWidth::new().file_data(data).widths([1, 1]);
+    let err = builder().execute().unwrap_err();
+    assert!(matches!(
+        err,
+        CzvError::Parse {
+            line: Some(3),
+            byte: Some(6),
+            record: Some(2),
+            ..
+        }
+    ));
+
+    let report = builder()
+        .error_policy(ErrorPolicy::Collect)
+        .execute_with_report()?;
+    assert_eq!("a,b\n1,2\n4,5\n", report.output);
+    assert_eq!(1, report.skipped.len());
+    assert_eq!(Some(3), report.skipped[0].line);
+    Ok(())
+}
+
+#[test]
+fn test_fixed_width_invalid_options() {
+    let neither = FromFixedWidth::new().file_data("a\n").execute();
+    assert!(matches!(neither, Err(CzvError::InvalidOptions(_))));
+
+    let both = ToFixedWidth::new()
+        .file_data("a\n")
+        .widths([1])
+        .layout([("a".to_string(), 1)])
+        .execute();
+    assert!(matches!(both, Err(CzvError::InvalidOptions(_))));
+
+    let zero = ToFixedWidth::new().file_data("a\n").widths([0]).execute();
+    assert!(matches!(zero, Err(CzvError::InvalidOptions(_))));
+
+    let count = ToFixedWidth::new().file_data("a,b\n").widths([1]).execute();
+    assert!(matches!(count, Err(CzvError::InvalidOptions(_))));
+
+    let header = ToFixedWidth::new()
+        .file_data("abc\n1\n")
+        .widths([2])
+        .error_policy(ErrorPolicy::Skip)
+        .execute();
+    assert!(matches!(header, Err(CzvError::InvalidOptions(_))));
+
+    let missing = ToFixedWidth::new()
+        .file_data("a,b\n")
+        .layout([("c".to_string(), 1)])
+        .execute();
+    assert!(matches!(missing, Err(CzvError::ColumnNotFound(_))));
+}
+
+#[test]
+fn test_to_fixed_width() -> Result<()> {
+    let got = czv::fixed_width::to_fixed_width(
+        Some("tests/resources/fruits.csv".into()),
+        None,
+        vec![],
+        vec![],
+        Alignment::Left,
+        false,
+        true,
+        ErrorPolicy::Fail,
+        Encoding::Utf8,
+    )?;
+    assert_eq!(
+        "fruit     price\napple     2.50 \nbanana    3.00 \nstrawberry1.50 \n",
+        got
+    );
+
+    let got = ToFixedWidth::new()
+        .file_path("tests/resources/fruits.csv")
+        .layout([("price".to_string(), 5), ("fruit".to_string(), 4)])
+        .alignment(Alignment::Right)
+        .truncate(true)
+        .include_header_row(false)
+        .execute()?;
+    assert_eq!(" 2.50appl\n 3.00bana\n 1.50stra\n", got);
+    Ok(())
+}
+
+#[test]
+fn test_to_fixed_width_error_policy() -> Result<()> {
+    let data = "a,b\n1,2\n333,4\n5,6\n";
+    let builder = || ToFixedWidth::new().file_data(data).widths([2, 2]);
+    assert!(matches!(
+        builder().execute(),
+        Err(CzvError::Parse {
+            record: Some(2),
+            ..
+        })
+    ));
+
+    let report = builder()
+        .error_policy(ErrorPolicy::Collect)
+        .execute_with_report()?;
+    assert_eq!("a b \n1 2 \n5 6 \n", report.output);
+    assert_eq!(Some(2), report.skipped[0].record);
+    Ok(())
+}
+
+#[test]
+fn test_fixed_width_round_trip() -> Result<()> {
+    let data = "id,name,city\n1,Ana,Lisbon\n22,Bo,\n";
+    let fixed = ToFixedWidth::new().file_data(data).execute()?;
+    let got = FromFixedWidth::new()
+        .file_data(fixed)
+        .widths([2, 4, 6])
+        .execute()?;
+    assert_eq!(data, got);
+    Ok(())
+}
@@ -0,0 +1,176 @@
+use czv::json::{ArrayMode, JsonFormat};
+use czv::{CzvError, Encoding, ErrorPolicy, Result};
+
+#[test]
+fn test_to_json() -> Result<()> {
+    let got = czv::json::to_json(
+        Some("tests/resources/fruits.csv".into()),
+        None,
+        JsonFormat::Array,
+        false,
+        false,
+        ErrorPolicy::Fail,
+        Encoding::Utf8,
+    )?;
+    let expected = r#"[{"fruit":"apple","price":"2.50"},{"fruit":"banana","price":"3.00"},{"fruit":"strawberry","price":"1.50"}]
+"#;
+    assert_eq!(expected, got);
+    Ok(())
+}
+
+#[test]
+fn test_to_json_infer_types() -> Result<()> {
+    let data = "id,zip,score,active,note\n1,007,1.5,true,\n2,010,2,FALSE,hi\n";
+    let got = czv::ToJson::new()
+        .file_data(data)
+        .format(JsonFormat::Lines)
+        .infer_types(true)
+        .execute()?;
+    let expected = r#"{"id":1,"zip":"007","score":1.5,"active":true,"note":null}
+{"id":2,"zip":"010","score":2.0,"active":false,"note":"hi"}
+"#;
+    assert_eq!(expected, got);
+    Ok(())
+}
+
+#[test]
+fn test_to_json_nested() -> Result<()> {
+    let data = "name,address.city,address.geo.lat\nAda,London,51.5\n";
+    let got = czv::ToJson::new().file_data(data).nested(true).execute()?;
+    let expected = r#"[{"name":"Ada","address":{"city":"London","geo":{"lat":"51.5"}}}]
+"#;
+    assert_eq!(expected, got);
+    Ok(())
+}
+
+#[test]
+fn test_to_json_nested_conflict() {
+    let data = "address,address.city\nx,London\n";
+    let got = czv::ToJson::new().file_data(data).nested(true).execute();
+    assert!(matches!(got, Err(CzvError::Parse { .. })));
+
+    let flat = czv::ToJson::new().file_data(data).execute();
+    assert!(flat.is_ok());
+}
+
+#[test]
+fn test_to_json_empty() -> Result<()> {
+    let got = czv::ToJson::new().file_data("a,b\n").execute()?;
+    assert_eq!("[]\n", got);
+    Ok(())
+}
+
+#[test]
+fn test_to_json_to_writer() -> Result<()> {
+    let mut output = vec![];
+    let report = czv::ToJson::new()
+        .file_data("a,b\n1,2\n3\n")
+        .format(JsonFormat::Lines)
+        .error_policy(ErrorPolicy::Collect)
+        .execute_to_writer(&mut output)?;
+    assert_eq!(b"{\"a\":\"1\",\"b\":\"2\"}\n", output.as_slice());
+    assert_eq!(1, report.skipped.len());
+    Ok(())
+}
+
+#[test]
+fn test_from_json_lines() -> Result<()> {
+    let data = "{\"a\":1,\"b\":{\"c\":true,\"d\":null}}\n\n{\"e\":\"x,y\",\"a\":2.5}\n";
+    let got = czv::json::from_json(
+        None,
+        Some(data.into()),
+        ArrayMode::Join,
+        ",".into(),
+        ErrorPolicy::Fail,
+        Encoding::Utf8,
+    )?;
+    assert_eq!("a,b.c,b.d,e\n1,true,,\n2.5,,,\"x,y\"\n", got);
+    Ok(())
+}
+
+#[test]
+fn test_from_json_explode_combinations() -> Result<()> {
+    let data = r#"[{"id":1,"x":[1,2],"y":[{"z":"a"},{"z":"b"}]},{"id":2,"x":[]}]"#;
+    let got = czv::FromJson::new()
+        .file_data(data)
+        .arrays(ArrayMode::Explode)
+        .execute()?;
+    assert_eq!("id,x,y.z\n1,1,a\n1,1,b\n1,2,a\n1,2,b\n2,,\n", got);
+    Ok(())
+}
+
+#[test]
+fn test_from_json_join_nested_values() -> Result<()> {
+    let data = r#"[{"tags":["a",{"b":1},null]}]"#;
+    let got = czv::FromJson::new()
+        .file_data(data)
+        .separator("|")
+        .execute()?;
+    assert_eq!("tags\n\"a|{\"\"b\"\":1}|\"\n", got);
+    Ok(())
+}
+
+#[test]
+fn test_from_json_error_policy() -> Result<()> {
+    let data = "{\"a\":1}\nnot json\n[1]\n{\"a\":2}\n";
+    let got = czv::FromJson::new().file_data(data).execute();
+    assert!(matches!(
+        got,
+        Err(CzvError::Parse {
+            line: Some(2),
+            record: Some(1),
+            ..
+        })
+    ));
+
+    let got = czv::FromJson::new()
+        .file_data(data)
+        .error_policy(ErrorPolicy::Collect)
+        .execute_with_report()?;
+    assert_eq!("a\n1\n2\n", got.output);
+    assert_eq!(2, got.skipped.len());
+    assert_eq!(Some(3), got.skipped[1].line);
+    Ok(())
+}
+
+#[test]
+fn test_from_json_empty_object() -> Result<()> {
+    let got = czv::FromJson::new()
+        .file_data("{\"a\":1}\n{}\n")
+        .execute()?;
+    assert_eq!("a\n1\n\"\"\n", got);
+    Ok(())
+}
+
+#[test]
+fn test_from_json_key_collision() -> Result<()> {
+    let data = "{\"a\":{\"b\":1},\"a.b\":2}\n{\"a.b\":3}\n";
+    let got = czv::FromJson::new().file_data(data).execute();
+    assert!(matches!(
+        got,
+        Err(CzvError::Parse {
+            line: Some(1),
+            record: Some(0),
+            ..
+        })
+    ));
+
+    let got = czv::FromJson::new()
+        .file_data(data)
+        .error_policy(ErrorPolicy::Skip)
+        .execute()?;
+    assert_eq!("a.b\n3\n", got);
+    Ok(())
+}
+
+#[test]
+fn test_round_trip() -> Result<()> {
+    let csv = czv::FromJson::new()
+        .file_path("tests/resources/fruits.json")
+        .execute()?;
+    let json = czv::ToJson::new().file_data(csv).execute()?;
+    let expected = r#"[{"fruit":"apple","price":"2.50"},{"fruit":"banana","price":"3.00"},{"fruit":"strawberry","price":"1.50"}]
+"#;
+    assert_eq!(expected, json);
+    Ok(())
+}
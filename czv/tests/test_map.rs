@@ -0,0 +1,122 @@
+#![cfg(feature = "rhai")]
+
+use czv::{CzvError, Encoding, ErrorPolicy, Map, Result};
+
+#[test]
+fn test_map() -> Result<()> {
+    let got = czv::map::map(
+        Some("tests/resources/fruits.csv".into()),
+        None,
+        Some("row.fruit = row.fruit.to_upper(); row.price.parse_float() < 3.0".to_string()),
+        None,
+        vec![],
+        1_000_000,
+        false,
+        ErrorPolicy::Fail,
+        Encoding::Utf8,
+    )?;
+    assert_eq!("fruit,price\nAPPLE,2.50\nSTRAWBERRY,1.50\n", got);
+    Ok(())
+}
+
+#[test]
+fn test_map_state() -> Result<()> {
+    let got = Map::new()
+        .file_data("name,team\nada,x\nalan,y\ngrace,x\n")
+        .begin("let counts = #{}; fn label(name) { `${name}!` }")
+        .script(
+            "
+            let count = (counts[row.team] ?? 0) + 1;
+            counts[row.team] = count;
+            row.rank = count;
+            row.label = label(row.name);
+            row.missing = ();
+            ",
+        )
+        .new_columns([
+            "rank".to_string(),
+            "label".to_string(),
+            "missing".to_string(),
+            "unset".to_string(),
+        ])
+        .execute()?;
+    assert_eq!(
+        "name,team,rank,label,missing,unset\nada,x,1,ada!,,\nalan,y,1,alan!,,\ngrace,x,2,grace!,,\n",
+        got
+    );
+
+    // Variables declared by the script do not carry over to the next row.
+    let got = Map::new()
+        .file_data("a\n1\n2\n")
+        .script(r#"row.a = is_def_var("seen"); let seen = true;"#)
+        .execute()?;
+    assert_eq!("a\nfalse\nfalse\n", got);
+
+    let got = Map::new()
+        .file_data("unit price\n2\n")
+        .script(r#"row["unit price"] = row["unit price"].parse_int() * 3"#)
+        .execute()?;
+    assert_eq!("unit price\n6\n", got);
+    Ok(())
+}
+
+#[test]
+fn test_map_errors() -> Result<()> {
+    let report = Map::new()
+        .file_data("a\n1\nx\n3\n")
+        .script("row.a = row.a.parse_int() + 1")
+        .error_policy(ErrorPolicy::Collect)
+        .execute_with_report()?;
+    assert_eq!("a\n2\n4\n", report.output);
+    assert_eq!(1, report.skipped.len());
+    assert_eq!(Some(2), report.skipped[0].record);
+    assert!(report.skipped[0].reason.starts_with("Script failed: "));
+
+    let got = Map::new()
+        .file_data("a\n1\n")
+        .script(r#"throw "bad row""#)
+        .execute();
+    assert!(
+        matches!(got, Err(CzvError::Parse { message, record: Some(1), .. }) if message.contains("bad row"))
+    );
+
+    let got = Map::new().file_data("a\n1\n").script("row = 1").execute();
+    assert!(matches!(got, Err(CzvError::Parse { .. })));
+
+    for (script, begin) in [("row.a = ", None), ("", Some("let")), ("", Some("throw 1"))] {
+        let got = Map::new()
+            .file_data("a\n1\n")
+            .script(script)
+            .begin_opt(begin.map(String::from))
+            .execute();
+        assert!(
+            matches!(got, Err(CzvError::InvalidOptions(_))),
+            "{script:?} {begin:?}"
+        );
+    }
+
+    let got = Map::new()
+        .file_data("a\n1\n")
+        .script("loop {}")
+        .max_operations(1000)
+        .execute();
+    assert!(matches!(got, Err(CzvError::Parse { .. })));
+
+    let got = Map::new()
+        .file_data("a\n1\n")
+        .begin("fn down(n) { down(n + 1) }")
+        .script("down(0)")
+        .execute();
+    assert!(matches!(got, Err(CzvError::Parse { .. })));
+
+    let got = Map::new().file_data("a\n1\n").execute();
+    assert!(matches!(got, Err(CzvError::InvalidOptions(_))));
+
+    let got = Map::new()
+        .file_data("a\n1\n")
+        .script("")
+        .new_columns(["a".to_string()])
+        .execute();
+    assert!(matches!(got, Err(CzvError::InvalidOptions(_))));
+    Ok(())
+}
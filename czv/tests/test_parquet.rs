@@ -0,0 +1,98 @@
+#![cfg(feature = "parquet")]
+
+use czv::infer::ColumnType;
+use czv::parquet::ParquetCompression;
+use czv::{CzvError, Encoding, ErrorPolicy, FromParquet, Result, ToParquet};
+use std::collections::HashMap;
+
+#[test]
+fn test_parquet_round_trip() -> Result<()> {
+    let data = "id,zip,score,active,note\n1,02134,1.5,true,\n2,10001,,FALSE,hi\n";
+    let parquet = czv::parquet::to_parquet(
+        None,
+        Some(data.into()),
+        HashMap::new(),
+        ParquetCompression::Snappy,
+        1024,
+        ErrorPolicy::Fail,
+        Encoding::Utf8,
+    )?;
+    let got = czv::parquet::from_parquet(None, Some(parquet))?;
+    assert_eq!(
+        "id,zip,score,active,note\n1,02134,1.5,true,\n2,10001,,false,hi\n",
+        got
+    );
+    Ok(())
+}
+
+#[test]
+fn test_parquet_compression_and_row_groups() -> Result<()> {
+    let data = (0..100).fold("n\n".to_string(), |data, n| {
+        data + format!("{n}\n").as_str()
+    });
+    for compression in [
+        ParquetCompression::Uncompressed,
+        ParquetCompression::Gzip,
+        ParquetCompression::Zstd,
+        ParquetCompression::Brotli,
+        ParquetCompression::Lz4,
+    ] {
+        let parquet = ToParquet::new()
+            .file_data(data.as_str())
+            .compression(compression)
+            .row_group_size(10)
+            .execute()?;
+        let got = FromParquet::new().file_data(parquet).execute()?;
+        assert_eq!(data, got);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_parquet_column_types() -> Result<()> {
+    let data = "code,amount\n7,10\nx,20\n";
+    let got = ToParquet::new()
+        .file_data(data)
+        .column_types([("code".to_string(), ColumnType::Integer)])
+        .execute();
+    assert!(matches!(
+        got,
+        Err(CzvError::Parse {
+            record: Some(2),
+            ..
+        })
+    ));
+
+    let got = ToParquet::new()
+        .file_data(data)
+        .column_types([
+            ("code".to_string(), ColumnType::Integer),
+            ("amount".to_string(), ColumnType::Float),
+        ])
+        .error_policy(ErrorPolicy::Collect)
+        .execute_with_report()?;
+    assert_eq!(1, got.skipped.len());
+    let csv = FromParquet::new().file_data(got.output).execute()?;
+    assert_eq!("code,amount\n7,10.0\n", csv);
+
+    let got = ToParquet::new()
+        .file_data(data)
+        .column_types([("missing".to_string(), ColumnType::String)])
+        .execute();
+    assert!(matches!(got, Err(CzvError::ColumnNotFound(_))));
+    Ok(())
+}
+
+#[test]
+fn test_from_parquet_file_path_and_data() {
+    let got = FromParquet::new()
+        .file_path("tests/resources/fruits.csv")
+        .file_data(vec![])
+        .execute();
+    assert!(matches!(got, Err(CzvError::InvalidOptions(_))));
+
+    let got = FromParquet::new()
+        .file_path("tests/resources/fruits.csv")
+        .execute();
+    assert!(matches!(got, Err(CzvError::Parse { .. })));
+}
@@ -0,0 +1,146 @@
+use czv::aggregate::AggregateFunction;
+use czv::{CzvError, Encoding, ErrorPolicy, Pivot, Result, Unpivot};
+
+const SALES: &str = "\
+store,fruit,count
+north,apple,3
+north,banana,4
+south,apple,2
+north,apple,1
+";
+
+#[test]
+fn test_pivot() -> Result<()> {
+    let got = czv::pivot::pivot(
+        None,
+        Some(SALES.to_string()),
+        vec!["store".to_string()],
+        Some("fruit".to_string()),
+        Some("count".to_string()),
+        AggregateFunction::First,
+        ",".to_string(),
+        ErrorPolicy::Fail,
+        Encoding::Utf8,
+    )?;
+    assert_eq!("store,apple,banana\nnorth,3,4\nsouth,2,\n", got);
+    Ok(())
+}
+
+#[test]
+fn test_pivot_aggregate() -> Result<()> {
+    let pivot = |aggregate| {
+        Pivot::new()
+            .file_data(SALES)
+            .index(["store".to_string()])
+            .pivot_column("fruit")
+            .value_column("count")
+            .aggregate(aggregate)
+            .execute()
+    };
+    assert_eq!(
+        "store,apple,banana\nnorth,2.0,4.0\nsouth,2.0,\n",
+        pivot(AggregateFunction::Mean)?
+    );
+    assert_eq!(
+        "store,apple,banana\nnorth,\"3,1\",4\nsouth,2,\n",
+        pivot(AggregateFunction::Concat)?
+    );
+    assert_eq!(
+        "store,apple,banana\nnorth,2,1\nsouth,1,0\n",
+        pivot(AggregateFunction::Count)?
+    );
+
+    let got = Pivot::new()
+        .file_data(SALES)
+        .index(["store".to_string()])
+        .pivot_column("fruit")
+        .value_column("count")
+        .aggregate(AggregateFunction::Concat)
+        .separator(";")
+        .execute()?;
+    assert_eq!("store,apple,banana\nnorth,3;1,4\nsouth,2,\n", got);
+
+    let got = Pivot::new()
+        .file_data(SALES)
+        .pivot_column("store")
+        .value_column("count")
+        .aggregate(AggregateFunction::Count)
+        .execute()?;
+    assert_eq!("north,south\n3,1\n", got);
+    Ok(())
+}
+
+#[test]
+fn test_pivot_errors() -> Result<()> {
+    let report = Pivot::new()
+        .file_data("k,c,v\na,x,1\na,x,one\na,y,2\n")
+        .index(["k".to_string()])
+        .pivot_column("c")
+        .value_column("v")
+        .aggregate(AggregateFunction::Sum)
+        .error_policy(ErrorPolicy::Collect)
+        .execute_with_report()?;
+    assert_eq!("k,x,y\na,1,2\n", report.output);
+    assert_eq!(1, report.skipped.len());
+
+    let got = Pivot::new()
+        .file_data(SALES)
+        .pivot_column("fruit")
+        .value_column("price")
+        .execute();
+    assert!(matches!(got, Err(CzvError::ColumnNotFound(name)) if name == "price"));
+
+    let got = Pivot::new()
+        .file_data(SALES)
+        .pivot_column("fruit")
+        .execute();
+    assert!(matches!(got, Err(CzvError::InvalidOptions(_))));
+
+    for data in [
+        "store,fruit,count\nn,store,1\n",
+        "store,fruit,count\nn,,1\n",
+    ] {
+        let got = Pivot::new()
+            .file_data(data)
+            .index(["store".to_string()])
+            .pivot_column("fruit")
+            .value_column("count")
+            .execute();
+        assert!(matches!(got, Err(CzvError::InvalidOptions(_))), "{data}");
+    }
+    Ok(())
+}
+
+#[test]
+fn test_unpivot() -> Result<()> {
+    let data = "store,apple,banana\nnorth,4,4\nsouth,2,\n";
+    let got = czv::pivot::unpivot(
+        None,
+        Some(data.to_string()),
+        vec!["store".to_string()],
+        vec![],
+        "variable".to_string(),
+        "value".to_string(),
+        false,
+        ErrorPolicy::Fail,
+        Encoding::Utf8,
+    )?;
+    assert_eq!(
+        "store,variable,value\nnorth,apple,4\nnorth,banana,4\nsouth,apple,2\nsouth,banana,\n",
+        got
+    );
+
+    let got = Unpivot::new()
+        .file_data(data)
+        .value_columns(["banana".to_string()])
+        .skip_empty(true)
+        .execute()?;
+    assert_eq!("variable,value\nbanana,4\n", got);
+
+    let got = Unpivot::new()
+        .file_data(data)
+        .id_columns(["region".to_string()])
+        .execute();
+    assert!(matches!(got, Err(CzvError::ColumnNotFound(name)) if name == "region"));
+    Ok(())
+}
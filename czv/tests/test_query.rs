@@ -0,0 +1,108 @@
+#![cfg(feature = "sqlite")]
+
+use czv::query::QueryRecords;
+use czv::{CzvError, Encoding, ErrorPolicy, Query, Result};
+
+#[test]
+fn test_query() -> Result<()> {
+    let got = czv::query::query(
+        vec![("fruits".to_string(), "tests/resources/fruits.csv".into())],
+        vec![],
+        Some("SELECT fruit FROM fruits WHERE price < 3 ORDER BY price".to_string()),
+        ErrorPolicy::Fail,
+        Encoding::Utf8,
+    )?;
+    assert_eq!("fruit\nstrawberry\napple\n", got);
+    Ok(())
+}
+
+#[test]
+fn test_query_join_group_by() -> Result<()> {
+    let orders = "id,fruit,count\n1,apple,2\n2,banana,1\n3,apple,3\n4,kiwi,1\n";
+    let got = Query::new()
+        .file_paths([("fruits".to_string(), "tests/resources/fruits.csv".into())])
+        .file_data([("orders".to_string(), orders.to_string())])
+        .sql(
+            "SELECT o.fruit, SUM(o.count) AS total, f.price \
+             FROM orders o LEFT JOIN fruits f ON f.fruit = o.fruit \
+             GROUP BY o.fruit ORDER BY total DESC, o.fruit",
+        )
+        .execute()?;
+    assert_eq!(
+        "fruit,total,price\napple,5,2.5\nbanana,1,3.0\nkiwi,1,\n",
+        got
+    );
+    Ok(())
+}
+
+#[test]
+fn test_query_records() -> Result<()> {
+    let got = Query::new()
+        .file_data([("t".to_string(), "a,b\n1,x\n2,\n".to_string())])
+        .sql("SELECT a, b, a > 1 AS big FROM t")
+        .execute_to_records()?;
+    assert_eq!(
+        QueryRecords {
+            headers: vec!["a".to_string(), "b".to_string(), "big".to_string()],
+            rows: vec![
+                vec![
+                    Some("1".to_string()),
+                    Some("x".to_string()),
+                    Some("0".to_string())
+                ],
+                vec![Some("2".to_string()), None, Some("1".to_string())],
+            ],
+        },
+        got
+    );
+    Ok(())
+}
+
+#[test]
+fn test_query_errors() -> Result<()> {
+    let data = || [("t".to_string(), "a,b\n1,x\n2\n3,y\n".to_string())];
+    let report = Query::new()
+        .file_data(data())
+        .sql("SELECT COUNT(*) AS n FROM t")
+        .error_policy(ErrorPolicy::Collect)
+        .execute_with_report()?;
+    assert_eq!("n\n2\n", report.output);
+    assert_eq!(1, report.skipped.len());
+    assert!(report.skipped[0].reason.starts_with("Table \"t\": "));
+
+    let attached = std::env::temp_dir().join(format!("czv-test-attach-{}.db", std::process::id()));
+    let attach = format!("ATTACH DATABASE '{}' AS x", attached.display());
+    for sql in [
+        "DELETE FROM t",
+        "SELEC 1",
+        "SELECT * FROM missing",
+        "SELECT 1; SELECT 2",
+        "SELECT 1; DELETE FROM t",
+        "",
+        &attach,
+    ] {
+        let got = Query::new()
+            .file_data(data())
+            .sql(sql)
+            .error_policy(ErrorPolicy::Skip)
+            .execute();
+        assert!(matches!(got, Err(CzvError::InvalidOptions(_))), "{sql}");
+    }
+    assert!(!attached.exists());
+
+    let got = Query::new()
+        .file_data([data(), [("T".to_string(), "a\n1\n".to_string())]].concat())
+        .sql("SELECT 1")
+        .execute();
+    assert!(matches!(got, Err(CzvError::InvalidOptions(_))));
+
+    let got = Query::new()
+        .file_data([("t".to_string(), "a,a\n1,2\n".to_string())])
+        .sql("SELECT 1")
+        .execute();
+    assert!(matches!(got, Err(CzvError::InvalidOptions(_))));
+
+    let got = Query::new().sql("SELECT 1").execute();
+    assert!(matches!(got, Err(CzvError::InvalidOptions(_))));
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use czv::{Encoding, ErrorPolicy, Result};
+
+const SCHEMA: &str = r#"{
+    "type": "object",
+    "properties": {
+        "fruit": { "type": "string", "enum": ["apple", "banana", "strawberry"] },
+        "price": { "type": ["number", "null"], "minimum": 2 }
+    },
+    "required": ["fruit"]
+}"#;
+
+#[test]
+fn test_schema_validate() -> Result<()> {
+    let got = czv::schema::schema_validate(
+        Some("tests/resources/fruits.csv".into()),
+        None,
+        None,
+        Some(SCHEMA.to_string()),
+        ErrorPolicy::Fail,
+        Encoding::Utf8,
+    )?;
+    assert_eq!("fruit,price\napple,2.50\nbanana,3.00\n", got.valid);
+    assert_eq!("fruit,price\nstrawberry,1.50\n", got.invalid);
+    assert_eq!(1, got.errors.len());
+    assert_eq!(3, got.errors[0].record);
+    assert_eq!(Some("price".to_string()), got.errors[0].column);
+    Ok(())
+}
+
+#[test]
+fn test_schema_validate_builder() -> Result<()> {
+    let data = "fruit,price\nkiwi,\n,2.00\n";
+    let got = czv::SchemaValidate::new()
+        .file_data(data)
+        .schema_data(SCHEMA)
+        .execute()?;
+    assert_eq!("fruit,price\n", got.valid);
+    assert_eq!("fruit,price\nkiwi,\n,2.00\n", got.invalid);
+    let columns = got
+        .errors
+        .iter()
+        .map(|err| (err.record, err.column.as_deref()))
+        .collect::<Vec<_>>();
+    assert_eq!(vec![(1, Some("fruit")), (2, Some("fruit"))], columns);
+    Ok(())
+}
+
+#[test]
+fn test_schema_validate_invalid_schema() {
+    let got = czv::SchemaValidate::new()
+        .file_path("tests/resources/fruits.csv")
+        .schema_data(r#"{"type": 5}"#)
+        .execute();
+    assert!(got.is_err());
+}
+
+#[test]
+fn test_schema_generate() -> Result<()> {
+    let data = "name,age,score,active\nada,36,1.5,true\nalan,,2,false\n";
+    let got = czv::SchemaGenerate::new().file_data(data).execute()?;
+    let schema: serde_json::Value = serde_json::from_str(&got)?;
+    assert_eq!(
+        serde_json::json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "object",
+            "properties": {
+                "name": { "type": "string" },
+                "age": { "type": ["integer", "null"] },
+                "score": { "type": "number" },
+                "active": { "type": "boolean" }
+            },
+            "required": ["name", "score", "active"]
+        }),
+        schema
+    );
+    Ok(())
+}
+
+#[test]
+fn test_schema_validate_error_policy() -> Result<()> {
+    let data = "fruit,price\napple,2.50\nbanana\nstrawberry,1.50,extra\n";
+    let got = czv::SchemaValidate::new()
+        .file_data(data)
+        .schema_data(SCHEMA)
+        .error_policy(ErrorPolicy::Collect)
+        .execute_with_report()?;
+    assert_eq!("fruit,price\napple,2.50\n", got.output.valid);
+    assert_eq!("fruit,price\n", got.output.invalid);
+    let records = got
+        .skipped
+        .iter()
+        .map(|skipped| skipped.record)
+        .collect::<Vec<_>>();
+    assert_eq!(vec![Some(2), Some(3)], records);
+    Ok(())
+}
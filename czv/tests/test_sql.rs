@@ -0,0 +1,107 @@
+use czv::infer::ColumnType;
+use czv::sql::SqlDialect;
+use czv::{CzvError, Encoding, ErrorPolicy, Result, ToSql};
+use std::collections::HashMap;
+
+#[test]
+fn test_to_sql_sqlite() -> Result<()> {
+    let got = czv::sql::to_sql(
+        Some("tests/resources/fruits.csv".into()),
+        None,
+        None,
+        SqlDialect::Sqlite,
+        HashMap::new(),
+        true,
+        2,
+        ErrorPolicy::Fail,
+        Encoding::Utf8,
+    )?;
+    assert_eq!(
+        "CREATE TABLE \"fruits\" (\n  \"fruit\" TEXT NOT NULL,\n  \"price\" REAL NOT NULL\n);\n\
+         INSERT INTO \"fruits\" (\"fruit\", \"price\") VALUES\n  ('apple', 2.50),\n  ('banana', 3.00);\n\
+         INSERT INTO \"fruits\" (\"fruit\", \"price\") VALUES\n  ('strawberry', 1.50);\n",
+        got
+    );
+    Ok(())
+}
+
+#[test]
+fn test_to_sql_dialects() -> Result<()> {
+    let data = "id,ok,na`me\n+7,TRUE,a\\b\n8,false,\n";
+    let got = ToSql::new()
+        .file_data(data)
+        .dialect(SqlDialect::Mysql)
+        .execute()?;
+    assert_eq!(
+        "CREATE TABLE `data` (\n  `id` BIGINT NOT NULL,\n  `ok` BOOLEAN NOT NULL,\n  `na``me` TEXT\n);\n\
+         INSERT INTO `data` (`id`, `ok`, `na``me`) VALUES\n  (7, TRUE, 'a\\\\b'),\n  (8, FALSE, NULL);\n",
+        got
+    );
+
+    let got = ToSql::new()
+        .file_data(data)
+        .table_name("t")
+        .create_table(false)
+        .execute()?;
+    assert_eq!(
+        "INSERT INTO \"t\" (\"id\", \"ok\", \"na`me\") VALUES\n  (7, 1, 'a\\b'),\n  (8, 0, NULL);\n",
+        got
+    );
+    Ok(())
+}
+
+#[test]
+fn test_to_sql_column_types() -> Result<()> {
+    let data = "zip,n\n02134,1\n10001,x\n";
+    let builder = || {
+        ToSql::new()
+            .file_data(data)
+            .dialect(SqlDialect::Postgres)
+            .column_types([("n".to_string(), ColumnType::Integer)])
+            .create_table(false)
+    };
+    assert!(matches!(
+        builder().execute(),
+        Err(CzvError::Parse {
+            record: Some(2),
+            ..
+        })
+    ));
+
+    let report = builder()
+        .error_policy(ErrorPolicy::Collect)
+        .execute_with_report()?;
+    assert_eq!(
+        "INSERT INTO \"data\" (\"zip\", \"n\") VALUES\n  ('02134', 1);\n",
+        report.output
+    );
+    assert_eq!(1, report.skipped.len());
+    Ok(())
+}
+
+#[test]
+fn test_to_sql_invalid_options() {
+    let missing = ToSql::new()
+        .file_data("a\n1\n")
+        .column_types([("b".to_string(), ColumnType::String)])
+        .execute();
+    assert!(matches!(missing, Err(CzvError::ColumnNotFound(_))));
+
+    let batch_size = ToSql::new().file_data("a\n1\n").batch_size(0).execute();
+    assert!(matches!(batch_size, Err(CzvError::InvalidOptions(_))));
+
+    for data in ["a,a\n1,2\n", "a,A\n1,2\n", "a,\n1,2\n"] {
+        let got = ToSql::new().file_data(data).execute();
+        assert!(matches!(got, Err(CzvError::InvalidOptions(_))), "{data}");
+    }
+}
+
+#[test]
+fn test_to_sql_no_rows() -> Result<()> {
+    let got = ToSql::new().file_data("a,b\n").execute()?;
+    assert_eq!(
+        "CREATE TABLE \"data\" (\n  \"a\" TEXT NOT NULL,\n  \"b\" TEXT NOT NULL\n);\n",
+        got
+    );
+    Ok(())
+}
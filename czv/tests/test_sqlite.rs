@@ -0,0 +1,175 @@
+#![cfg(feature = "sqlite")]
+
+use czv::infer::ColumnType;
+use czv::sqlite::IfExists;
+use czv::{CzvError, Encoding, ErrorPolicy, Result, ToSqlite};
+use rusqlite::Connection;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Returns a database path unique to the test, removing any file left by an earlier run.
+fn database_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("czv-test-{name}-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+#[test]
+fn test_to_sqlite() -> Result<()> {
+    let path = database_path("to-sqlite");
+    let inserted = czv::sqlite::to_sqlite(
+        Some("tests/resources/fruits.csv".into()),
+        None,
+        Some(path.clone()),
+        None,
+        HashMap::new(),
+        vec!["fruit".to_string()],
+        IfExists::Fail,
+        2,
+        ErrorPolicy::Fail,
+        Encoding::Utf8,
+    )?;
+    assert_eq!(3, inserted);
+
+    let conn = Connection::open(&path).unwrap();
+    let rows: Vec<(String, f64)> = conn
+        .prepare("SELECT fruit, price FROM fruits ORDER BY rowid")
+        .unwrap()
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .unwrap()
+        .collect::<rusqlite::Result<_>>()
+        .unwrap();
+    assert_eq!(
+        vec![
+            ("apple".to_string(), 2.5),
+            ("banana".to_string(), 3.0),
+            ("strawberry".to_string(), 1.5)
+        ],
+        rows
+    );
+    let index: String = conn
+        .query_row(
+            "SELECT name FROM sqlite_master WHERE type = 'index' AND tbl_name = 'fruits'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!("fruits_fruit_idx", index);
+    drop(conn);
+    std::fs::remove_file(path)?;
+    Ok(())
+}
+
+#[test]
+fn test_to_sqlite_types() -> Result<()> {
+    let path = database_path("types");
+    let inserted = ToSqlite::new()
+        .file_data("id,ok,code,note\n1,true,007,\n2,FALSE,010,x\n")
+        .database_path(&path)
+        .table_name("items")
+        .column_types([("code".to_string(), ColumnType::String)])
+        .execute()?;
+    assert_eq!(2, inserted);
+
+    let conn = Connection::open(&path).unwrap();
+    let rows: Vec<(i64, i64, String, Option<String>)> = conn
+        .prepare("SELECT id, ok, code, note FROM items ORDER BY id")
+        .unwrap()
+        .query_map([], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+        })
+        .unwrap()
+        .collect::<rusqlite::Result<_>>()
+        .unwrap();
+    assert_eq!(
+        vec![
+            (1, 1, "007".to_string(), None),
+            (2, 0, "010".to_string(), Some("x".to_string()))
+        ],
+        rows
+    );
+    drop(conn);
+    std::fs::remove_file(path)?;
+    Ok(())
+}
+
+#[test]
+fn test_to_sqlite_if_exists() -> Result<()> {
+    let path = database_path("if-exists");
+    let load = |if_exists| {
+        ToSqlite::new()
+            .file_data("a\n1\n2\n")
+            .database_path(&path)
+            .if_exists(if_exists)
+            .execute()
+    };
+    assert_eq!(2, load(IfExists::Fail)?);
+    assert!(matches!(
+        load(IfExists::Fail),
+        Err(CzvError::InvalidOptions(_))
+    ));
+    assert_eq!(2, load(IfExists::Append)?);
+    let count = |path: &PathBuf| -> i64 {
+        Connection::open(path)
+            .unwrap()
+            .query_row("SELECT COUNT(*) FROM data", [], |row| row.get(0))
+            .unwrap()
+    };
+    assert_eq!(4, count(&path));
+    assert_eq!(2, load(IfExists::Replace)?);
+    assert_eq!(2, count(&path));
+    for file_data in ["b\n1\n", "a,b\n1,2\n"] {
+        assert!(matches!(
+            ToSqlite::new()
+                .file_data(file_data)
+                .database_path(&path)
+                .if_exists(IfExists::Append)
+                .execute(),
+            Err(CzvError::InvalidOptions(_))
+        ));
+    }
+    assert_eq!(2, count(&path));
+    std::fs::remove_file(path)?;
+    Ok(())
+}
+
+#[test]
+fn test_to_sqlite_errors() -> Result<()> {
+    let path = database_path("errors");
+    let report = ToSqlite::new()
+        .file_data("a,b\n1,x\n2\n3,y\n")
+        .database_path(&path)
+        .column_types([("a".to_string(), ColumnType::Integer)])
+        .batch_size(1)
+        .error_policy(ErrorPolicy::Collect)
+        .execute_with_report()?;
+    assert_eq!(2, report.output);
+    assert_eq!(1, report.skipped.len());
+
+    let got = ToSqlite::new()
+        .file_data("a\nx\n")
+        .database_path(&path)
+        .table_name("typed")
+        .column_types([("a".to_string(), ColumnType::Integer)])
+        .execute();
+    assert!(matches!(got, Err(CzvError::Parse { .. })));
+
+    let got = ToSqlite::new()
+        .file_data("a\n1\n")
+        .database_path(&path)
+        .indexes(["b".to_string()])
+        .execute();
+    assert!(matches!(got, Err(CzvError::ColumnNotFound(name)) if name == "b"));
+
+    let got = ToSqlite::new()
+        .file_data("a,a\n1,2\n")
+        .database_path(&path)
+        .table_name("duplicate")
+        .execute();
+    assert!(matches!(got, Err(CzvError::InvalidOptions(_))));
+
+    let got = ToSqlite::new().file_data("a\n1\n").execute();
+    assert!(matches!(got, Err(CzvError::InvalidOptions(_))));
+    std::fs::remove_file(path)?;
+    Ok(())
+}
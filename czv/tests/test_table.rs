@@ -0,0 +1,71 @@
+use czv::table::TableFormat;
+use czv::{CzvError, Encoding, ErrorPolicy, Result, Table};
+
+#[test]
+fn test_table_text() -> Result<()> {
+    let got = czv::table::table(
+        Some("tests/resources/fruits.csv".into()),
+        None,
+        TableFormat::Text,
+        None,
+        ErrorPolicy::Fail,
+        Encoding::Utf8,
+    )?;
+    assert_eq!(
+        "fruit       price\n----------  -----\napple        2.50\nbanana       3.00\nstrawberry   1.50\n",
+        got
+    );
+    Ok(())
+}
+
+#[test]
+fn test_table_markdown() -> Result<()> {
+    let data = "a,b,n\nx|y,\"two\nlines\",1\nz,,-2\n";
+    let got = Table::new()
+        .file_data(data)
+        .format(TableFormat::Markdown)
+        .execute()?;
+    assert_eq!(
+        "| a    | b         |   n |\n\
+         | ---- | --------- | --: |\n\
+         | x\\|y | two lines |   1 |\n\
+         | z    |           |  -2 |\n",
+        got
+    );
+    Ok(())
+}
+
+#[test]
+fn test_table_html() -> Result<()> {
+    let data = "name,score\n<b>Ana</b> & co,9.5\n";
+    let got = Table::new()
+        .file_data(data)
+        .format(TableFormat::Html)
+        .max_width(8)
+        .execute()?;
+    assert_eq!(
+        "<table>\n  <thead>\n    <tr><th>name</th><th style=\"text-align: right\">score</th></tr>\n  </thead>\n  <tbody>\n    <tr><td>&lt;b&gt;Ana&lt;…</td><td style=\"text-align: right\">9.5</td></tr>\n  </tbody>\n</table>\n",
+        got
+    );
+    Ok(())
+}
+
+#[test]
+fn test_table_options() -> Result<()> {
+    assert_eq!(TableFormat::Markdown, "Markdown".parse()?);
+    assert!(matches!(
+        "csv".parse::<TableFormat>(),
+        Err(CzvError::InvalidOptions(_))
+    ));
+
+    let zero = Table::new().file_data("a\n1\n").max_width(0).execute();
+    assert!(matches!(zero, Err(CzvError::InvalidOptions(_))));
+
+    let report = Table::new()
+        .file_data("a,b\n1\n2,3\n")
+        .error_policy(ErrorPolicy::Collect)
+        .execute_with_report()?;
+    assert_eq!("a  b\n-  -\n2  3\n", report.output);
+    assert_eq!(1, report.skipped.len());
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use czv::{Encoding, ErrorPolicy, Result};
+
+#[test]
+fn test_transpose() -> Result<()> {
+    let got = czv::transpose::transpose(
+        Some("tests/resources/fruits.csv".into()),
+        None,
+        false,
+        ErrorPolicy::Fail,
+        Encoding::Utf8,
+    )?;
+    let expected = "fruit,apple,banana,strawberry\nprice,2.50,3.00,1.50\n";
+    assert_eq!(expected, got);
+    Ok(())
+}
+
+#[test]
+fn test_transpose_builder_multipass() -> Result<()> {
+    let got = czv::Transpose::new()
+        .file_path("tests/resources/fruits.csv")
+        .multipass(true)
+        .execute()?;
+    let expected = "fruit,apple,banana,strawberry\nprice,2.50,3.00,1.50\n";
+    assert_eq!(expected, got);
+    Ok(())
+}
+
+#[test]
+fn test_transpose_to_writer() -> Result<()> {
+    let mut output = vec![];
+    let report = czv::Transpose::new()
+        .file_data("a,b\n1,2\n3\n")
+        .multipass(true)
+        .error_policy(ErrorPolicy::Collect)
+        .execute_to_writer(&mut output)?;
+    assert_eq!(b"a,1\nb,2\n".as_slice(), output);
+    assert_eq!(1, report.skipped.len());
+    Ok(())
+}
+
+#[test]
+fn test_transpose_multipass_matches_in_memory() -> Result<()> {
+    let data = "a,b,c\n1,\"x,y\",3\n4,5,6\n";
+    let in_memory = czv::Transpose::new().file_data(data).execute()?;
+    let multipass = czv::Transpose::new()
+        .file_data(data)
+        .multipass(true)
+        .execute()?;
+    assert_eq!("a,1,4\nb,\"x,y\",5\nc,3,6\n", in_memory);
+    assert_eq!(in_memory, multipass);
+    Ok(())
+}
+
+#[test]
+fn test_transpose_file_path_and_data() {
+    let got = czv::Transpose::new()
+        .file_path("tests/resources/fruits.csv")
+        .file_data("a,b")
+        .execute();
+    assert!(got.is_err());
+}
+
+#[test]
+fn test_transpose_error_policy() -> Result<()> {
+    let data = "a,b\n1,2\n3\n4,5\n";
+    for multipass in [false, true] {
+        let got = czv::Transpose::new()
+            .file_data(data)
+            .multipass(multipass)
+            .error_policy(ErrorPolicy::Collect)
+            .execute_with_report()?;
+        assert_eq!("a,1,4\nb,2,5\n", got.output);
+        assert_eq!(1, got.skipped.len());
+        assert_eq!(Some(2), got.skipped[0].record);
+    }
+    Ok(())
+}
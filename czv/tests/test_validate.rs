@@ -0,0 +1,87 @@
+use czv::validate::{ValidationProblem, ValidationProblemKind};
+use czv::{Encoding, Result};
+
+#[test]
+fn test_validate_valid() -> Result<()> {
+    let cases = vec![
+        "tests/resources/fruits.csv",
+        "tests/resources/constituents_altnames.csv",
+    ];
+    for file_name in cases {
+        let got = czv::validate::validate(Some(file_name.into()), None, Encoding::Utf8)?;
+        assert_eq!(Vec::<ValidationProblem>::new(), got);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_validate_unequal_lengths() -> Result<()> {
+    let data = "a,b\n1,2,3\n4\r\n5,6\n";
+    let got = czv::Validate::new().file_data(data).execute()?;
+    let expected = vec![
+        ValidationProblem {
+            kind: ValidationProblemKind::UnequalLengths {
+                expected: 2,
+                found: 3,
+            },
+            record: 1,
+            byte: 8,
+            column: Some(2),
+        },
+        ValidationProblem {
+            kind: ValidationProblemKind::UnequalLengths {
+                expected: 2,
+                found: 1,
+            },
+            record: 2,
+            byte: 10,
+            column: None,
+        },
+    ];
+    assert_eq!(expected, got);
+    Ok(())
+}
+
+#[test]
+fn test_validate_quotes() -> Result<()> {
+    let data = "a,b\n\"x\"\"y\",z\"\n\"x\"y,z\n1,\"open";
+    let got = czv::Validate::new().file_data(data).execute()?;
+    let kinds = got
+        .iter()
+        .map(|problem| (problem.record, problem.column, problem.kind.clone()))
+        .collect::<Vec<_>>();
+    let expected = vec![
+        (1, Some(1), ValidationProblemKind::StrayQuote),
+        (2, Some(0), ValidationProblemKind::UnexpectedAfterQuote),
+        (3, Some(1), ValidationProblemKind::UnterminatedQuote),
+    ];
+    assert_eq!(expected, kinds);
+    Ok(())
+}
+
+#[test]
+fn test_validate_duplicate_headers_and_utf8() -> Result<()> {
+    let mut file_path = std::env::temp_dir();
+    file_path.push("czv_test_validate_utf8.csv");
+    std::fs::write(&file_path, b"a,b,a\n1,\xff,3\n")?;
+    let got = czv::Validate::new().file_path(&file_path).execute()?;
+    std::fs::remove_file(&file_path)?;
+    let expected = vec![
+        ValidationProblem {
+            kind: ValidationProblemKind::DuplicateHeader {
+                name: "a".to_string(),
+            },
+            record: 0,
+            byte: 4,
+            column: Some(2),
+        },
+        ValidationProblem {
+            kind: ValidationProblemKind::InvalidUtf8,
+            record: 1,
+            byte: 8,
+            column: Some(1),
+        },
+    ];
+    assert_eq!(expected, got);
+    Ok(())
+}